@@ -1,7 +1,11 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 
+#[allow(clippy::approx_constant)]
 const NUMS: &[f64] = &[0., -69., 123406000., 0.1234, 2.718281828459045, 1.7976931348623157e308];
 
+/// The integers printed by the fast path of `format_general`, and the first one past it.
+const SMALL_INTS: &[f64] = &[1., 7., -9., 10.];
+
 fn benchmark_id(x: f64) -> BenchmarkId {
     BenchmarkId::from_parameter(ryu::Buffer::new().format(x))
 }
@@ -28,6 +32,29 @@ fn teju_general(c: &mut Criterion) {
     g.finish();
 }
 
+fn teju_general_small_int(c: &mut Criterion) {
+    let mut g = c.benchmark_group("teju_general_small_int");
+
+    for num in SMALL_INTS {
+        g.bench_with_input(benchmark_id(*num), num, |b, &num| {
+            b.iter(|| teju::Buffer::new().format_finite(black_box(num)).len() );
+        });
+    }
+    g.finish();
+}
+
+/// Comparable to `std`, whose `Display` also never uses scientific notation.
+fn teju_dec(c: &mut Criterion) {
+    let mut g = c.benchmark_group("teju_dec");
+
+    for num in NUMS {
+        g.bench_with_input(benchmark_id(*num), num, |b, &num| {
+            b.iter(|| teju::Buffer::new().format_dec_finite(black_box(num)).len() );
+        });
+    }
+    g.finish();
+}
+
 /*fn teju_core(c: &mut Criterion) {
     let mut g = c.benchmark_group("teju_core");
 
@@ -63,7 +90,7 @@ fn std(c: &mut Criterion) {
     g.finish();
 }
 
-criterion_group!(microbench, teju_general, teju_exp, ryu, std);
+criterion_group!(microbench, teju_general, teju_general_small_int, teju_exp, teju_dec, ryu, std);
 
 //
 
@@ -82,6 +109,12 @@ fn read_distribution_file(name: &str) -> Vec<f64> {
     }
 }
 
+/// An `f64` serialized by teju rather than by `serde_json`.
+#[cfg(feature = "serde_json")]
+#[derive(serde::Serialize)]
+#[serde(transparent)]
+struct TejuF64(#[serde(serialize_with = "teju::serde::serialize_json")] f64);
+
 fn benchmark_distribution_finite(c: &mut Criterion, name: &str) {
     let data = read_distribution_file(name);
     let mut g = c.benchmark_group(name);
@@ -93,6 +126,50 @@ fn benchmark_distribution_finite(c: &mut Criterion, name: &str) {
             }
         });
     });
+    g.bench_with_input(BenchmarkId::new("teju_dec", data.len()), &data.len(), |b, _| {
+        b.iter(|| {
+            for &i in &data {
+                let _ = teju::Buffer::new().format_dec_finite(black_box(i));
+            }
+        });
+    });
+    #[cfg(feature = "std")]
+    g.bench_with_input(BenchmarkId::new("teju_pooled", data.len()), &data.len(), |b, _| {
+        b.iter(|| {
+            for &i in &data {
+                let _ = teju::with_buffer(|buf| buf.format_finite(black_box(i)).len());
+            }
+        });
+    });
+    #[cfg(feature = "alloc")]
+    g.bench_with_input(BenchmarkId::new("teju_append", data.len()), &data.len(), |b, _| {
+        let mut str = String::with_capacity(32 * data.len());
+        b.iter(|| {
+            str.clear();
+            let mut buf = teju::Buffer::new();
+            for &i in &data {
+                buf.format_append(black_box(i), &mut str);
+            }
+            black_box(&str);
+        });
+    });
+    #[cfg(feature = "serde_json")]
+    {
+        let mut json = Vec::with_capacity(32 * data.len());
+        g.bench_with_input(BenchmarkId::new("serde_json", data.len()), &data.len(), |b, _| {
+            b.iter(|| {
+                json.clear();
+                serde_json::to_writer(&mut json, black_box(&data)).unwrap();
+            });
+        });
+        let nums: Vec<_> = data.iter().map(|&i| TejuF64(i)).collect();
+        g.bench_with_input(BenchmarkId::new("teju_serde_json", data.len()), &data.len(), |b, _| {
+            b.iter(|| {
+                json.clear();
+                serde_json::to_writer(&mut json, black_box(&nums)).unwrap();
+            });
+        });
+    }
     g.bench_with_input(BenchmarkId::new("ryu", data.len()), &data.len(), |b, _| {
         b.iter(|| {
             for &i in &data {
@@ -122,6 +199,33 @@ fn benchmark_distribution(c: &mut Criterion, name: &str) {
             }
         });
     });
+    g.bench_with_input(BenchmarkId::new("teju_dec", data.len()), &data.len(), |b, _| {
+        b.iter(|| {
+            for &i in &data {
+                let _ = teju::Buffer::new().format_dec(black_box(i));
+            }
+        });
+    });
+    #[cfg(feature = "std")]
+    g.bench_with_input(BenchmarkId::new("teju_pooled", data.len()), &data.len(), |b, _| {
+        b.iter(|| {
+            for &i in &data {
+                let _ = teju::with_buffer(|buf| buf.format(black_box(i)).len());
+            }
+        });
+    });
+    #[cfg(feature = "alloc")]
+    g.bench_with_input(BenchmarkId::new("teju_append", data.len()), &data.len(), |b, _| {
+        let mut str = String::with_capacity(32 * data.len());
+        b.iter(|| {
+            str.clear();
+            let mut buf = teju::Buffer::new();
+            for &i in &data {
+                buf.format_append(black_box(i), &mut str);
+            }
+            black_box(&str);
+        });
+    });
     g.bench_with_input(BenchmarkId::new("ryu", data.len()), &data.len(), |b, _| {
         b.iter(|| {
             for &i in &data {
@@ -164,6 +268,32 @@ fn int32(c: &mut Criterion) {
     benchmark_distribution_finite(c, "int32")
 }
 
+/// A synthetic time series where each value repeats 16 times, to compare `Buffer` with
+/// `CachingBuffer`.
+fn repetitive(c: &mut Criterion) {
+    let data: Vec<f64> = read_distribution_file("unit_gaussian_around_zero").into_iter()
+        .flat_map(|x| std::iter::repeat_n(x, 16))
+        .collect();
+    let mut g = c.benchmark_group("repetitive");
+    g.throughput(criterion::Throughput::Elements(data.len().try_into().unwrap()));
+    g.bench_with_input(BenchmarkId::new("teju", data.len()), &data.len(), |b, _| {
+        b.iter(|| {
+            let mut buf = teju::Buffer::new();
+            for &i in &data {
+                let _ = black_box(buf.format(black_box(i)));
+            }
+        });
+    });
+    #[cfg(feature = "caching-buffer")]
+    g.bench_with_input(BenchmarkId::new("teju_caching", data.len()), &data.len(), |b, _| {
+        b.iter(|| {
+            let mut buf = teju::CachingBuffer::new();
+            for &i in &data {
+                let _ = black_box(buf.format(black_box(i)));
+            }
+        });
+    });
+}
 
 criterion_group!(distributions,
     uniform_zero_to_one,
@@ -172,6 +302,7 @@ criterion_group!(distributions,
     pareto_fat_tail,
     poisson_very_large_mean,
     int32,
+    repetitive,
 );
 
 //
@@ -0,0 +1,120 @@
+//! Fixed-point notation with accounting-style negatives, as in `(1234.00)`.
+
+use crate::{Buffer, Float, RoundingMode, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Fixed> {
+    /// Print a floating point `num` into this buffer in fixed-point notation with exactly `scale`
+    /// digits after the decimal point, with negative numbers in parentheses instead of with a minus
+    /// sign, and return a reference to its string representation.
+    ///
+    /// The number is rounded as by [Self::format_fixed_scale]. Negative zero, and negative numbers
+    /// which round to zero, are printed as zero, without parentheses.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_accounting(-1234.0, 2), "(1234.00)");
+    /// assert_eq!(buffer.format_accounting(1234.0, 2), "1234.00");
+    /// assert_eq!(buffer.format_accounting(-0.001, 2), "0.00");
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `scale` is greater than 324, which is more digits than this buffer can hold.
+    pub fn format_accounting(&mut self, num: F, scale: u32) -> &str {
+        assert!(scale as usize <= format::MAX_PRECISION, "Scale must be at most {}", format::MAX_PRECISION);
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        let mut decimal = RoundingMode::HalfEven.round(num, scale as usize);
+        decimal.sign |= decimal.mant == 0;
+
+        let ptr = <format::Fixed as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            let n = decimal.format_fixed(ptr, scale as usize);
+            if decimal.sign {
+                n
+            } else {
+                // Replace the minus sign.
+                ptr.write(b'(');
+                ptr.add(n).write(b')');
+                n + 1
+            }
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn accounting() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_accounting(-1234.5f64, 2), "(1234.50)");
+        assert_eq!(buf.format_accounting(-9.996f64, 2), "(10.00)");
+        assert_eq!(buf.format_accounting(-2.5f64, 0), "(2)");
+        assert_eq!(buf.format_accounting(0.125f64, 2), "0.12");
+        assert_eq!(buf.format_accounting(-1e-3f64, 4), "(0.0010)");
+        assert_eq!(buf.format_accounting(-f64::MIN_POSITIVE, 324).len(), 2 + 2 + 323 + 1);
+        assert_eq!(Buffer::new().format_accounting(-1.5f32, 1), "(1.5)");
+    }
+
+    #[test]
+    fn zero() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_accounting(0.0f64, 2), "0.00");
+        assert_eq!(buf.format_accounting(-0.0f64, 2), "0.00");
+        assert_eq!(buf.format_accounting(-0.0f64, 0), "0");
+        assert_eq!(buf.format_accounting(-0.4f64, 0), "0");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_accounting(f64::NAN, 2), "NaN");
+        assert_eq!(buf.format_accounting(f64::INFINITY, 2), "inf");
+        assert_eq!(buf.format_accounting(f64::NEG_INFINITY, 2), "-inf");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        let str = buf.format_accounting(-f64::MAX, 324);
+        assert_eq!(str.len(), 1 + 309 + 1 + 324 + 1);
+        assert!(str.starts_with("(17976931348623157") && str.ends_with("0)"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_too_large() {
+        Buffer::new().format_accounting(1.5f64, 325);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_fixed_scale(bits in any::<u64>(), scale in 0u32 .. 30) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite());
+            let fixed = Buffer::new().format_fixed_scale(num, scale).to_owned();
+            let expected = match fixed.strip_prefix('-') {
+                Some(abs) if abs.bytes().any(|b| b.is_ascii_digit() && b != b'0') => format!("({abs})"),
+                Some(abs) => abs.to_owned(),
+                None => fixed,
+            };
+            assert_eq!(Buffer::new().format_accounting(num, scale), expected);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Decimal notation aligned on the decimal point, for columns of numbers.
+
+use crate::{Buffer, CapacityError, Float};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::Fixed> {
+    /// Print a floating point `num` into an array of `W` bytes with its decimal point at column
+    /// `int_width`, and return the array by value, e.g. for aligning the numbers of a table on
+    /// their decimal points. Returns an error if the integer part (with its sign) is longer than
+    /// `int_width` bytes.
+    ///
+    /// The number is rounded to the `W - int_width - 1` digits after the decimal point as by
+    /// [Self::format_round], trimming trailing zeros. The integer part is then right-justified in
+    /// the first `int_width` bytes, and the fractional part left-justified in the last
+    /// `W - int_width - 1`, both padded with spaces. If there are no digits after the decimal point
+    /// (as when `W - int_width - 1` is `0`), the decimal point is replaced by a space too.
+    ///
+    /// NaN and infinities are printed as `"NaN"`, `"inf"`, and `"-inf"`, right-justified in the
+    /// integer part.
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(&buffer.format_aligned::<8>(3.14159, 3).unwrap(), b"  3.1416");
+    /// assert_eq!(&buffer.format_aligned::<8>(-12.5, 3).unwrap(), b"-12.5   ");
+    /// assert_eq!(&buffer.format_aligned::<8>(f64::NAN, 3).unwrap(), b"NaN     ");
+    /// assert!(buffer.format_aligned::<8>(1234.5, 3).is_err());
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `int_width` is not less than `W`, i.e. if there is no room for the decimal
+    /// point.
+    pub fn format_aligned<const W: usize>(&mut self, num: F, int_width: usize) -> Result<[u8; W], CapacityError> {
+        assert!(int_width < W, "Integer width must leave room for the decimal point");
+        let frac_width = W - int_width - 1;
+        let str = self.format_round(num, frac_width, true);
+        let (int, frac) = str.split_once('.').unwrap_or((str, ""));
+        let start = int_width.checked_sub(int.len())
+            .ok_or(CapacityError { needed: int.len() + 1 + frac_width, have: W })?;
+
+        let mut aligned = [b' '; W];
+        aligned[start .. int_width].copy_from_slice(int.as_bytes());
+        if !frac.is_empty() {
+            aligned[int_width] = b'.';
+            aligned[int_width + 1 .. int_width + 1 + frac.len()].copy_from_slice(frac.as_bytes());
+        }
+        Ok(aligned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn aligned() {
+        let mut buf = Buffer::new();
+        assert_eq!(&buf.format_aligned::<10>(1.5f64, 4).unwrap(), b"   1.5    ");
+        assert_eq!(&buf.format_aligned::<10>(-1234.0f64, 5).unwrap(), b"-1234.0   ");
+        assert_eq!(&buf.format_aligned::<10>(0.001234f64, 1).unwrap(), b"0.001234  ");
+        assert_eq!(&buf.format_aligned::<8>(0.999999f64, 2).unwrap(), b" 1.0    ");
+        assert_eq!(&buf.format_aligned::<4>(2.5f64, 3).unwrap(), b"  2 ");
+        assert_eq!(&buf.format_aligned::<4>(3.5f64, 3).unwrap(), b"  4 ");
+        assert_eq!(buf.format_aligned::<1>(3.0f64, 0), Err(CapacityError { needed: 2, have: 1 }));
+        assert_eq!(&Buffer::new().format_aligned::<8>(0.1f32, 3).unwrap(), b"  0.1   ");
+    }
+
+    #[test]
+    fn too_wide() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_aligned::<8>(1234.5f64, 3), Err(CapacityError { needed: 9, have: 8 }));
+        assert_eq!(buf.format_aligned::<8>(-123.5f64, 3), Err(CapacityError { needed: 9, have: 8 }));
+        assert_eq!(buf.format_aligned::<8>(999.99996f64, 3), Err(CapacityError { needed: 9, have: 8 }));
+        assert_eq!(buf.format_aligned::<6>(f64::NEG_INFINITY, 3), Err(CapacityError { needed: 7, have: 6 }));
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(&buf.format_aligned::<6>(0.0f64, 2).unwrap(), b" 0.0  ");
+        assert_eq!(&buf.format_aligned::<6>(-0.0f64, 2).unwrap(), b"-0.0  ");
+        assert_eq!(&buf.format_aligned::<6>(f64::INFINITY, 3).unwrap(), b"inf   ");
+        assert_eq!(&buf.format_aligned::<6>(f64::NEG_INFINITY, 4).unwrap(), b"-inf  ");
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_point() {
+        let _ = Buffer::new().format_aligned::<4>(1.5f64, 4);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_round(bits in any::<u64>(), int_width in 0usize .. 16) {
+            let num = f64::from_bits(bits);
+            let rounded = Buffer::new().format_round(num, 15 - int_width, true).to_owned();
+            match Buffer::new().format_aligned::<16>(num, int_width) {
+                Ok(aligned) => {
+                    let aligned = core::str::from_utf8(&aligned).unwrap();
+                    assert_eq!(aligned.replace(' ', ""), rounded);
+                    let point = aligned.as_bytes()[int_width];
+                    assert!(point == b'.' || (point == b' ' && !rounded.contains('.')), "{aligned}");
+                },
+                Err(_) => assert!(rounded.split('.').next().unwrap().len() > int_width),
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! A buffer for formatting floats of any type.
+
+use core::mem::MaybeUninit;
+use crate::{Float, teju};
+use teju::format;
+
+/// Safe API for formatting both `f32`s and `f64`s to text with one buffer, as
+/// [Buffer::format](crate::Buffer::format) does.
+///
+/// A [Buffer](crate::Buffer) is specific to one float type, so code which handles several needs a
+/// buffer for each; this one is large enough for any of them.
+///
+/// ## Example
+///
+/// ```
+/// let mut buffer = teju::AnyFloatBuffer::new();
+/// assert_eq!(buffer.format_f32(0.1), "0.1");
+/// assert_eq!(buffer.format_f64(0.1), "0.1");
+/// assert_eq!(buffer.format_f64(f64::MAX), "1.7976931348623157e308");
+/// ```
+#[derive(Clone, Copy)]
+pub struct AnyFloatBuffer {
+    bytes: [MaybeUninit<u8>; format::LEN_EXP],
+}
+
+impl AnyFloatBuffer {
+    /// This is a cheap operation; you don't need to worry about reusing buffers for efficiency.
+    pub fn new() -> Self {
+        AnyFloatBuffer { bytes: [MaybeUninit::uninit(); format::LEN_EXP] }
+    }
+
+    /// Print an `f32` into this buffer as [Buffer::format](crate::Buffer::format) does, and return
+    /// a reference to its string representation.
+    pub fn format_f32(&mut self, num: f32) -> &str {
+        self.format(num)
+    }
+
+    /// Print an `f64` into this buffer as [Buffer::format](crate::Buffer::format) does, and return
+    /// a reference to its string representation.
+    pub fn format_f64(&mut self, num: f64) -> &str {
+        self.format(num)
+    }
+
+    fn format<F: Float>(&mut self, num: F) -> &str {
+        // The general format needs at most this buffer size for every float type.
+        const { assert!(format::LEN_EXP >= <<format::General as format::Sealed>::Buffer<F> as format::Bytes>::LEN) };
+        let ptr = self.bytes.as_mut_ptr() as *mut u8;
+        let n = unsafe { crate::write_general(num, ptr) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= self.bytes.len());
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+/// Prints only the type, as the contents are uninitialised or left over from previous calls.
+impl core::fmt::Debug for AnyFloatBuffer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AnyFloatBuffer").finish_non_exhaustive()
+    }
+}
+
+impl Default for AnyFloatBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", AnyFloatBuffer::new()), "AnyFloatBuffer { .. }");
+    }
+
+    #[test]
+    fn mixed() {
+        let mut buf = AnyFloatBuffer::new();
+        assert_eq!(buf.format_f64(-2.2250738585072014e-308), "-2.2250738585072014e-308");
+        assert_eq!(buf.format_f32(-1.1754944e-38), "-1.1754944e-38");
+        assert_eq!(buf.format_f64(-0.0), "-0.0");
+        assert_eq!(buf.format_f32(f32::NAN), "NaN");
+        assert_eq!(buf.format_f64(f64::INFINITY), "inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_buffer(bits in any::<u64>()) {
+            let mut buf = AnyFloatBuffer::new();
+            let (x, y) = (f64::from_bits(bits), f32::from_bits(bits as u32));
+            assert_eq!(buf.format_f64(x), crate::Buffer::new().format(x));
+            assert_eq!(buf.format_f32(y), crate::Buffer::new().format(y));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Appending to a [`String`].
+
+use alloc::string::String;
+
+use crate::{Buffer, Float};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Like [Self::format], but appends the string to `str` instead of returning it, reusing its
+    /// capacity. Returns the number of bytes appended.
+    ///
+    /// ```
+    /// let mut line = String::new();
+    /// let mut buffer = teju::Buffer::new();
+    /// for num in [1.5, f64::NAN, 1e30] {
+    ///     buffer.format_append(num, &mut line);
+    ///     line.push(' ');
+    /// }
+    /// assert_eq!(line, "1.5 NaN 1e30 ");
+    /// ```
+    pub fn format_append(&mut self, num: F, str: &mut String) -> usize {
+        let formatted = self.format(num);
+        str.push_str(formatted);
+        formatted.len()
+    }
+}
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// Like [Self::format_exp], but appends the string to `str` instead of returning it, reusing
+    /// its capacity. Returns the number of bytes appended.
+    ///
+    /// ```
+    /// let mut line = String::from("x = ");
+    /// teju::Buffer::new().format_exp_append(1500.0, &mut line);
+    /// assert_eq!(line, "x = 1.5e3");
+    /// ```
+    pub fn format_exp_append(&mut self, num: F, str: &mut String) -> usize {
+        let formatted = self.format_exp(num);
+        str.push_str(formatted);
+        formatted.len()
+    }
+}
+
+impl<F: Float> Buffer<F, format::Decimal> {
+    /// Like [Self::format_dec], but appends the string to `str` instead of returning it, reusing
+    /// its capacity. Returns the number of bytes appended.
+    ///
+    /// ```
+    /// let mut line = String::from("x = ");
+    /// teju::Buffer::new().format_dec_append(1e3, &mut line);
+    /// assert_eq!(line, "x = 1000.0");
+    /// ```
+    pub fn format_dec_append(&mut self, num: F, str: &mut String) -> usize {
+        let formatted = self.format_dec(num);
+        str.push_str(formatted);
+        formatted.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append() {
+        let mut str = String::with_capacity(64);
+        let capacity = str.capacity();
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_append(-1e-7f64, &mut str), 5);
+        assert_eq!(buf.format_append(0.1f64, &mut str), 3);
+        assert_eq!(Buffer::new().format_exp_append(1500.0f64, &mut str), 5);
+        assert_eq!(Buffer::new().format_dec_append(1e3f32, &mut str), 6);
+        assert_eq!(str, "-1e-70.11.5e31000.0");
+        assert_eq!(str.capacity(), capacity);
+    }
+
+    #[test]
+    fn specials() {
+        let mut str = String::new();
+        Buffer::new().format_append(f64::NAN, &mut str);
+        Buffer::new().format_exp_append(f64::NEG_INFINITY, &mut str);
+        Buffer::new().format_dec_append(-0.0f64, &mut str);
+        assert_eq!(str, "NaN-inf-0.0");
+    }
+}
@@ -0,0 +1,92 @@
+//! Decimal mantissas with binary exponents, as `mant * 2^exp`.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// Print a floating point `num` into this buffer as an integer mantissa and a binary exponent,
+    /// `{mant}p{exp}` for `mant * 2^exp`, and return a reference to its string representation.
+    ///
+    /// This is exact, as it is read directly from the bits of `num`, with no decimal conversion.
+    /// The mantissa is odd, i.e. the smallest integer for which there is such an exponent.
+    ///
+    /// This function formats zero as `"0p0"`, NaN as the string `"NaN"`, positive infinity as
+    /// `"inf"`, and negative infinity as `"-inf"`.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_bin_exp(1.5), "3p-1");
+    /// assert_eq!(teju::Buffer::new().format_bin_exp(-1024.0), "-1p10");
+    /// assert_eq!(teju::Buffer::new().format_bin_exp(0.1), "3602879701896397p-55");
+    /// assert_eq!(teju::Buffer::new().format_bin_exp(5e-324), "1p-1074");
+    /// ```
+    pub fn format_bin_exp(&mut self, num: F) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return "0p0",
+            teju::float::FiniteFloatType::NegZero => return "-0p0",
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let ptr = <format::Scientific as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { num.format_bin_exp_finite_nonzero(ptr) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Parse `{mant}p{exp}` back to an `f64`, exactly.
+    fn parse(str: &str) -> f64 {
+        let (mant, exp) = str.split_once('p').unwrap();
+        let (mant, exp) = (mant.parse::<i64>().unwrap() as f64, exp.parse::<i32>().unwrap());
+        // `2^exp` may not be representable, so multiply by `2^(exp/2)` twice.
+        let pow2 = |k: i32| f64::from_bits(((k + 1023) as u64) << 52);
+        mant * pow2(exp / 2) * pow2(exp - exp / 2)
+    }
+
+    #[test]
+    fn bin_exp() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_bin_exp(1.0f64), "1p0");
+        assert_eq!(buf.format_bin_exp(0.75f64), "3p-2");
+        assert_eq!(buf.format_bin_exp(-6.0f64), "-3p1");
+        assert_eq!(buf.format_bin_exp(f64::MAX), "9007199254740991p971");
+        assert_eq!(buf.format_bin_exp(f64::MIN_POSITIVE), "1p-1022");
+        assert_eq!(buf.format_bin_exp(-f64::from_bits(0x000f_ffff_ffff_ffff)), "-4503599627370495p-1074");
+        assert_eq!(Buffer::new().format_bin_exp(0.1f32), "13421773p-27");
+        assert_eq!(Buffer::new().format_bin_exp(f32::from_bits(1)), "1p-149");
+        assert_eq!(Buffer::new().format_bin_exp(f32::MAX), "16777215p104");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_bin_exp(0.0f64), "0p0");
+        assert_eq!(buf.format_bin_exp(-0.0f64), "-0p0");
+        assert_eq!(buf.format_bin_exp(f64::NAN), "NaN");
+        assert_eq!(buf.format_bin_exp(f64::NEG_INFINITY), "-inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100_000))]
+
+        #[test]
+        fn exact(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite());
+            let str = Buffer::new().format_bin_exp(num).to_owned();
+            assert_eq!(parse(&str).to_bits(), bits, "{str}");
+            let mant = str.split_once('p').unwrap().0.trim_start_matches('-');
+            assert!(mant == "0" || mant.ends_with(['1', '3', '5', '7', '9']), "{str}");
+        }
+    }
+}
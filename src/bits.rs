@@ -0,0 +1,164 @@
+//! Formatting which only inspects the bit representation of floats.
+//!
+//! The regular methods classify numbers (as NaN, infinite, zero...) with floating point
+//! operations, which some targets implement in ways that are not bit-for-bit reproducible. The
+//! methods here do so with integer operations on [`to_bits`](f64::to_bits), so that the output
+//! only depends on the bits of the input, on every platform. Their output is otherwise identical
+//! to that of the corresponding regular methods.
+//!
+//! Neither kind of method is affected by targets which flush subnormal numbers to zero: the
+//! regular methods only compare floats with infinity, which gives the same result either way, and
+//! read the digits from the bits. However, on such targets, subnormal numbers may already have
+//! been flushed to zero by the computations which produced them, before being formatted; see
+//! [subnormals_supported].
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// Check whether this target computes with subnormal numbers, i.e. whether arithmetic can produce
+/// them and they are kept when used as operands, rather than flushed to zero (as with e.g. the
+/// FTZ and DAZ modes of x86, or the FZ mode of ARM). This is meant to be checked once at startup.
+///
+/// Formatting works either way, but if this returns `false`, numbers smaller than
+/// [f64::MIN_POSITIVE] (or [f32::MIN_POSITIVE]) are likely to have become `0.0` before reaching
+/// [teju](crate).
+///
+/// ```
+/// assert!(teju::subnormals_supported());
+/// ```
+pub fn subnormals_supported() -> bool {
+    use core::hint::black_box;
+    // Producing a subnormal result (flushed by FTZ), and using a subnormal operand (flushed by DAZ).
+    let produced = black_box(f64::MIN_POSITIVE) / black_box(2.0);
+    let used = black_box(f64::from_bits(1)) * black_box(1.0);
+    let produced_f32 = black_box(f32::MIN_POSITIVE) / black_box(2.0);
+    let used_f32 = black_box(f32::from_bits(1)) * black_box(1.0);
+    produced.to_bits() == f64::MIN_POSITIVE.to_bits() >> 1
+        && used.to_bits() == 1
+        && produced_f32.to_bits() == f32::MIN_POSITIVE.to_bits() >> 1
+        && used_f32.to_bits() == 1
+}
+
+impl<F: Float> Buffer<F, format::General> {
+    /// As [Self::format], but classifying `num` only by its bit representation.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_bits(3.14159), "3.14159");
+    /// assert_eq!(teju::Buffer::new().format_bits(f64::from_bits(0x7ff0_0000_0000_0001)), "NaN");
+    /// ```
+    pub fn format_bits(&mut self, num: F) -> &str {
+        match num.classify_bits() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite_bits() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { num.format_general_finite_nonzero(ptr) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// As [Self::format_exp], but classifying `num` only by its bit representation.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_bits_exp(-1e-7), "-1e-7");
+    /// ```
+    pub fn format_bits_exp(&mut self, num: F) -> &str {
+        match num.classify_bits() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite_bits() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO_EXP,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO_EXP,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let ptr = <format::Scientific as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { num.format_exp_finite_nonzero(ptr) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+impl<F: Float> Buffer<F, format::Decimal> {
+    /// As [Self::format_dec], but classifying `num` only by its bit representation.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_bits_dec(1e7), "10000000.0");
+    /// ```
+    pub fn format_bits_dec(&mut self, num: F) -> &str {
+        match num.classify_bits() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite_bits() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let ptr = <format::Decimal as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { num.format_dec_finite_nonzero(ptr) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn subnormals() {
+        assert!(subnormals_supported());
+        assert_eq!(Buffer::new().format_bits(f64::MIN_POSITIVE / 2.0), "1.1125369292536007e-308");
+    }
+
+    #[test]
+    fn specials() {
+        for bits in [0x7ff0_0000_0000_0001, 0xfff8_0000_0000_0000, 0x7fff_ffff_ffff_ffff] {
+            assert_eq!(Buffer::new().format_bits(f64::from_bits(bits)), "NaN");
+        }
+        assert_eq!(Buffer::new().format_bits(f64::INFINITY), "inf");
+        assert_eq!(Buffer::new().format_bits_exp(f64::NEG_INFINITY), "-inf");
+        assert_eq!(Buffer::new().format_bits_dec(-0.0f64), "-0.0");
+        assert_eq!(Buffer::new().format_bits_exp(0.0f32), "0e0");
+        assert_eq!(Buffer::new().format_bits(f32::from_bits(0xff80_0001)), "NaN");
+        assert_eq!(Buffer::new().format_bits(f32::NEG_INFINITY), "-inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_classify_f64(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            assert_eq!(Buffer::new().format_bits(num), Buffer::new().format(num));
+            assert_eq!(Buffer::new().format_bits_exp(num), Buffer::new().format_exp(num));
+            assert_eq!(Buffer::new().format_bits_dec(num), Buffer::new().format_dec(num));
+        }
+
+        #[test]
+        fn same_as_classify_f32(bits in any::<u32>()) {
+            let num = f32::from_bits(bits);
+            assert_eq!(Buffer::new().format_bits(num), Buffer::new().format(num));
+            assert_eq!(Buffer::new().format_bits_exp(num), Buffer::new().format_exp(num));
+            assert_eq!(Buffer::new().format_bits_dec(num), Buffer::new().format_dec(num));
+        }
+    }
+}
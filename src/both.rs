@@ -0,0 +1,93 @@
+//! General and scientific notation from a single conversion.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Both> {
+    /// Print a floating point `num` into this buffer in both general and scientific notation, and
+    /// return references to the two string representations, in that order.
+    ///
+    /// The strings are the same as those of [Buffer::format] and [Buffer::format_exp], but the
+    /// shortest decimal representation of `num` is only computed once.
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_both(1500.0), ("1500.0", "1.5e3"));
+    /// assert_eq!(buffer.format_both(-1e-7), ("-1e-7", "-1e-7"));
+    /// ```
+    pub fn format_both(&mut self, num: F) -> (&str, &str) {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return (crate::POS_INF, crate::POS_INF),
+            teju::float::FloatType::NegInf => return (crate::NEG_INF, crate::NEG_INF),
+            teju::float::FloatType::Nan => return (crate::NAN, crate::NAN),
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return (crate::POS_ZERO, crate::POS_ZERO_EXP),
+            teju::float::FiniteFloatType::NegZero => return (crate::NEG_ZERO, crate::NEG_ZERO_EXP),
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+
+        let ptr = <format::Both as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let (general_len, exp_len) = unsafe {
+            let decimal = num.to_decimal_finite();
+            let general_len = F::format_general_decimal(decimal, ptr);
+            (general_len, F::format_exp_decimal(decimal, ptr.add(general_len)))
+        };
+        debug_assert!(general_len + exp_len <= Self::MAX_LEN);
+        let (general, exp) = unsafe {
+            let general = core::slice::from_raw_parts(ptr, general_len);
+            let exp = core::slice::from_raw_parts(ptr.add(general_len), exp_len);
+            (core::str::from_utf8_unchecked(general), core::str::from_utf8_unchecked(exp))
+        };
+        debug_assert!(general.is_ascii() && exp.is_ascii());
+        (general, exp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn both() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_both(1.0f64), ("1.0", "1e0"));
+        assert_eq!(buf.format_both(-0.001234f64), ("-0.001234", "-1.234e-3"));
+        assert_eq!(buf.format_both(1e16f64), ("1e16", "1e16"));
+        assert_eq!(buf.format_both(f64::MAX), ("1.7976931348623157e308", "1.7976931348623157e308"));
+        assert_eq!(Buffer::new().format_both(-f32::MIN_POSITIVE), ("-1.1754944e-38", "-1.1754944e-38"));
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_both(0.0f64), ("0.0", "0e0"));
+        assert_eq!(buf.format_both(-0.0f64), ("-0.0", "-0e0"));
+        assert_eq!(buf.format_both(f64::NAN), ("NaN", "NaN"));
+        assert_eq!(buf.format_both(f64::NEG_INFINITY), ("-inf", "-inf"));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_format_and_format_exp(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let mut buf = Buffer::new();
+            let (general, exp) = buf.format_both(num);
+            assert_eq!(general, Buffer::new().format(num));
+            assert_eq!(exp, Buffer::new().format_exp(num));
+        }
+
+        #[test]
+        fn same_as_format_and_format_exp_f32(bits in any::<u32>()) {
+            let num = f32::from_bits(bits);
+            let mut buf = Buffer::new();
+            let (general, exp) = buf.format_both(num);
+            assert_eq!(general, Buffer::new().format(num));
+            assert_eq!(exp, Buffer::new().format_exp(num));
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Formatting into a [`bytes::BufMut`].
+
+use bytes::BufMut;
+
+use crate::{Buffer, Float};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Like [Self::format], but appends the string to `buf` instead of returning it. Returns the
+    /// number of bytes appended.
+    ///
+    /// ```
+    /// let mut frame = bytes::BytesMut::new();
+    /// let mut buffer = teju::Buffer::new();
+    /// buffer.format_to_buf(1.5, &mut frame);
+    /// buffer.format_to_buf(f64::NAN, &mut frame);
+    /// assert_eq!(&frame[..], b"1.5NaN");
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `buf` has less remaining capacity than the length of the string, as
+    /// [BufMut::put_slice] does.
+    pub fn format_to_buf<B: BufMut>(&mut self, num: F, buf: &mut B) -> usize {
+        let str = self.format(num);
+        buf.put_slice(str.as_bytes());
+        str.len()
+    }
+}
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// Like [Self::format_exp], but appends the string to `buf` instead of returning it. Returns
+    /// the number of bytes appended.
+    ///
+    /// ```
+    /// let mut frame = bytes::BytesMut::new();
+    /// teju::Buffer::new().format_exp_to_buf(1e30, &mut frame);
+    /// assert_eq!(&frame[..], b"1e30");
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `buf` has less remaining capacity than the length of the string, as
+    /// [BufMut::put_slice] does.
+    pub fn format_exp_to_buf<B: BufMut>(&mut self, num: F, buf: &mut B) -> usize {
+        let str = self.format_exp(num);
+        buf.put_slice(str.as_bytes());
+        str.len()
+    }
+}
+
+impl<F: Float> Buffer<F, format::Decimal> {
+    /// Like [Self::format_dec], but appends the string to `buf` instead of returning it. Returns
+    /// the number of bytes appended.
+    ///
+    /// ```
+    /// let mut frame = bytes::BytesMut::new();
+    /// teju::Buffer::new().format_dec_to_buf(1e3, &mut frame);
+    /// assert_eq!(&frame[..], b"1000.0");
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `buf` has less remaining capacity than the length of the string, as
+    /// [BufMut::put_slice] does.
+    pub fn format_dec_to_buf<B: BufMut>(&mut self, num: F, buf: &mut B) -> usize {
+        let str = self.format_dec(num);
+        buf.put_slice(str.as_bytes());
+        str.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends() {
+        let mut frame = bytes::BytesMut::new();
+        let mut buffer = Buffer::new();
+        for num in [1.5f64, -0.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, 1e-7] {
+            assert_eq!(buffer.format_to_buf(num, &mut frame), Buffer::new().format(num).len());
+            frame.put_u8(b',');
+        }
+        assert_eq!(&frame[..], b"1.5,-0.0,inf,-inf,NaN,1e-7,");
+    }
+
+    #[test]
+    fn all_formats() {
+        let mut frame = Vec::new();
+        Buffer::new().format_to_buf(1e30f32, &mut frame);
+        Buffer::new().format_exp_to_buf(0.0f32, &mut frame);
+        Buffer::new().format_dec_to_buf(-1e-3f32, &mut frame);
+        assert_eq!(frame, b"1e300e0-0.001");
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_short() {
+        let mut out = [0u8; 3];
+        Buffer::new().format_dec_to_buf(1e30f64, &mut out.as_mut_slice());
+    }
+}
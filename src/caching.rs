@@ -0,0 +1,120 @@
+//! A buffer which remembers the last number it formatted.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// Like a [Buffer] for [Buffer::format], but remembers the last number it formatted, and returns
+/// the same string without formatting it again if the next one is identical.
+///
+/// Numbers are compared by their bit representation, so e.g. `0.0` and `-0.0` are different
+/// numbers, and so are NaNs with different payloads.
+///
+/// This only pays off on streams where the same value often repeats consecutively, e.g. some
+/// time series; otherwise it is slightly slower than a [Buffer]. Requires the `caching-buffer`
+/// feature.
+///
+/// ## Example
+///
+/// ```
+/// let mut buffer = teju::CachingBuffer::new();
+/// assert_eq!(buffer.format(1.5), "1.5");
+/// assert_eq!(buffer.format(1.5), "1.5");
+/// assert_eq!(buffer.format(-0.0), "-0.0");
+/// ```
+pub struct CachingBuffer<F: Float> {
+    buffer: Buffer<F, format::General>,
+    /// Bits of the last number formatted, and the length of its string in `buffer`.
+    last: Option<(u64, usize)>,
+}
+
+impl<F: Float> CachingBuffer<F> {
+    /// This is a cheap operation, but a new buffer has nothing cached.
+    pub fn new() -> Self {
+        CachingBuffer { buffer: Buffer::new(), last: None }
+    }
+
+    /// Print a floating point `num` into this buffer, as [Buffer::format], and return a reference
+    /// to its string representation. If `num` has the same bits as the last number formatted,
+    /// the same string is returned without formatting it again.
+    pub fn format(&mut self, num: F) -> &str {
+        let bits = num.to_bits_u64();
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.buffer.bytes);
+        let n = match self.last {
+            Some((last_bits, n)) if last_bits == bits => n,
+            _ => {
+                let n = unsafe { crate::write_general(num, ptr) };
+                self.last = Some((bits, n));
+                n
+            }
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Buffer::<F, format::General>::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+/// Prints only the type, as the contents are uninitialised or left over from previous calls.
+impl<F: Float> core::fmt::Debug for CachingBuffer<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "CachingBuffer<{}> {{ .. }}", core::any::type_name::<F>())
+    }
+}
+
+impl<F: Float> Default for CachingBuffer<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn repeated() {
+        let mut buf = CachingBuffer::new();
+        assert_eq!(buf.format(1.5f64), "1.5");
+        assert_eq!(buf.format(1.5f64), "1.5");
+        assert_eq!(buf.format(2.5f64), "2.5");
+        assert_eq!(buf.format(2.5f64), "2.5");
+        assert_eq!(buf.format(1.5f64), "1.5");
+    }
+
+    #[test]
+    fn signed_zero() {
+        let mut buf = CachingBuffer::new();
+        assert_eq!(buf.format(0.0f64), "0.0");
+        assert_eq!(buf.format(-0.0f64), "-0.0");
+        assert_eq!(buf.format(0.0f64), "0.0");
+        assert_eq!(CachingBuffer::new().format(-0.0f32), "-0.0");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = CachingBuffer::new();
+        assert_eq!(buf.format(f64::NAN), "NaN");
+        assert_eq!(buf.format(f64::NAN), "NaN");
+        assert_eq!(buf.format(f64::INFINITY), "inf");
+        assert_eq!(buf.format(f64::NEG_INFINITY), "-inf");
+        assert_eq!(buf.format(1e300f64), "1e300");
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", CachingBuffer::<f32>::new()), "CachingBuffer<f32> { .. }");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_buffer(a in any::<u64>(), b in any::<u64>(), repeat in any::<bool>()) {
+            let (a, b) = (f64::from_bits(a), f64::from_bits(b));
+            let b = if repeat { a } else { b };
+            let mut buf = CachingBuffer::new();
+            assert_eq!(buf.format(a), Buffer::new().format(a));
+            assert_eq!(buf.format(b), Buffer::new().format(b));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! General notation, with sentinels for magnitudes outside a range.
+
+use crate::{Buffer, Float, teju};
+use teju::common::SignedDecimal;
+use teju::float::abs_bits;
+use teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer as [Self::format] does, unless its magnitude
+    /// is greater than that of `hi` or less than that of `lo`, in which case a sentinel is printed
+    /// instead, and return a reference to its string representation.
+    ///
+    /// The sentinels are `">{hi}"` and `"<{lo}"` for positive numbers, and `"<-{hi}"` and
+    /// `">-{lo}"` for negative numbers, with the bound in scientific notation as by
+    /// [Buffer::format_exp]. Zero is never out of range. The magnitudes are compared directly,
+    /// without formatting `num` first, and the signs of `lo` and `hi` are ignored.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_clamped(1234.5, 1e-9, 1e9), "1234.5");
+    /// assert_eq!(teju::Buffer::new().format_clamped(2e9, 1e-9, 1e9), ">1e9");
+    /// assert_eq!(teju::Buffer::new().format_clamped(-2e9, 1e-9, 1e9), "<-1e9");
+    /// assert_eq!(teju::Buffer::new().format_clamped(1e-10, 1e-9, 1e9), "<1e-9");
+    /// assert_eq!(teju::Buffer::new().format_clamped(1e9, 1e-9, 1e9), "1000000000.0");
+    /// assert_eq!(teju::Buffer::new().format_clamped(-0.02, 0.05, 1.5), ">-5e-2");
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `lo` or `hi` is not finite, if `hi` is zero, or if the magnitude of `lo` is greater
+    /// than that of `hi`.
+    pub fn format_clamped(&mut self, num: F, lo: F, hi: F) -> &str {
+        assert!(
+            matches!(lo.classify(), teju::float::FloatType::Finite)
+                && matches!(hi.classify(), teju::float::FloatType::Finite)
+                && abs_bits(lo) <= abs_bits(hi) && abs_bits(hi) != 0,
+            "Bounds must be finite, with `|lo| <= |hi|` and `hi` nonzero",
+        );
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+
+        let (bound, beyond) = if abs_bits(num) > abs_bits(hi) {
+            (hi, true)
+        } else if abs_bits(num) < abs_bits(lo) {
+            (lo, false)
+        } else {
+            let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+            let n = unsafe { F::format_general_decimal(num.to_decimal_finite(), ptr) };
+            let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+            debug_assert!(n <= Self::MAX_LEN);
+            return unsafe { core::str::from_utf8_unchecked(slice) }
+        };
+
+        // `>1e9`, `<-1e9`, `<1e-9`, `>-1e-9`: the bound with the sign of `num`, after a `<` or `>`.
+        let positive = !num.is_sign_negative();
+        let decimal = SignedDecimal { sign: positive, ..unsafe { bound.to_decimal_finite() } };
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            let n = F::format_exp_decimal(decimal, ptr);
+            core::ptr::copy(ptr, ptr.add(1), n);
+            *ptr = if beyond == positive {b'>'} else {b'<'};
+            n + 1
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn sentinels() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_clamped(1.5e9f64, 1e-9, 1e9), ">1e9");
+        assert_eq!(buf.format_clamped(1.0000000000000002e9f64, 1e-9, 1e9), ">1e9");
+        assert_eq!(buf.format_clamped(-1.5e9f64, 1e-9, 1e9), "<-1e9");
+        assert_eq!(buf.format_clamped(9.99e-10f64, 1e-9, 1e9), "<1e-9");
+        assert_eq!(buf.format_clamped(-9.99e-10f64, 1e-9, 1e9), ">-1e-9");
+        assert_eq!(buf.format_clamped(2.5f64, 0.5, 2.0), ">2e0");
+        assert_eq!(buf.format_clamped(-1.25e-3f64, 1.5e-3, 7.25e3), ">-1.5e-3");
+        assert_eq!(buf.format_clamped(f64::MAX, 5e-324, f64::MAX), "1.7976931348623157e308");
+        assert_eq!(buf.format_clamped(5e-324f64, 1e-300, 1e300), "<1e-300");
+        assert_eq!(Buffer::new().format_clamped(1e30f32, 1.0, 1.0), ">1e0");
+    }
+
+    #[test]
+    fn bounds_inclusive() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_clamped(1e9f64, 1e-9, 1e9), "1000000000.0");
+        assert_eq!(buf.format_clamped(-1e9f64, 1e-9, 1e9), "-1000000000.0");
+        assert_eq!(buf.format_clamped(1e-9f64, 1e-9, 1e9), "1e-9");
+        assert_eq!(buf.format_clamped(1.0f64, 1.0, 1.0), "1.0");
+        assert_eq!(buf.format_clamped(5e-324f64, 0.0, 1.0), "5e-324");
+    }
+
+    #[test]
+    fn signs_of_bounds_ignored() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_clamped(2e9f64, -1e-9, -1e9), ">1e9");
+        assert_eq!(buf.format_clamped(-1e-10f64, -1e-9, 1e9), ">-1e-9");
+        assert_eq!(buf.format_clamped(-1.5f64, -0.0, -1e9), "-1.5");
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(Buffer::new().format_clamped(0.0f64, 1.0, 2.0), "0.0");
+        assert_eq!(Buffer::new().format_clamped(-0.0f64, 1.0, 2.0), "-0.0");
+        assert_eq!(Buffer::new().format_clamped(f64::NAN, 1.0, 2.0), "NaN");
+        assert_eq!(Buffer::new().format_clamped(f64::INFINITY, 1.0, 2.0), "inf");
+    }
+
+    #[test]
+    #[should_panic]
+    fn bad_range() {
+        Buffer::new().format_clamped(1.0f64, 2.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bad_bound() {
+        Buffer::new().format_clamped(1.0f64, 1.0, f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_hi() {
+        Buffer::new().format_clamped(1.0f64, 0.0, 0.0);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn in_range_same_as_format(bits in any::<u64>(), lo in 0.0 .. 1.0f64, hi in 1.0 .. f64::MAX) {
+            let num = f64::from_bits(bits);
+            let str = Buffer::new().format_clamped(num, lo, hi).to_owned();
+            if !num.is_finite() || num == 0.0 || (lo <= num.abs() && num.abs() <= hi) {
+                assert_eq!(str, Buffer::new().format(num));
+            } else {
+                let bound = if num.abs() > hi { hi } else { lo };
+                let sign = if num < 0.0 { "-" } else { "" };
+                assert_eq!(&str[1 ..], format!("{sign}{}", Buffer::new().format_exp(bound)));
+                assert_eq!(str.starts_with('>'), (num.abs() > hi) == (num > 0.0), "{str}");
+            }
+        }
+    }
+}
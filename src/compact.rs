@@ -0,0 +1,202 @@
+//! The shortest of decimal and scientific notation, within a length budget.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Decimal> {
+    /// Print a floating point `num` into this buffer in the most compact form, and return a
+    /// reference to its string representation.
+    ///
+    /// The number is written in decimal notation if that takes at most `budget` bytes, or if it
+    /// is no longer than scientific notation; otherwise in scientific notation, as by
+    /// [Self::format_exp]. Unlike [Self::format_dec], integers are written without a trailing
+    /// `.0`, e.g. `"1500"` rather than `"1500.0"`.
+    ///
+    /// This function formats zero as `"0"`, NaN as the string `"NaN"`, positive infinity as
+    /// `"inf"`, and negative infinity as `"-inf"`.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_compact(1500.0, 6), "1500");
+    /// assert_eq!(teju::Buffer::new().format_compact(1.5e7, 6), "1.5e7");
+    /// assert_eq!(teju::Buffer::new().format_compact(0.000125, 7), "1.25e-4");
+    /// assert_eq!(teju::Buffer::new().format_compact(0.000125, 8), "0.000125");
+    /// assert_eq!(teju::Buffer::new().format_compact(123456789.0, 0), "123456789");
+    /// ```
+    pub fn format_compact(&mut self, num: F, budget: usize) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return "0",
+            teju::float::FiniteFloatType::NegZero => return "-0",
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+
+        let decimal = unsafe { num.to_decimal_finite() }.trim_trailing_zeros();
+        let (dec_len, sci_len) = format::notation_lens(decimal);
+
+        let ptr = <format::Decimal as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = if dec_len <= budget || dec_len <= sci_len {
+            let precision = (-decimal.exp).max(0) as usize;
+            unsafe { decimal.format_fixed(ptr, precision) }
+        } else {
+            unsafe { num.format_exp_finite_nonzero(ptr) }
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+
+    /// Print a floating point `num` into this buffer as the shorter of [Self::format_dec] and
+    /// [Self::format_exp], preferring [Self::format_dec] if they have the same length, and return
+    /// a reference to its string representation.
+    ///
+    /// Unlike [Self::format_compact], the output is always exactly one of those, e.g. integers are
+    /// written with a trailing `.0`.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_shortest_string(1e9), "1e9");
+    /// assert_eq!(teju::Buffer::new().format_shortest_string(1234.0), "1234.0");
+    /// assert_eq!(teju::Buffer::new().format_shortest_string(15000.0), "1.5e4");
+    /// assert_eq!(teju::Buffer::new().format_shortest_string(0.00125), "0.00125");
+    /// ```
+    pub fn format_shortest_string(&mut self, num: F) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+
+        let decimal = unsafe { num.to_decimal_finite() }.trim_trailing_zeros();
+        let (dec_len, sci_len) = format::notation_lens(decimal);
+        // `format_dec` writes integers with a trailing `.0`.
+        let dec_len = dec_len + 2 * (decimal.exp >= 0) as usize;
+
+        let ptr = <format::Decimal as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            if dec_len <= sci_len {
+                num.format_dec_finite_nonzero(ptr)
+            } else {
+                num.format_exp_finite_nonzero(ptr)
+            }
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn magnitudes() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_compact(1.0f64, 8), "1");
+        assert_eq!(buf.format_compact(-2.5f64, 8), "-2.5");
+        assert_eq!(buf.format_compact(1e7f64, 8), "10000000");
+        assert_eq!(buf.format_compact(1e8f64, 8), "1e8");
+        assert_eq!(buf.format_compact(123456789.0f64, 8), "123456789");
+        assert_eq!(buf.format_compact(1234567890.0f64, 8), "1234567890");
+        assert_eq!(buf.format_compact(1.2345e14f64, 8), "1.2345e14");
+        assert_eq!(buf.format_compact(0.5f64, 8), "0.5");
+        assert_eq!(buf.format_compact(0.000001f64, 8), "0.000001");
+        assert_eq!(buf.format_compact(0.0000001f64, 8), "1e-7");
+        assert_eq!(buf.format_compact(-1.25e-300f64, 8), "-1.25e-300");
+        assert_eq!(buf.format_compact(f64::MAX, 8), "1.7976931348623157e308");
+        assert_eq!(Buffer::new().format_compact(1e10f32, 8), "1e10");
+    }
+
+    #[test]
+    fn budget_boundary() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_compact(-150000.0f64, 7), "-150000");
+        assert_eq!(buf.format_compact(-150000.0f64, 6), "-1.5e5");
+        assert_eq!(buf.format_compact(0.000125f64, 8), "0.000125");
+        assert_eq!(buf.format_compact(0.000125f64, 7), "1.25e-4");
+        // Ties go to decimal.
+        assert_eq!(buf.format_compact(-15000.0f64, 0), "-15000");
+        assert_eq!(buf.format_compact(0.00125f64, 0), "0.00125");
+        // Decimal is used regardless of the budget if it is no longer.
+        assert_eq!(buf.format_compact(100.0f64, 0), "100");
+        assert_eq!(buf.format_compact(1000.0f64, 0), "1e3");
+        assert_eq!(buf.format_compact(0.001f64, 0), "1e-3");
+        assert_eq!(buf.format_compact(0.01f64, 0), "0.01");
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(Buffer::new().format_compact(0.0f64, 8), "0");
+        assert_eq!(Buffer::new().format_compact(-0.0f64, 8), "-0");
+        assert_eq!(Buffer::new().format_compact(f64::NAN, 8), "NaN");
+        assert_eq!(Buffer::new().format_compact(f64::NEG_INFINITY, 8), "-inf");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_compact(-f64::MAX, usize::MAX), Buffer::new().format_dec(-f64::MAX).trim_end_matches(".0"));
+        assert_eq!(buf.format_compact(5e-324f64, usize::MAX), Buffer::new().format_dec(5e-324f64));
+    }
+
+    #[test]
+    fn shortest_string() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_shortest_string(1e9f64), "1e9");
+        assert_eq!(buf.format_shortest_string(1000.0f64), "1e3");
+        assert_eq!(buf.format_shortest_string(100.0f64), "1e2");
+        assert_eq!(buf.format_shortest_string(-1234.0f64), "-1234.0");
+        // Ties go to decimal.
+        assert_eq!(buf.format_shortest_string(1.0f64), "1.0");
+        assert_eq!(buf.format_shortest_string(-0.01f64), "-0.01");
+        assert_eq!(buf.format_shortest_string(0.001f64), "1e-3");
+        assert_eq!(buf.format_shortest_string(0.00125f64), "0.00125");
+        assert_eq!(buf.format_shortest_string(123.456f64), "123.456");
+        assert_eq!(buf.format_shortest_string(-0.0f64), "-0.0");
+        assert_eq!(buf.format_shortest_string(f64::NAN), "NaN");
+        assert_eq!(Buffer::new().format_shortest_string(1e-45f32), "1e-45");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn shortest_string_of_dec_and_exp(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let str = Buffer::new().format_shortest_string(num).to_owned();
+            let dec = Buffer::new().format_dec(num).to_owned();
+            let exp = Buffer::new().format_exp(num).to_owned();
+            assert_eq!(str, if dec.len() <= exp.len() { dec } else { exp });
+        }
+
+        #[test]
+        fn shortest_within_budget(bits in any::<u64>(), budget in 0usize .. 30) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite() && num != 0.0);
+            let str = Buffer::new().format_compact(num, budget).to_owned();
+            let dec = Buffer::new().format_dec(num).to_owned();
+            let dec = dec.strip_suffix(".0").unwrap_or(&dec);
+            let exp = Buffer::new().format_exp(num).to_owned();
+            assert_eq!(str.parse::<f64>().unwrap(), num);
+            if dec.len() <= budget || dec.len() <= exp.len() {
+                assert_eq!(str, dec);
+            } else {
+                assert_eq!(str, exp);
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Complex numbers, as `re+imi`.
+
+use num_complex::Complex;
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Complex> {
+    /// Print a complex number `z` into this buffer and return a reference to its string
+    /// representation.
+    ///
+    /// Both parts are formatted as by [Buffer::format], joined by the sign of the imaginary part,
+    /// and followed by `i`. Both parts are always printed, even if zero, and the sign of a zero
+    /// imaginary part is kept. A NaN imaginary part is written as `+NaNi`.
+    ///
+    /// Requires the `num-complex` feature.
+    ///
+    /// ```
+    /// use num_complex::Complex;
+    /// assert_eq!(teju::Buffer::new().format_complex(Complex::new(3.0, 4.5)), "3.0+4.5i");
+    /// assert_eq!(teju::Buffer::new().format_complex(Complex::new(-1e-7, -2.0)), "-1e-7-2.0i");
+    /// assert_eq!(teju::Buffer::new().format_complex(Complex::new(1.0, 0.0)), "1.0+0.0i");
+    /// ```
+    pub fn format_complex(&mut self, z: Complex<F>) -> &str {
+        let im_negative = z.im.is_sign_negative() && !matches!(z.im.classify(), teju::float::FloatType::Nan);
+        let ptr = <format::Complex as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            let mut n = crate::write_general(z.re, ptr);
+            if !im_negative {
+                ptr.add(n).write(b'+');
+                n += 1;
+            }
+            n += crate::write_general(z.im, ptr.add(n));
+            ptr.add(n).write(b'i');
+            n + 1
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn signs() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_complex(Complex::new(1.5f64, 2.0)), "1.5+2.0i");
+        assert_eq!(buf.format_complex(Complex::new(1.5f64, -2.0)), "1.5-2.0i");
+        assert_eq!(buf.format_complex(Complex::new(-1.5f64, 2.0)), "-1.5+2.0i");
+        assert_eq!(buf.format_complex(Complex::new(0.0f64, 0.0)), "0.0+0.0i");
+        assert_eq!(buf.format_complex(Complex::new(0.0f64, -0.0)), "0.0-0.0i");
+        assert_eq!(Buffer::new().format_complex(Complex::new(0.1f32, -0.2)), "0.1-0.2i");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_complex(Complex::new(f64::NAN, f64::NAN)), "NaN+NaNi");
+        assert_eq!(buf.format_complex(Complex::new(1.0, -f64::NAN)), "1.0+NaNi");
+        assert_eq!(buf.format_complex(Complex::new(f64::INFINITY, f64::NEG_INFINITY)), "inf-infi");
+        assert_eq!(buf.format_complex(Complex::new(f64::NEG_INFINITY, f64::INFINITY)), "-inf+infi");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        assert_eq!(
+            buf.format_complex(Complex::new(-2.2250738585072014e-308, -1.7976931348623157e-300)),
+            "-2.2250738585072014e-308-1.7976931348623157e-300i",
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn parts(re in any::<u64>(), im in any::<u64>()) {
+            let (re, im) = (f64::from_bits(re), f64::from_bits(im));
+            let str = Buffer::new().format_complex(Complex::new(re, im)).to_owned();
+            let re_str = Buffer::new().format(re).to_owned();
+            let im_str = Buffer::new().format(im).to_owned();
+            let rest = str.strip_prefix(&re_str).unwrap().strip_suffix('i').unwrap();
+            let rest = rest.strip_prefix('+').unwrap_or(rest);
+            assert_eq!(rest, im_str);
+        }
+    }
+}
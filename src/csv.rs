@@ -0,0 +1,62 @@
+//! CSV fields.
+
+use core::fmt;
+
+use crate::{Buffer, Float, Specials};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Write a floating point `num` to `out` as a CSV field, without quotes or a delimiter.
+    ///
+    /// Finite numbers are formatted as by [Self::format], which never needs quoting. NaN is
+    /// written as an empty field, and infinities as `pos_inf` and `neg_inf`, since CSV has no
+    /// standard representation for them. These are written as given, so they should not contain
+    /// the delimiter, quotes, or line breaks.
+    ///
+    /// ```
+    /// let mut line = String::new();
+    /// let mut buffer = teju::Buffer::new();
+    /// for num in [1.5, f64::NAN, f64::INFINITY] {
+    ///     buffer.format_csv_field(num, "inf", "-inf", &mut line).unwrap();
+    ///     line.push(',');
+    /// }
+    /// assert_eq!(line, "1.5,,inf,");
+    /// ```
+    pub fn format_csv_field<W: fmt::Write>(&mut self, num: F, pos_inf: &str, neg_inf: &str, out: &mut W) -> fmt::Result {
+        out.write_str(self.format_specials(num, Specials { nan: "", pos_inf, neg_inf }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv() {
+        let mut out = String::new();
+        let mut buf = Buffer::new();
+        for num in [1e-7, -0.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e300] {
+            buf.format_csv_field(num, "Infinity", "-Infinity", &mut out).unwrap();
+            out.push(';');
+        }
+        assert_eq!(out, "1e-7;-0.0;;Infinity;-Infinity;1e300;");
+
+        let mut out = String::new();
+        Buffer::new().format_csv_field(-0.5f32, "", "", &mut out).unwrap();
+        Buffer::new().format_csv_field(f32::NEG_INFINITY, "", "", &mut out).unwrap();
+        assert_eq!(out, "-0.5");
+    }
+
+    #[test]
+    fn error() {
+        struct Full;
+
+        impl fmt::Write for Full {
+            fn write_str(&mut self, _: &str) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+
+        assert_eq!(Buffer::new().format_csv_field(1.5f64, "inf", "-inf", &mut Full), Err(fmt::Error));
+    }
+}
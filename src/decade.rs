@@ -0,0 +1,99 @@
+//! Decimal notation with the same length for every number of the same order of magnitude.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Fixed> {
+    /// Print a floating point `num` into this buffer in decimal notation with exactly `frac_digits`
+    /// digits after the decimal point, truncating any further digits, and return a reference to
+    /// its string representation.
+    ///
+    /// Unlike [Self::format_round], digits are truncated rather than rounded, so the integer part
+    /// is exactly that of `num`, and is never carried into (e.g. `9.999` is not printed as
+    /// `10.00`). So all numbers of the same sign in each decade `[10^k, 10^(k+1))`, or `[0, 1)`,
+    /// are printed with the same length, for aligning in fixed-width columns. If `frac_digits` is
+    /// `0`, no decimal point is printed.
+    ///
+    /// `frac_digits` is capped at 324: no `f64` has significant digits past that.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_decade_aligned(1.5, 3), "1.500");
+    /// assert_eq!(buffer.format_decade_aligned(9.9999, 3), "9.999");
+    /// assert_eq!(buffer.format_decade_aligned(12.25, 3), "12.250");
+    /// ```
+    pub fn format_decade_aligned(&mut self, num: F, frac_digits: usize) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        let frac_digits = frac_digits.min(format::MAX_PRECISION);
+        let decimal = unsafe { num.to_decimal_finite() }.truncate(frac_digits);
+
+        let ptr = <format::Fixed as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { decimal.format_fixed(ptr, frac_digits) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn decade_aligned() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_decade_aligned(1.0f64, 2), "1.00");
+        assert_eq!(buf.format_decade_aligned(9.999f64, 2), "9.99");
+        assert_eq!(buf.format_decade_aligned(-9.999f64, 2), "-9.99");
+        assert_eq!(buf.format_decade_aligned(10.0f64, 2), "10.00");
+        assert_eq!(buf.format_decade_aligned(0.999f64, 2), "0.99");
+        assert_eq!(buf.format_decade_aligned(0.001f64, 2), "0.00");
+        assert_eq!(buf.format_decade_aligned(-0.001f64, 2), "-0.00");
+        assert_eq!(buf.format_decade_aligned(99.9f64, 0), "99");
+        assert_eq!(buf.format_decade_aligned(1e20f64, 1), "100000000000000000000.0");
+        assert_eq!(Buffer::new().format_decade_aligned(0.1f32, 3), "0.100");
+    }
+
+    #[test]
+    fn same_decade_same_length() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_decade_aligned(100.0f64, 4).len(), buf.format_decade_aligned(999.99999f64, 4).len());
+        assert_eq!(buf.format_decade_aligned(0.0f64, 4).len(), buf.format_decade_aligned(0.99999f64, 4).len());
+        assert_eq!(buf.format_decade_aligned(1e-300f64, 4).len(), buf.format_decade_aligned(0.5f64, 4).len());
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_decade_aligned(-0.0f64, 2), "-0.00");
+        assert_eq!(buf.format_decade_aligned(f64::NAN, 2), "NaN");
+        assert_eq!(buf.format_decade_aligned(f64::NEG_INFINITY, 2), "-inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn length_by_decade(bits in any::<u64>(), frac_digits in 0usize .. 30) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite());
+            // The integer part is that of the shortest representation, so depends only on the
+            // decade.
+            let shortest = Buffer::new().format_dec(num).to_owned();
+            let int = shortest.split('.').next().unwrap();
+            let expected = format!("{int}.{:0<frac_digits$}", "");
+            let str = Buffer::new().format_decade_aligned(num, frac_digits).to_owned();
+            assert_eq!(str.len(), expected.trim_end_matches('.').len(), "{str}");
+            assert!(str.starts_with(int), "{str}");
+        }
+    }
+}
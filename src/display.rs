@@ -0,0 +1,61 @@
+//! A [Display](core::fmt::Display) adapter, for using floats in `format!` strings.
+
+use core::fmt;
+
+use crate::{Float, format_into_formatter};
+
+/// A float which implements [Display](core::fmt::Display) by formatting it as by
+/// [Buffer::format](crate::Buffer::format), as returned by [display].
+///
+/// The formatter's flags, e.g. width and precision, are honoured as by [format_into_formatter],
+/// which caps the precision at 324 digits.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, PartialOrd)]
+pub struct Displayable<F: Float>(pub F);
+
+/// Wrap a floating point `num` so that it can be printed with `{}` in `format!` strings and
+/// other [Display](core::fmt::Display)-based code, in the same format as by
+/// [Buffer::format](crate::Buffer::format).
+///
+/// ```
+/// assert_eq!(format!("value = {}", teju::display(1.5)), "value = 1.5");
+/// assert_eq!(format!("{}", teju::display(1e30)), "1e30");
+/// assert_eq!(teju::display(f32::NAN).to_string(), "NaN");
+/// assert_eq!(format!("{:>8.2}", teju::display(-0.5)), "   -0.50");
+/// ```
+pub fn display<F: Float>(num: F) -> Displayable<F> {
+    Displayable(num)
+}
+
+impl<F: Float> fmt::Display for Displayable<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format_into_formatter(self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+    use proptest::prelude::*;
+
+    #[test]
+    fn flags() {
+        assert_eq!(format!("{:8.3}", display(-0.5f64)), "  -0.500");
+        assert_eq!(format!("{:+}", display(0.1f32)), "+0.1");
+        assert_eq!(format!("{:<6}", display(f64::NEG_INFINITY)), "-inf  ");
+        assert_eq!(format!("{}", display(f64::NEG_INFINITY)), "-inf");
+        assert_eq!(format!("{:.400}", display(0.1f64)).len(), 2 + 324);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_format(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            assert_eq!(display(num).to_string(), Buffer::new().format(num));
+        }
+    }
+}
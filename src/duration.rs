@@ -0,0 +1,97 @@
+//! Durations in seconds, with an SI time prefix.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// Units, and the power of ten by which seconds are multiplied to convert to each, from largest to
+/// smallest. A duration is written in the first unit in which it is at least 1.
+const UNITS: [(&str, i32); 4] = [("s", 0), ("ms", 3), ("µs", 6), ("ns", 9)];
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a duration of `secs` seconds into this buffer, in the largest of `s`, `ms`, `µs`, and
+    /// `ns` in which it is at least 1, and return a reference to its string representation.
+    ///
+    /// The number is formatted as by [Self::format], but without a trailing `.0`, and followed by
+    /// the unit. Converting between units is exact, as it only moves the decimal point. Durations
+    /// of less than 1 ns are written in ns, and very long or very short durations are written in
+    /// scientific notation, as by [Self::format].
+    ///
+    /// This function formats zero as `"0s"`, NaN as the string `"NaN"`, positive infinity as
+    /// `"inf"`, and negative infinity as `"-inf"`.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_duration_secs(0.0000012), "1.2µs");
+    /// assert_eq!(teju::Buffer::new().format_duration_secs(0.25), "250ms");
+    /// assert_eq!(teju::Buffer::new().format_duration_secs(90.5), "90.5s");
+    /// assert_eq!(teju::Buffer::new().format_duration_secs(0.0), "0s");
+    /// assert_eq!(teju::Buffer::new().format_duration_secs(1e-15), "1e-6ns");
+    /// ```
+    pub fn format_duration_secs(&mut self, secs: F) -> &str {
+        match secs.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match secs.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return "0s",
+            teju::float::FiniteFloatType::NegZero => return "-0s",
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+
+        let mut decimal = unsafe { secs.to_decimal_finite() };
+        let decimal_exp = teju::fmt::len_u64(decimal.mant) as i32 + decimal.exp;
+        let (unit, shift) = UNITS.into_iter()
+            .find(|&(_, shift)| decimal_exp + shift > 0)
+            .unwrap_or(UNITS[UNITS.len() - 1]);
+        decimal.exp += shift;
+
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            let mut n = F::format_general_decimal(decimal, ptr);
+            if core::slice::from_raw_parts(ptr, n).ends_with(b".0") {
+                n -= 2;
+            }
+            n + teju::fmt::write_str(unit, ptr.add(n))
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_duration_secs(1.0f64), "1s");
+        assert_eq!(buf.format_duration_secs(0.999f64), "999ms");
+        assert_eq!(buf.format_duration_secs(0.001f64), "1ms");
+        assert_eq!(buf.format_duration_secs(0.0009995f64), "999.5µs");
+        assert_eq!(buf.format_duration_secs(1e-6f64), "1µs");
+        assert_eq!(buf.format_duration_secs(1.5e-9f64), "1.5ns");
+        assert_eq!(buf.format_duration_secs(-0.0123f64), "-12.3ms");
+        assert_eq!(buf.format_duration_secs(3600.0f64), "3600s");
+        assert_eq!(Buffer::new().format_duration_secs(0.1f32), "100ms");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_duration_secs(1e-10f64), "0.1ns");
+        assert_eq!(buf.format_duration_secs(5e-324f64), "5e-315ns");
+        assert_eq!(buf.format_duration_secs(f64::MAX), "1.7976931348623157e308s");
+        assert_eq!(buf.format_duration_secs(-2.2250738585072014e-308f64), "-2.2250738585072014e-299ns");
+        assert_eq!(Buffer::new().format_duration_secs(f32::from_bits(1)), "1e-36ns");
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(Buffer::new().format_duration_secs(-0.0f64), "-0s");
+        assert_eq!(Buffer::new().format_duration_secs(f64::NAN), "NaN");
+        assert_eq!(Buffer::new().format_duration_secs(f64::NEG_INFINITY), "-inf");
+    }
+}
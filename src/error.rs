@@ -0,0 +1,37 @@
+//! Errors of the methods writing to caller-provided storage.
+
+/// The storage passed to a method writing its output there, e.g. [Buffer::format_to_slice], is
+/// too short for the output.
+///
+/// [Buffer::format_to_slice]: crate::Buffer::format_to_slice
+///
+/// ```
+/// let mut out = [0u8; 4];
+/// let err = teju::Buffer::new().format_to_slice(1.2345, &mut out).unwrap_err();
+/// assert_eq!(err, teju::CapacityError { needed: 6, have: 4 });
+/// assert_eq!(err.to_string(), "output of 6 bytes does not fit in 4 bytes");
+/// ```
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub struct CapacityError {
+    /// Length of the output.
+    pub needed: usize,
+    /// Length of the storage.
+    pub have: usize,
+}
+
+impl core::fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "output of {} bytes does not fit in {} bytes", self.needed, self.have)
+    }
+}
+
+impl core::error::Error for CapacityError {}
+
+/// For using the fallible methods in [core::fmt::Write] and [core::fmt::Display] impls.
+impl From<CapacityError> for core::fmt::Error {
+    fn from(_: CapacityError) -> Self {
+        core::fmt::Error
+    }
+}
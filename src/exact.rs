@@ -0,0 +1,185 @@
+//! The exact decimal value of a float, with all of its digits.
+//!
+//! Unlike the rest of the crate this needs the `alloc` feature: the exact value of a float has up
+//! to 767 significant digits, which are computed with a small base-`10^9` bignum kept in a [Vec],
+//! and returned as a [String]. No fixed-width arithmetic is enough, as `mant * 5^k` for the
+//! smallest subnormals alone is over 2400 bits long.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{Float, teju};
+
+/// Print the exact value of a floating point `num` in decimal notation, with all of its digits,
+/// and return it as a new [String].
+///
+/// Every finite float is an integer times a power of two, and so has a finite decimal expansion.
+/// This is usually much longer than the shortest representation printed by
+/// [Buffer::format_dec](crate::Buffer::format_dec), which only has enough digits to identify
+/// `num`: e.g. for `0.1` it is `0.1000000000000000055511151231257827021181583404541015625`, and
+/// for the smallest subnormal `f64` it has 751 significant digits. Integers are printed with a
+/// trailing `.0`.
+///
+/// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+/// infinity as `"-inf"`, to match [core::fmt].
+///
+/// ```
+/// assert_eq!(teju::format_exact(0.1), "0.1000000000000000055511151231257827021181583404541015625");
+/// assert_eq!(teju::format_exact(-0.75), "-0.75");
+/// assert_eq!(teju::format_exact(1e23), "99999999999999991611392.0");
+/// assert_eq!(teju::format_exact(0.1f32), "0.100000001490116119384765625");
+/// ```
+pub fn format_exact<F: Float>(num: F) -> String {
+    match num.classify() {
+        teju::float::FloatType::Finite => (),
+        teju::float::FloatType::PosInf => return String::from(crate::POS_INF),
+        teju::float::FloatType::NegInf => return String::from(crate::NEG_INF),
+        teju::float::FloatType::Nan => return String::from(crate::NAN),
+    }
+    match num.classify_finite() {
+        teju::float::FiniteFloatType::PosZero => return String::from(crate::POS_ZERO),
+        teju::float::FiniteFloatType::NegZero => return String::from(crate::NEG_ZERO),
+        teju::float::FiniteFloatType::Nonzero => (),
+    }
+
+    // `mant * 2^exp` is an integer if `exp >= 0`, and `mant * 5^-exp / 10^-exp` otherwise.
+    let (mant, exp) = unsafe { num.to_binary_finite_nonzero() };
+    let mut limbs = Limbs::new(mant);
+    let frac_len = if exp >= 0 {
+        limbs.mul_pow(2, exp as u32);
+        0
+    } else {
+        limbs.mul_pow(5, exp.unsigned_abs());
+        exp.unsigned_abs() as usize
+    };
+    let digits = limbs.to_digits();
+
+    let mut str = String::with_capacity(1 + digits.len().max(frac_len) + 3);
+    if num.is_sign_negative() {
+        str.push('-');
+    }
+    if digits.len() > frac_len {
+        let (int, frac) = digits.split_at(digits.len() - frac_len);
+        str.push_str(int);
+        str.push('.');
+        str.push_str(if frac.is_empty() { "0" } else { frac });
+    } else {
+        str.push_str("0.");
+        str.extend(core::iter::repeat_n('0', frac_len - digits.len()));
+        str.push_str(&digits);
+    }
+    str
+}
+
+/// An arbitrarily large unsigned integer, as base-`10^9` limbs from least to most significant.
+struct Limbs(Vec<u32>);
+
+impl Limbs {
+    const BASE: u64 = 1_000_000_000;
+
+    fn new(x: u64) -> Self {
+        let mut limbs = Vec::new();
+        let mut x = x;
+        while x != 0 {
+            limbs.push((x % Self::BASE) as u32);
+            x /= Self::BASE;
+        }
+        Limbs(limbs)
+    }
+
+    /// Multiply by `m`, which must be less than `2^32`.
+    fn mul_small(&mut self, m: u32) {
+        let mut carry = 0;
+        for limb in &mut self.0 {
+            let x = *limb as u64 * m as u64 + carry;
+            *limb = (x % Self::BASE) as u32;
+            carry = x / Self::BASE;
+        }
+        while carry != 0 {
+            self.0.push((carry % Self::BASE) as u32);
+            carry /= Self::BASE;
+        }
+    }
+
+    /// Multiply by `base^n`, for `base` 2 or 5, in chunks of the largest power of `base` which
+    /// fits in a `u32`.
+    fn mul_pow(&mut self, base: u32, mut n: u32) {
+        let chunk = if base == 2 { 31 } else { 13 };
+        while n >= chunk {
+            self.mul_small(base.pow(chunk));
+            n -= chunk;
+        }
+        self.mul_small(base.pow(n));
+    }
+
+    /// The decimal digits, without leading zeros.
+    fn to_digits(&self) -> String {
+        let mut str = String::with_capacity(9 * self.0.len());
+        let mut limbs = self.0.iter().rev();
+        if let Some(first) = limbs.next() {
+            let _ = write!(str, "{first}");
+        }
+        for limb in limbs {
+            let _ = write!(str, "{limb:09}");
+        }
+        str
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// The exact value of `num` as printed by `core::fmt`, which prints exact digits when asked for
+    /// enough of them.
+    fn std_exact(num: f64) -> String {
+        let str = format!("{num:.1100}");
+        let str = str.trim_end_matches('0');
+        if str.ends_with('.') { format!("{str}0") } else { str.to_owned() }
+    }
+
+    #[test]
+    fn exact() {
+        assert_eq!(format_exact(1.0f64), "1.0");
+        assert_eq!(format_exact(-1.5f64), "-1.5");
+        assert_eq!(format_exact(0.3f64), "0.299999999999999988897769753748434595763683319091796875");
+        assert_eq!(format_exact(2f64.powi(64)), "18446744073709551616.0");
+        assert_eq!(format_exact(2f64.powi(-3)), "0.125");
+        assert_eq!(format_exact(1e15f64 + 0.5), "1000000000000000.5");
+        assert_eq!(format_exact(f32::MAX), "340282346638528859811704183484516925440.0");
+        assert_eq!(format_exact(f32::from_bits(1)), format!("{:.149}", f32::from_bits(1)));
+    }
+
+    #[test]
+    fn extremes() {
+        let max = format_exact(f64::MAX);
+        assert!(max.starts_with("1797693134862315708145274237317043567980") && max.ends_with("858368.0"));
+        assert_eq!(max.len(), 309 + 2);
+        let min = format_exact(-5e-324f64);
+        assert!(min.starts_with(&format!("-0.{}4940656458412465441765687928682213723650", "0".repeat(323))));
+        assert!(min.ends_with("5625"));
+        assert_eq!(min.len(), 1 + 2 + 323 + 751);
+        assert_eq!(min, std_exact(-5e-324));
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(format_exact(0.0f64), "0.0");
+        assert_eq!(format_exact(-0.0f64), "-0.0");
+        assert_eq!(format_exact(f64::NAN), "NaN");
+        assert_eq!(format_exact(f64::INFINITY), "inf");
+        assert_eq!(format_exact(f64::NEG_INFINITY), "-inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_std(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite());
+            assert_eq!(format_exact(num), std_exact(num));
+        }
+    }
+}
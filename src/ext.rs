@@ -0,0 +1,95 @@
+//! Formatting methods on the float types themselves.
+
+use arrayvec::ArrayString;
+
+use crate::{Buffer, Float};
+use crate::teju::format;
+
+/// Extension trait for formatting floats with method call syntax, e.g. `1.5.teju()`.
+///
+/// Each method returns an owned `ArrayString`, as [Buffer::format_owned] and friends do. Requires
+/// the `arrayvec` feature.
+///
+/// ```
+/// use teju::FloatExt;
+/// assert_eq!(1.234.teju().as_str(), "1.234");
+/// assert_eq!(1e-7f32.teju_exp().as_str(), "1e-7");
+/// assert_eq!(1e30.teju_dec().as_str(), "1000000000000000000000000000000.0");
+/// ```
+pub trait FloatExt: Float {
+    /// Format as [Buffer::format].
+    fn teju(self) -> ArrayString<{format::LEN_EXP}>;
+    /// Format as [Buffer::format_exp].
+    fn teju_exp(self) -> ArrayString<{format::LEN_EXP}>;
+    /// Format as [Buffer::format_dec].
+    fn teju_dec(self) -> ArrayString<{format::LEN_DEC}>;
+}
+
+impl<F: Float> FloatExt for F {
+    fn teju(self) -> ArrayString<{format::LEN_EXP}> {
+        Buffer::new().format_owned(self)
+    }
+
+    fn teju_exp(self) -> ArrayString<{format::LEN_EXP}> {
+        Buffer::new().format_exp_owned(self)
+    }
+
+    fn teju_dec(self) -> ArrayString<{format::LEN_DEC}> {
+        Buffer::new().format_dec_owned(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn teju() {
+        assert_eq!(1.5f64.teju().as_str(), "1.5");
+        assert_eq!((-1e30f64).teju().as_str(), "-1e30");
+        assert_eq!(0.1f32.teju().as_str(), "0.1");
+        assert_eq!(f64::MIN_POSITIVE.teju().as_str(), "2.2250738585072014e-308");
+    }
+
+    #[test]
+    fn teju_exp() {
+        assert_eq!(1.5f64.teju_exp().as_str(), "1.5e0");
+        assert_eq!((-1234.5f64).teju_exp().as_str(), "-1.2345e3");
+        assert_eq!(1e-7f32.teju_exp().as_str(), "1e-7");
+        assert_eq!((-f64::MAX).teju_exp().as_str(), "-1.7976931348623157e308");
+    }
+
+    #[test]
+    fn teju_dec() {
+        assert_eq!(1.5f64.teju_dec().as_str(), "1.5");
+        assert_eq!(1e20f64.teju_dec().as_str(), "100000000000000000000.0");
+        assert_eq!((-1e-7f32).teju_dec().as_str(), "-0.0000001");
+        assert_eq!(f64::MAX.teju_dec().len(), 311);
+    }
+
+    #[test]
+    fn specials() {
+        for (num, str, exp) in [
+            (0.0, "0.0", "0e0"), (-0.0, "-0.0", "-0e0"), (f64::NAN, "NaN", "NaN"),
+            (f64::INFINITY, "inf", "inf"), (f64::NEG_INFINITY, "-inf", "-inf"),
+        ] {
+            assert_eq!(num.teju().as_str(), str);
+            assert_eq!(num.teju_exp().as_str(), exp);
+            assert_eq!(num.teju_dec().as_str(), str);
+            assert_eq!((num as f32).teju().as_str(), str);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_buffer(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            assert_eq!(num.teju().as_str(), Buffer::new().format(num));
+            assert_eq!(num.teju_exp().as_str(), Buffer::new().format_exp(num));
+            assert_eq!(num.teju_dec().as_str(), Buffer::new().format_dec(num));
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Scientific notation in fixed-width fields.
+
+use crate::{Buffer, Float, teju};
+use teju::{common::SignedDecimal, format};
+
+/// Maximum number of significant digits in [Buffer::format_fixed_field].
+const MAX_MANT_DIGITS: usize = 17;
+
+/// Maximum number of exponent digits in [Buffer::format_fixed_field].
+const MAX_EXP_DIGITS: usize = 5;
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// Print a floating point `num` into this buffer in scientific notation with exactly
+    /// `mant_digits` significant digits and `exp_digits` exponent digits, and return a reference to
+    /// its string representation, e.g. `+1.234560e+002` for 123.456 with 7 and 3 digits.
+    ///
+    /// Every number is printed with the same width, for aligning columns:
+    /// `mant_digits + exp_digits + 4` bytes, or one less if `mant_digits` is 1 (as there is no
+    /// decimal point). That is a sign (`+` or `-`), the first digit, the decimal point, the other
+    /// digits, `e`, the sign of the exponent, and the exponent padded with zeros.
+    ///
+    /// The number is rounded to `mant_digits` digits, with ties rounding to even, or padded with
+    /// zeros if its shortest representation has fewer. Zero is printed with exponent `0`. NaN and
+    /// infinities are printed as `NaN`, `+inf`, and `-inf`, padded with spaces on the left to the
+    /// same width.
+    ///
+    /// Returns `None` if the exponent has more than `exp_digits` digits, e.g. for `1e100` with
+    /// `exp_digits = 2`. 3 digits are enough for any `f64`, and 2 for any `f32`.
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_fixed_field(123.456, 7, 3), Some("+1.234560e+002"));
+    /// assert_eq!(buffer.format_fixed_field(-0.000999, 2, 2), Some("-1.0e-03"));
+    /// assert_eq!(buffer.format_fixed_field(5.0, 1, 1), Some("+5e+0"));
+    /// assert_eq!(buffer.format_fixed_field(f64::NAN, 3, 2), Some("      NaN"));
+    /// assert_eq!(buffer.format_fixed_field(1e100, 3, 2), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics unless `1 <= mant_digits <= 17` and `1 <= exp_digits <= 5`.
+    pub fn format_fixed_field(&mut self, num: F, mant_digits: usize, exp_digits: usize) -> Option<&str> {
+        assert!((1 ..= MAX_MANT_DIGITS).contains(&mant_digits), "Significant digits must be between 1 and {MAX_MANT_DIGITS}");
+        assert!((1 ..= MAX_EXP_DIGITS).contains(&exp_digits), "Exponent digits must be between 1 and {MAX_EXP_DIGITS}");
+        let width = mant_digits + exp_digits + 3 + (mant_digits > 1) as usize;
+
+        let ptr = <format::Scientific as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let special = match num.classify() {
+            teju::float::FloatType::Finite => None,
+            teju::float::FloatType::PosInf => Some("+inf"),
+            teju::float::FloatType::NegInf => Some(crate::NEG_INF),
+            teju::float::FloatType::Nan => Some(crate::NAN),
+        };
+        if let Some(special) = special {
+            let pad = width - special.len();
+            unsafe {
+                core::ptr::write_bytes(ptr, b' ', pad);
+                teju::fmt::write_str(special, ptr.add(pad));
+            }
+        } else {
+            let (sign, mant, exp) = match num.classify_finite() {
+                teju::float::FiniteFloatType::PosZero => (true, 0, 0),
+                teju::float::FiniteFloatType::NegZero => (false, 0, 0),
+                teju::float::FiniteFloatType::Nonzero => {
+                    let decimal = unsafe { num.to_decimal_finite() };
+                    let (mant, exp) = to_digits(decimal, mant_digits);
+                    (decimal.sign, mant, exp)
+                },
+            };
+            // Exponent of the first digit.
+            let exp = if mant == 0 { 0 } else { exp + mant_digits as i32 - 1 };
+            if exp.unsigned_abs() >= 10u32.pow(exp_digits as u32) {
+                return None
+            }
+
+            unsafe {
+                *ptr = if sign { b'+' } else { b'-' };
+                let buf = ptr.add(1);
+                if mant == 0 {
+                    core::ptr::write_bytes(buf.add(1), b'0', mant_digits);
+                } else {
+                    teju::fmt::print_u64_mantissa_known_len(mant, buf.add(1), mant_digits);
+                }
+                *buf = *buf.add(1);
+                *buf.add(1) = b'.';
+                let buf = buf.add(mant_digits + (mant_digits > 1) as usize);
+                *buf = b'e';
+                *buf.add(1) = if exp < 0 { b'-' } else { b'+' };
+                let mut exp = exp.unsigned_abs();
+                for i in (0 .. exp_digits).rev() {
+                    *buf.add(2 + i) = b'0' + (exp % 10) as u8;
+                    exp /= 10;
+                }
+            }
+        }
+        let slice = unsafe { core::slice::from_raw_parts(ptr, width) };
+        debug_assert!(width <= Self::MAX_LEN);
+        Some(unsafe { core::str::from_utf8_unchecked(slice) })
+    }
+}
+
+/// Round or pad the nonzero `decimal` to exactly `digits` significant digits, with ties rounding
+/// to even. Returns the new mantissa and exponent.
+fn to_digits(decimal: SignedDecimal, digits: usize) -> (u64, i32) {
+    let SignedDecimal { mant, exp, .. } = decimal.round_sig(digits);
+    let pad = (digits - teju::fmt::len_u64(mant)) as u32;
+    (mant * 10u64.pow(pad), exp - pad as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fixed_field(123.456f64, 7, 3), Some("+1.234560e+002"));
+        assert_eq!(buf.format_fixed_field(-123.456f64, 2, 3), Some("-1.2e+002"));
+        assert_eq!(buf.format_fixed_field(9.96f64, 2, 1), Some("+1.0e+1"));
+        assert_eq!(buf.format_fixed_field(0.125f64, 2, 2), Some("+1.2e-01"));
+        assert_eq!(buf.format_fixed_field(0.375f64, 2, 2), Some("+3.8e-01"));
+        assert_eq!(buf.format_fixed_field(f64::MAX, 17, 3), Some("+1.7976931348623157e+308"));
+        assert_eq!(buf.format_fixed_field(5e-324f64, 3, 3), Some("+5.00e-324"));
+        assert_eq!(buf.format_fixed_field(1.5f64, 4, 5), Some("+1.500e+00000"));
+        assert_eq!(Buffer::new().format_fixed_field(-1e-45f32, 1, 2), Some("-1e-45"));
+    }
+
+    #[test]
+    fn zero() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fixed_field(0.0f64, 3, 2), Some("+0.00e+00"));
+        assert_eq!(buf.format_fixed_field(-0.0f64, 1, 1), Some("-0e+0"));
+    }
+
+    /// The exponent of numbers in `[1, 10)` is printed with a `+`, never as `-0`.
+    #[test]
+    fn zero_exponent() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fixed_field(5.0f64, 1, 1), Some("+5e+0"));
+        assert_eq!(buf.format_fixed_field(-1.5f64, 2, 2), Some("-1.5e+00"));
+        assert_eq!(buf.format_fixed_field(9.94f64, 2, 3), Some("+9.9e+000"));
+        assert_eq!(buf.format_fixed_field(-0.999f64, 2, 1), Some("-1.0e+0"));
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fixed_field(f64::INFINITY, 1, 1), Some(" +inf"));
+        assert_eq!(buf.format_fixed_field(f64::NEG_INFINITY, 3, 3), Some("      -inf"));
+        assert_eq!(buf.format_fixed_field(f64::NAN, 17, 5), Some(format!("{:>26}", "NaN").as_str()));
+    }
+
+    #[test]
+    fn overflow() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fixed_field(1e10f64, 3, 1), None);
+        assert_eq!(buf.format_fixed_field(1e9f64, 3, 1), Some("+1.00e+9"));
+        assert_eq!(buf.format_fixed_field(9.99e9f64, 2, 1), None);
+        assert_eq!(buf.format_fixed_field(1e-10f64, 3, 1), None);
+    }
+
+    #[test]
+    fn same_width() {
+        let mut buf = Buffer::new();
+        for num in [1.0, -1.0, 0.0, 123456.789, -5e-324, f64::MAX, f64::NAN, f64::INFINITY] {
+            assert_eq!(buf.format_fixed_field(num, 6, 3).unwrap().len(), 13);
+            assert_eq!(buf.format_fixed_field(num, 1, 3).unwrap().len(), 7);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_digits() {
+        Buffer::new().format_fixed_field(1.0f64, 18, 3);
+    }
+}
@@ -0,0 +1,176 @@
+//! The most precise representation which fits in a fixed width, as for numeric displays.
+
+use crate::{Buffer, CapacityError, Float, teju};
+use teju::common::SignedDecimal;
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Fixed> {
+    /// Print a floating point `num` into this buffer with as many significant digits as fit in
+    /// `total_width` bytes, right-justified and padded with spaces, and return a reference to its
+    /// string representation, e.g. for a display with a fixed number of characters. Returns an
+    /// error if `num` does not fit even without any digits after the decimal point.
+    ///
+    /// The candidates are decimal notation rounded as by [Self::format_round] to as many digits
+    /// after the decimal point as fit, and scientific notation as by
+    /// [Buffer::format_exp](crate::Buffer::format_exp) rounded to as many significant digits as
+    /// fit. Ties round to even, and trailing fractional zeros are removed down to one digit, e.g.
+    /// `100.0`. Scientific notation is printed only if it keeps a finer digit and so rounds to a
+    /// different number, so e.g. `0.000123` stays in decimal notation in 8 bytes, but `1.23456e-7`
+    /// is printed as `1.235e-7`.
+    ///
+    /// NaN and infinities are printed as `"NaN"`, `"inf"`, and `"-inf"`, right-justified.
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_fit(3.14159265, 8), Ok("3.141593"));
+    /// assert_eq!(buffer.format_fit(-0.5, 8), Ok("    -0.5"));
+    /// assert_eq!(buffer.format_fit(123456789.0, 8), Ok("1.2346e8"));
+    /// assert_eq!(buffer.format_fit(1.23456e-7, 8), Ok("1.235e-7"));
+    /// assert_eq!(buffer.format_fit(f64::NAN, 8), Ok("     NaN"));
+    /// assert!(buffer.format_fit(1e100, 4).is_err());
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `total_width` is greater than [Self::MAX_LEN].
+    pub fn format_fit(&mut self, num: F, total_width: usize) -> Result<&str, CapacityError> {
+        assert!(total_width <= Self::MAX_LEN, "Width must be at most {}", Self::MAX_LEN);
+        let ptr = <format::Fixed as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let special = match num.classify() {
+            teju::float::FloatType::Finite => None,
+            teju::float::FloatType::PosInf => Some(crate::POS_INF),
+            teju::float::FloatType::NegInf => Some(crate::NEG_INF),
+            teju::float::FloatType::Nan => Some(crate::NAN),
+        };
+        let decimal = match (special, num.classify_finite()) {
+            (None, teju::float::FiniteFloatType::Nonzero) => Some(unsafe { num.to_decimal_finite() }),
+            _ => None,
+        };
+
+        let n = if let Some(special) = special {
+            unsafe { teju::fmt::write_str(special, ptr) }
+        } else {
+            let dec = self.fit_dec(num, decimal, total_width);
+            let sci = decimal.and_then(|decimal| fit_sci(decimal, total_width));
+            // Scientific notation if it keeps a finer digit, i.e. if it rounds to a different
+            // number whose last digit has a lower exponent.
+            let use_sci = match (dec, sci, decimal) {
+                (Some(precision), Some((sci, sci_exp)), Some(decimal)) =>
+                    decimal.round(precision).trim_trailing_zeros() != sci && sci_exp < -(precision as i32),
+                (None, Some(_), _) => true,
+                _ => false,
+            };
+            match (dec, sci) {
+                (_, Some((sci, _))) if use_sci => unsafe { F::format_exp_decimal(sci, ptr) },
+                (Some(precision), _) => self.format_round(num, precision, true).len(),
+                _ => {
+                    let dec_len = self.format_round(num, 0, true).len();
+                    let sci_len = decimal.map_or(usize::MAX, |decimal| format::notation_lens(decimal.round_sig(1)).1);
+                    return Err(CapacityError { needed: dec_len.min(sci_len), have: total_width })
+                },
+            }
+        };
+
+        let pad = total_width.checked_sub(n).ok_or(CapacityError { needed: n, have: total_width })?;
+        unsafe {
+            core::ptr::copy(ptr, ptr.add(pad), n);
+            core::ptr::write_bytes(ptr, b' ', pad);
+        }
+        let slice = unsafe { core::slice::from_raw_parts(ptr, total_width) };
+        debug_assert!(total_width <= Self::MAX_LEN);
+        Ok(unsafe { core::str::from_utf8_unchecked(slice) })
+    }
+
+    /// The most digits after the decimal point with which the finite `num` fits in `width` bytes
+    /// in decimal notation, if any. `decimal` is its shortest representation, or `None` for zero.
+    fn fit_dec(&mut self, num: F, decimal: Option<SignedDecimal>, width: usize) -> Option<usize> {
+        let int_len = decimal.map_or(1, |decimal| {
+            (teju::fmt::len_u64(decimal.mant) as i32 + decimal.exp).max(1) as usize
+        });
+        let sign_len = num.is_sign_negative() as usize;
+        let precision = width.checked_sub(sign_len + int_len + 1).map_or(0, |p| p.min(format::MAX_PRECISION));
+        // Rounding may carry into a new integer digit, in which case there is room for one less.
+        if self.format_round(num, precision, true).len() <= width {
+            Some(precision)
+        } else {
+            let precision = precision.checked_sub(1)?;
+            (self.format_round(num, precision, true).len() <= width).then_some(precision)
+        }
+    }
+}
+
+/// The nonzero `decimal` rounded to the most significant digits with which it fits in `width`
+/// bytes in scientific notation, if any, and the exponent of its last digit before trimming.
+fn fit_sci(decimal: SignedDecimal, width: usize) -> Option<(SignedDecimal, i32)> {
+    let len = teju::fmt::len_u64(decimal.mant);
+    let sci_exp = len as i32 + decimal.exp - 1;
+    (1 ..= len).rev()
+        .map(|digits| (decimal.round_sig(digits), sci_exp - (digits as i32 - 1)))
+        .find(|&(rounded, _)| format::notation_lens(rounded).1 <= width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn fit() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fit(1.23456789f64, 4), Ok("1.23"));
+        assert_eq!(buf.format_fit(1.23456789f64, 1), Ok("1"));
+        assert_eq!(buf.format_fit(1.5f64, 10), Ok("       1.5"));
+        assert_eq!(buf.format_fit(99.96f64, 4), Ok(" 100"));
+        assert_eq!(buf.format_fit(99.996f64, 5), Ok("100.0"));
+        assert_eq!(buf.format_fit(12345678.0f64, 8), Ok("12345678"));
+        assert_eq!(buf.format_fit(-12345678.0f64, 8), Ok("-1.235e7"));
+        assert_eq!(buf.format_fit(0.000123f64, 8), Ok("0.000123"));
+        assert_eq!(buf.format_fit(0.0001234567f64, 8), Ok("1.235e-4"));
+        assert_eq!(buf.format_fit(1e20f64, 30), Ok("       100000000000000000000.0"));
+        assert_eq!(Buffer::new().format_fit(0.1f32, 12), Ok("         0.1"));
+    }
+
+    #[test]
+    fn too_narrow() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fit(1e100f64, 4), Err(CapacityError { needed: 5, have: 4 }));
+        assert_eq!(buf.format_fit(-123.0f64, 3), Err(CapacityError { needed: 4, have: 3 }));
+        assert_eq!(buf.format_fit(f64::NEG_INFINITY, 3), Err(CapacityError { needed: 4, have: 3 }));
+        assert_eq!(buf.format_fit(9.6e9f64, 3), Err(CapacityError { needed: 4, have: 3 }));
+        assert_eq!(buf.format_fit(0.5f64, 0), Err(CapacityError { needed: 1, have: 0 }));
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fit(0.0f64, 6), Ok("   0.0"));
+        assert_eq!(buf.format_fit(-0.0f64, 2), Ok("-0"));
+        assert_eq!(buf.format_fit(f64::INFINITY, 4), Ok(" inf"));
+        assert_eq!(buf.format_fit(f64::NEG_INFINITY, 4), Ok("-inf"));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn fits(bits in any::<u64>(), width in 0usize .. 32) {
+            let num = f64::from_bits(bits);
+            match Buffer::new().format_fit(num, width) {
+                Ok(str) => {
+                    assert_eq!(str.len(), width);
+                    let trimmed = str.trim_start_matches(' ');
+                    assert!(!trimmed.contains(' '), "{str}");
+                    let shortest = Buffer::new().format(num).to_owned();
+                    if shortest.len() <= width && !shortest.contains('e') {
+                        // A decimal string which fits is printed as is.
+                        assert_eq!(trimmed, shortest);
+                    }
+                    if let Ok(parsed) = trimmed.parse::<f64>() {
+                        assert!(num.is_nan() || parsed == 0.0 || parsed.signum() == num.signum(), "{str}");
+                    }
+                },
+                Err(err) => assert!(err.needed > width),
+            }
+        }
+    }
+}
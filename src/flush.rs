@@ -0,0 +1,84 @@
+//! General notation, with numbers of small magnitude shown as zero.
+
+use crate::{Buffer, Float, teju};
+use teju::{float::abs_bits, format};
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer as [Self::format] does, unless its magnitude
+    /// is less than that of `epsilon`, in which case `"0.0"` is printed, and return a reference to
+    /// its string representation.
+    ///
+    /// Numbers are flushed to `"0.0"` without a sign, even if negative, e.g. for hiding rounding
+    /// noise such as `-3e-17` in tables and plots. The magnitudes are compared directly, without
+    /// formatting `num` first, and the sign of `epsilon` is ignored. If `epsilon` is NaN, nothing
+    /// is flushed.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_flush(-3e-17, 1e-12), "0.0");
+    /// assert_eq!(buffer.format_flush(0.25, 1e-12), "0.25");
+    /// assert_eq!(buffer.format_flush(1e-12, 1e-12), "1e-12");
+    /// ```
+    pub fn format_flush(&mut self, num: F, epsilon: F) -> &str {
+        let flush = !matches!(num.classify(), teju::float::FloatType::Nan)
+            && !matches!(epsilon.classify(), teju::float::FloatType::Nan)
+            && abs_bits(num) < abs_bits(epsilon);
+        if flush {
+            crate::POS_ZERO
+        } else {
+            self.format(num)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn flush() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_flush(3e-17f64, 1e-9), "0.0");
+        assert_eq!(buf.format_flush(-3e-17f64, 1e-9), "0.0");
+        assert_eq!(buf.format_flush(-3e-17f64, -1e-9), "0.0");
+        assert_eq!(buf.format_flush(-0.0f64, 1e-9), "0.0");
+        assert_eq!(buf.format_flush(-0.0f64, 0.0), "-0.0");
+        assert_eq!(buf.format_flush(-2e-9f64, 1e-9), "-2e-9");
+        assert_eq!(buf.format_flush(1.5f64, 1e-9), "1.5");
+        assert_eq!(buf.format_flush(1e300f64, f64::INFINITY), "0.0");
+        assert_eq!(Buffer::new().format_flush(-1e-8f32, 1e-6), "0.0");
+        assert_eq!(Buffer::new().format_flush(f32::MIN_POSITIVE, f32::from_bits(1)), "1.1754944e-38");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_flush(f64::NAN, 1e-9), "NaN");
+        assert_eq!(buf.format_flush(f64::NEG_INFINITY, 1e-9), "-inf");
+        assert_eq!(buf.format_flush(f64::INFINITY, f64::INFINITY), "inf");
+        assert_eq!(buf.format_flush(1e-20f64, f64::NAN), "1e-20");
+        assert_eq!(buf.format_flush(f64::NAN, f64::NAN), "NaN");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_compare(bits in any::<u64>(), epsilon in any::<f64>()) {
+            let num = f64::from_bits(bits);
+            let expected = if num.abs() < epsilon.abs() { "0.0".to_owned() } else { Buffer::new().format(num).to_owned() };
+            assert_eq!(Buffer::new().format_flush(num, epsilon), expected);
+        }
+
+        #[test]
+        fn same_as_compare_f32(bits in any::<u32>(), epsilon in any::<f32>()) {
+            let num = f32::from_bits(bits);
+            let expected = if num.abs() < epsilon.abs() { "0.0".to_owned() } else { Buffer::new().format(num).to_owned() };
+            assert_eq!(Buffer::new().format_flush(num, epsilon), expected);
+        }
+    }
+}
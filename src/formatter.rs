@@ -0,0 +1,157 @@
+//! Formatting to a [core::fmt::Formatter], honouring its flags.
+
+use core::fmt::{self, Write};
+
+use crate::{Buffer, Float, teju};
+
+/// Write `num` to `f`, honouring its precision, width, fill, alignment, `+`, and `0` flags, for
+/// implementing [Display](core::fmt::Display) on types wrapping floats.
+///
+/// With a precision, e.g. `{:.3}`, the number is formatted as by
+/// [Buffer::format_round](crate::Buffer::format_round), i.e. in decimal notation with exactly that
+/// many digits after the decimal point (up to 324, see below); otherwise it is formatted as by
+/// [Buffer::format]. It is then padded to the width as an integer would be: to the right by
+/// default, `+` prints a sign for positive numbers, and `0` pads with zeros after the sign. As in
+/// [core::fmt], this applies to infinities too, but NaN is never printed with a sign.
+///
+/// ## Differences from `std`
+///
+/// With a precision, the digits are those of the shortest representation, not of the exact binary
+/// value as in [core::fmt]. Ties are decided on the exact value, so e.g. `{:.2}` of `2.675` is
+/// `2.67` in both, but a precision beyond the shortest representation pads it with zeros where
+/// [core::fmt] prints more digits of the exact value: `{:.20}` of `0.1` is
+/// `0.10000000000000000000` here and `0.10000000000000000555` in [core::fmt].
+///
+/// A precision above 324 is taken as 324, as no `f64` has significant digits past that, so e.g.
+/// `{:.400}` of `0.1` has 324 digits after the decimal point here and 400 in [core::fmt].
+///
+/// ```
+/// struct Meters(f64);
+///
+/// impl std::fmt::Display for Meters {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         teju::format_into_formatter(self.0, f)?;
+///         f.write_str(" m")
+///     }
+/// }
+///
+/// assert_eq!(format!("{}", Meters(1e30)), "1e30 m");
+/// assert_eq!(format!("{:8.3}", Meters(3.14159)), "   3.142 m");
+/// assert_eq!(format!("{:<8}", Meters(-0.5)), "-0.5     m");
+/// assert_eq!(format!("{:+08.2}", Meters(2.5)), "+0002.50 m");
+/// ```
+pub fn format_into_formatter<F: Float>(num: F, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match f.precision() {
+        Some(precision) => pad(f, num, Buffer::new().format_round(num, precision, false)),
+        None => pad(f, num, Buffer::new().format(num)),
+    }
+}
+
+/// Write `str`, the formatted `num`, to `f`, padded as an integer would be.
+fn pad<F: Float>(f: &mut fmt::Formatter<'_>, num: F, str: &str) -> fmt::Result {
+    match num.classify() {
+        teju::float::FloatType::Nan => pad_unsigned(f, str),
+        _ => {
+            let (is_nonnegative, digits) = match str.strip_prefix('-') {
+                Some(digits) => (false, digits),
+                None => (true, str),
+            };
+            f.pad_integral(is_nonnegative, "", digits)
+        },
+    }
+}
+
+/// As [fmt::Formatter::pad_integral], but never printing a sign.
+fn pad_unsigned(f: &mut fmt::Formatter<'_>, str: &str) -> fmt::Result {
+    let padding = f.width().unwrap_or(0).saturating_sub(str.len());
+    let (before, after) = match f.align() {
+        _ if f.sign_aware_zero_pad() => (padding, 0),
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(fmt::Alignment::Right) | None => (padding, 0),
+    };
+    let fill = if f.sign_aware_zero_pad() { '0' } else { f.fill() };
+    for _ in 0 .. before {
+        f.write_char(fill)?;
+    }
+    f.write_str(str)?;
+    for _ in 0 .. after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    struct Teju<F>(F);
+
+    impl<F: Float> fmt::Display for Teju<F> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            format_into_formatter(self.0, f)
+        }
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(format!("{:.2}", Teju(1.005f64)), "1.00");
+        assert_eq!(format!("{:.2}", Teju(2.675f64)), "2.67");
+        assert_eq!(format!("{:.0}", Teju(-2.5f64)), "-2");
+        assert_eq!(format!("{:.3}", Teju(1e20f64)), "100000000000000000000.000");
+        assert_eq!(format!("{:.1}", Teju(0.25f32)), "0.2");
+    }
+
+    #[test]
+    fn precision_beyond_shortest() {
+        // Padded with zeros, whereas std prints the digits of the exact value.
+        assert_eq!(format!("{:.20}", Teju(0.1f64)), "0.10000000000000000000");
+        assert_eq!(format!("{:.20}", 0.1f64), "0.10000000000000000555");
+        assert_eq!(format!("{:.10}", Teju(0.1f32)), "0.1000000000");
+        assert_eq!(format!("{:.10}", 0.1f32), "0.1000000015");
+        // Capped at 324 digits, whereas std pads up to the precision.
+        assert_eq!(format!("{:.400}", Teju(0.1f64)).len(), 2 + 324);
+        assert_eq!(format!("{:.400}", 0.1f64).len(), 2 + 400);
+    }
+
+    #[test]
+    fn width() {
+        assert_eq!(format!("{:6}", Teju(1.5f64)), "   1.5");
+        assert_eq!(format!("{:<6}", Teju(1.5f64)), "1.5   ");
+        assert_eq!(format!("{:*^7}", Teju(1.5f64)), "**1.5**");
+        assert_eq!(format!("{:06}", Teju(-1.5f64)), "-001.5");
+        assert_eq!(format!("{:+}", Teju(1.5f64)), "+1.5");
+        assert_eq!(format!("{:+09.3}", Teju(-1e-3f64)), "-0000.001");
+        assert_eq!(format!("{:2}", Teju(1e30f64)), "1e30");
+        assert_eq!(format!("{:>8}", Teju(-0.0f64)), "    -0.0");
+    }
+
+    #[test]
+    fn specials() {
+        // As in std.
+        for (num, fmt) in [
+            (f64::NAN, "000NaN"), (f64::INFINITY, "+00inf"), (f64::NEG_INFINITY, "-00inf"),
+        ] {
+            assert_eq!(format!("{:+06}", Teju(num)), fmt);
+            assert_eq!(format!("{:+06}", num), fmt);
+        }
+        assert_eq!(format!("{:+.2}", Teju(f64::NAN)), "NaN");
+        assert_eq!(format!("{:^7}", Teju(f64::NAN)), "  NaN  ");
+        assert_eq!(format!("{:<6.1}", Teju(f64::NEG_INFINITY)), "-inf  ");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn padded(bits in any::<u64>(), width in 0usize .. 40, precision in 0usize .. 20) {
+            let num = f64::from_bits(bits);
+            let general = Buffer::new().format(num).to_owned();
+            assert_eq!(format!("{:_^width$}", Teju(num)), format!("{general:_^width$}"));
+            assert_eq!(format!("{:width$}", Teju(num)), format!("{general:>width$}"));
+            let fixed = Buffer::new().format_round(num, precision, false).to_owned();
+            assert_eq!(format!("{:<width$.precision$}", Teju(num)), format!("{fixed:<width$}"));
+        }
+    }
+}
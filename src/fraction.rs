@@ -0,0 +1,105 @@
+//! Dyadic fractions, as `3/4`.
+
+use crate::{Buffer, Float, teju};
+use teju::{fmt, format};
+
+impl<F: Float> Buffer<F, format::Fraction> {
+    /// Print a floating point `num` into this buffer as an exact fraction `n/d` in lowest terms,
+    /// if its denominator `d` is at least 2 and at most `max_denominator`, or as [Buffer::format]
+    /// otherwise, and return a reference to its string representation.
+    ///
+    /// Every finite float is exactly `n/d` for an integer `n` and a power of two `d`, which is
+    /// read from its bits without rounding. So e.g. `0.1` is `3602879701896397/36028797018963968`,
+    /// which is printed in decimal unless `max_denominator` is that large. Integers are printed
+    /// as by [Buffer::format], e.g. `"2.0"`, as are zero, NaN, and infinities.
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_fraction(0.5, 1024), "1/2");
+    /// assert_eq!(buffer.format_fraction(-0.75, 1024), "-3/4");
+    /// assert_eq!(buffer.format_fraction(2.5, 1024), "5/2");
+    /// assert_eq!(buffer.format_fraction(0.1, 1024), "0.1");
+    /// assert_eq!(buffer.format_fraction(2.0, 1024), "2.0");
+    /// ```
+    pub fn format_fraction(&mut self, num: F, max_denominator: u64) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let (mant, exp) = unsafe { num.to_binary_finite_nonzero() };
+        let den = 1u64.checked_shl(exp.unsigned_abs()).filter(|&den| exp < 0 && den <= max_denominator);
+
+        let ptr = <format::Fraction as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            match den {
+                Some(den) => {
+                    let sign_len = num.is_sign_negative() as usize;
+                    ptr.write(b'-');
+                    let mut n = sign_len + fmt::print_u128(mant as u128, ptr.add(sign_len));
+                    *ptr.add(n) = b'/';
+                    n += 1;
+                    n + fmt::print_u128(den as u128, ptr.add(n))
+                },
+                None => num.format_general_finite_nonzero(ptr),
+            }
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn fraction() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fraction(0.125f64, 8), "1/8");
+        assert_eq!(buf.format_fraction(0.0625f64, 8), "0.0625");
+        assert_eq!(buf.format_fraction(-1.375f64, 8), "-11/8");
+        assert_eq!(buf.format_fraction(1.0f64, u64::MAX), "1.0");
+        assert_eq!(buf.format_fraction(0.1f64, (1 << 55) - 1), "0.1");
+        assert_eq!(buf.format_fraction(0.1f64, 1 << 55), "3602879701896397/36028797018963968");
+        assert_eq!(buf.format_fraction(-f64::from_bits(0x3f4f_ffff_ffff_ffff), u64::MAX), "-9007199254740991/9223372036854775808");
+        assert_eq!(buf.format_fraction(5e-324f64, u64::MAX), "5e-324");
+        assert_eq!(Buffer::new().format_fraction(0.1f32, 1 << 27), "13421773/134217728");
+        assert_eq!(Buffer::new().format_fraction(0.75f32, 2), "0.75");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fraction(0.0f64, 1024), "0.0");
+        assert_eq!(buf.format_fraction(-0.0f64, 1024), "-0.0");
+        assert_eq!(buf.format_fraction(f64::NAN, 1024), "NaN");
+        assert_eq!(buf.format_fraction(f64::NEG_INFINITY, 1024), "-inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100_000))]
+
+        #[test]
+        fn exact(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let str = Buffer::new().format_fraction(num, u64::MAX).to_owned();
+            match str.split_once('/') {
+                Some((n, d)) => {
+                    let (n, d) = (n.parse::<i64>().unwrap(), d.parse::<u64>().unwrap());
+                    assert!(n % 2 != 0 && d.is_power_of_two() && d >= 2, "{str}");
+                    assert_eq!((n as f64 / d as f64).to_bits(), bits, "{str}");
+                },
+                None => assert_eq!(str, Buffer::new().format(num)),
+            }
+        }
+    }
+}
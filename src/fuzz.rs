@@ -0,0 +1,68 @@
+//! Entry point for fuzzing.
+
+use crate::Buffer;
+
+/// Interprets `bytes` as a little-endian `f64`, formats it in every notation, and checks that each
+/// string parses back to the same number. For NaN and infinities, checks that the special strings
+/// `"NaN"`, `"inf"`, and `"-inf"` are produced instead. Returns `true` if all checks pass.
+///
+/// This is a stable target for fuzzers, e.g. for `cargo fuzz`:
+///
+/// ```ignore
+/// #![no_main]
+/// libfuzzer_sys::fuzz_target!(|bytes: [u8; 8]| assert!(teju::fuzz_roundtrip(bytes)));
+/// ```
+///
+/// ```
+/// assert!(teju::fuzz_roundtrip(1.234f64.to_le_bytes()));
+/// assert!(teju::fuzz_roundtrip([0xff; 8]));
+/// ```
+pub fn fuzz_roundtrip(bytes: [u8; 8]) -> bool {
+    let num = f64::from_le_bytes(bytes);
+
+    if !num.is_finite() {
+        let special = if num.is_nan() {
+            crate::NAN
+        } else if num.is_sign_positive() {
+            crate::POS_INF
+        } else {
+            crate::NEG_INF
+        };
+        return Buffer::new().format(num) == special
+            && Buffer::new().format_exp(num) == special
+            && Buffer::new().format_dec(num) == special
+    }
+
+    let roundtrips = |str: &str| str.parse::<f64>().is_ok_and(|x| x.to_bits() == num.to_bits());
+    roundtrips(Buffer::new().format(num))
+        && roundtrips(Buffer::new().format_finite(num))
+        && roundtrips(Buffer::new().format_exp(num))
+        && roundtrips(Buffer::new().format_exp_finite(num))
+        && roundtrips(Buffer::new().format_dec(num))
+        && roundtrips(Buffer::new().format_dec_finite(num))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzz_roundtrip_examples() {
+        for num in [0.0, -0.0, 1.0, -1.5e-300, f64::MAX, f64::MIN_POSITIVE, f64::from_bits(1)] {
+            assert!(fuzz_roundtrip(num.to_le_bytes()));
+        }
+        for num in [f64::NAN, -f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(fuzz_roundtrip(num.to_le_bytes()));
+        }
+    }
+
+    use proptest::prelude::*;
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn proptest_fuzz_roundtrip(bytes in any::<[u8; 8]>()) {
+            assert!(fuzz_roundtrip(bytes))
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Output identical to Go's `strconv.FormatFloat(f, 'g', -1, 64)`.
+
+use crate::{Buffer, Float, teju};
+use teju::common::SignedDecimal;
+use teju::{fmt, format};
+
+/// Print `decimal`, which must be nonzero and have no trailing zeros, with the layout shared by
+/// Go's `'g'` format and Swift's `description`, and return the number of bytes written.
+///
+/// `decimal` is written in decimal notation if its exponent in scientific notation is in
+/// `dec_exps`, followed by `.0` if `point_zero` and it is an integer, and in scientific notation
+/// otherwise, with the exponent always signed and at least two digits long.
+pub(crate) unsafe fn format_signed_exp_decimal(
+    decimal: SignedDecimal,
+    dec_exps: core::ops::Range<i32>,
+    point_zero: bool,
+    mut buf: *mut u8,
+) -> usize {
+    let buf_orig = buf;
+    unsafe {
+        buf.write(b'-');
+        buf = buf.add(!decimal.sign as usize);
+
+        let mant = decimal.mant;
+        let mant_len = fmt::len_u64(mant);
+        let decimal_exp = mant_len as i32 + decimal.exp;
+        let exp = decimal_exp - 1;
+        let len = if !dec_exps.contains(&exp) {
+            // `1234e30 -> 1.234e+33`, `1e-5 -> 1e-05`
+            let len = if mant_len == 1 {
+                *buf = b'0' + mant as u8;
+                1
+            } else {
+                fmt::print_u64_mantissa_with_point(mant, buf, mant_len, 1)
+            };
+            *buf.add(len) = b'e';
+            *buf.add(len + 1) = if exp < 0 {b'-'} else {b'+'};
+            let exp = exp.abs();
+            let exp_len = if exp < 10 {
+                *buf.add(len + 2) = b'0';
+                *buf.add(len + 3) = b'0' + exp as u8;
+                2
+            } else {
+                fmt::print_i32_exp(exp, buf.add(len + 2))
+            };
+            len + 2 + exp_len
+        } else if decimal.exp >= 0 {
+            // `1234e2 -> 123400`, or `123400.0` if `point_zero`
+            fmt::print_u64_mantissa_known_len(mant, buf, mant_len);
+            core::ptr::write_bytes(buf.add(mant_len), b'0', decimal.exp as usize);
+            let int_len = decimal_exp as usize;
+            if point_zero {
+                *buf.add(int_len) = b'.';
+                *buf.add(int_len + 1) = b'0';
+                int_len + 2
+            } else {
+                int_len
+            }
+        } else if decimal_exp > 0 {
+            // `1234e-1 -> 123.4`: write mantissa, shift digits after `decimal_exp` digit 1 place to
+            // the right, write decimal point in between.
+            fmt::print_u64_mantissa_known_len(mant, buf, mant_len);
+            let int_len = decimal_exp as usize;
+            core::ptr::copy(buf.add(int_len), buf.add(int_len + 1), -decimal.exp as usize);
+            *buf.add(int_len) = b'.';
+            mant_len + 1
+        } else {
+            // `1234e-6 -> 0.001234`
+            let n_zeros = -decimal_exp as usize;
+            *buf = b'0';
+            *buf.add(1) = b'.';
+            core::ptr::write_bytes(buf.add(2), b'0', n_zeros);
+            fmt::print_u64_mantissa_known_len(mant, buf.add(2 + n_zeros), mant_len);
+            2 + n_zeros + mant_len
+        };
+        buf.add(len).offset_from(buf_orig) as usize
+    }
+}
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer, and return a reference to its string
+    /// representation, which is byte-for-byte identical to the output of Go's
+    /// `strconv.FormatFloat(num, 'g', -1, 64)` (or `32`, for `f32`).
+    ///
+    /// As in Go, numbers are written in scientific notation if their exponent is less than `-4`
+    /// or at least `6`, and in decimal notation otherwise. Unlike [Self::format], the exponent
+    /// always has a sign and at least two digits, and integers are written without a trailing
+    /// `.0`.
+    ///
+    /// This function formats zero as `"0"`, NaN as the string `"NaN"`, positive infinity as
+    /// `"+Inf"`, and negative infinity as `"-Inf"`, to match Go.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_go(123456.0), "123456");
+    /// assert_eq!(teju::Buffer::new().format_go(1234567.0), "1.234567e+06");
+    /// assert_eq!(teju::Buffer::new().format_go(0.0001), "0.0001");
+    /// assert_eq!(teju::Buffer::new().format_go(0.00001), "1e-05");
+    /// assert_eq!(teju::Buffer::new().format_go(1. / 0.), "+Inf");
+    /// ```
+    pub fn format_go(&mut self, num: F) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return "+Inf",
+            teju::float::FloatType::NegInf => return "-Inf",
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return "0",
+            teju::float::FiniteFloatType::NegZero => return "-0",
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            let decimal = num.to_decimal_finite().trim_trailing_zeros();
+            format_signed_exp_decimal(decimal, -4 .. 6, false, ptr)
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Outputs of `strconv.FormatFloat(x, 'g', -1, 64)`.
+    #[test]
+    fn go_corpus() {
+        let mut buf = Buffer::new();
+        for (num, str) in [
+            (1.0, "1"),
+            (-1.5, "-1.5"),
+            (100000.0, "100000"),
+            (999999.0, "999999"),
+            (1e6, "1e+06"),
+            (1234567.0, "1.234567e+06"),
+            (1e21, "1e+21"),
+            (1e100, "1e+100"),
+            (0.1, "0.1"),
+            (0.000123, "0.000123"),
+            (0.00001234, "1.234e-05"),
+            (123.456, "123.456"),
+            (f64::MAX, "1.7976931348623157e+308"),
+            (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+            (5e-324, "5e-324"),
+            (0.0, "0"),
+            (-0.0, "-0"),
+        ] {
+            assert_eq!(buf.format_go(num), str);
+        }
+    }
+
+    #[test]
+    fn go_corpus_f32() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_go(0.1f32), "0.1");
+        assert_eq!(buf.format_go(16777216.0f32), "1.6777216e+07");
+        assert_eq!(buf.format_go(f32::MAX), "3.4028235e+38");
+        assert_eq!(buf.format_go(1e-45f32), "1e-45");
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(Buffer::new().format_go(f64::NAN), "NaN");
+        assert_eq!(Buffer::new().format_go(f64::INFINITY), "+Inf");
+        assert_eq!(Buffer::new().format_go(f64::NEG_INFINITY), "-Inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100_000))]
+
+        #[test]
+        fn same_as_exp_and_dec(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite() && num != 0.0);
+            let str = Buffer::new().format_go(num).to_owned();
+            let exp_str = Buffer::new().format_exp(num).to_owned();
+            let (mant, exp) = exp_str.split_once('e').unwrap();
+            let exp: i32 = exp.parse().unwrap();
+            let expected = if (-4 .. 6).contains(&exp) {
+                Buffer::new().format_dec(num).trim_end_matches(".0").to_owned()
+            } else {
+                format!("{mant}e{}{:02}", if exp < 0 {'-'} else {'+'}, exp.abs())
+            };
+            assert_eq!(str, expected);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Scientific notation with the digits of the mantissa in groups.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Grouped> {
+    /// Print a floating point `num` into this buffer in scientific notation, with a `sep` between
+    /// every `group` digits after the decimal point of the mantissa, and return a reference to its
+    /// string representation.
+    ///
+    /// The number is formatted as by [Self::format_exp], and the exponent is left unchanged.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_exp_grouped(1.2345678901234567e20, b'_', 5), "1.23456_78901_23456_7e20");
+    /// assert_eq!(buffer.format_exp_grouped(-1.5e-7, b' ', 3), "-1.5e-7");
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `sep` is not ASCII, or if `group` is `0`.
+    pub fn format_exp_grouped(&mut self, num: F, sep: u8, group: usize) -> &str {
+        assert!(sep.is_ascii(), "Separator must be ASCII");
+        assert!(group != 0, "Group size must be nonzero");
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO_EXP,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO_EXP,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+
+        let ptr = <format::Grouped as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            let n = num.format_exp_finite_nonzero(ptr);
+            let point = num.is_sign_negative() as usize + 1;
+            if *ptr.add(point) != b'.' {
+                n
+            } else {
+                // Move the exponent to the end, then the fractional digits from last to first,
+                // inserting separators.
+                let frac = ptr.add(point + 1);
+                let mut frac_len = 0;
+                while *frac.add(frac_len) != b'e' {
+                    frac_len += 1;
+                }
+                let seps = (frac_len - 1) / group;
+                let exp_len = n - (point + 1 + frac_len);
+                core::ptr::copy(frac.add(frac_len), frac.add(frac_len + seps), exp_len);
+                for k in (1 .. frac_len).rev() {
+                    *frac.add(k + k / group) = *frac.add(k);
+                    if k % group == 0 {
+                        *frac.add(k + k / group - 1) = sep;
+                    }
+                }
+                n + seps
+            }
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn grouped() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_exp_grouped(1.2345678901234567e20f64, b'_', 5), "1.23456_78901_23456_7e20");
+        assert_eq!(buf.format_exp_grouped(1.2345678901234567e20f64, b'_', 16), "1.2345678901234567e20");
+        assert_eq!(buf.format_exp_grouped(1.2345678901234567e20f64, b'_', 15), "1.234567890123456_7e20");
+        assert_eq!(buf.format_exp_grouped(-1.234567e-300f64, b' ', 3), "-1.234 567e-300");
+        assert_eq!(buf.format_exp_grouped(1.25f64, b'_', 1), "1.2_5e0");
+        assert_eq!(buf.format_exp_grouped(1e7f64, b'_', 1), "1e7");
+        assert_eq!(Buffer::new().format_exp_grouped(-3.4028235e38f32, b'_', 3), "-3.402_823_5e38");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        assert_eq!(
+            buf.format_exp_grouped(-2.2250738585072014e-308, b'_', 1),
+            "-2.2_2_5_0_7_3_8_5_8_5_0_7_2_0_1_4e-308",
+        );
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_exp_grouped(-0.0f64, b'_', 3), "-0e0");
+        assert_eq!(buf.format_exp_grouped(f64::NAN, b'_', 3), "NaN");
+        assert_eq!(buf.format_exp_grouped(f64::NEG_INFINITY, b'_', 3), "-inf");
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_group() {
+        Buffer::new().format_exp_grouped(1.5f64, b'_', 0);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_digits(bits in any::<u64>(), group in 1usize .. 20) {
+            let num = f64::from_bits(bits);
+            let str = Buffer::new().format_exp_grouped(num, b'_', group).to_owned();
+            assert_eq!(str.replace('_', ""), Buffer::new().format_exp(num));
+            if let Some((_, frac)) = str.split_once('.') {
+                let frac = frac.split('e').next().unwrap();
+                assert!(frac.split('_').all(|g| (1 ..= group).contains(&g.len())), "{str}");
+                assert!(frac.split('_').rev().skip(1).all(|g| g.len() == group), "{str}");
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Formatting of 128-bit integers, using the same digit printing routines as for floats.
+
+use core::mem::MaybeUninit;
+use crate::teju;
+
+/// Number of digits of `u128::MAX`, plus a sign.
+const LEN_INT: usize = 40;
+
+/// Safe API for formatting 128-bit integers to text.
+///
+/// ## Example
+///
+/// ```
+/// let mut buffer = teju::IntegerBuffer::new();
+/// assert_eq!(buffer.format_u128(u128::MAX), "340282366920938463463374607431768211455");
+/// assert_eq!(buffer.format_i128(-1234), "-1234");
+/// ```
+#[derive(Clone, Copy)]
+pub struct IntegerBuffer {
+    bytes: [MaybeUninit<u8>; LEN_INT],
+}
+
+impl IntegerBuffer {
+    /// This is a cheap operation; you don't need to worry about reusing buffers for efficiency.
+    pub fn new() -> Self {
+        IntegerBuffer { bytes: [MaybeUninit::uninit(); LEN_INT] }
+    }
+
+    /// Print an `u128` into this buffer and return a reference to its string representation.
+    pub fn format_u128(&mut self, num: u128) -> &str {
+        let ptr = self.bytes.as_mut_ptr() as *mut u8;
+        let n = unsafe { teju::fmt::print_u128(num, ptr) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= self.bytes.len());
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+
+    /// Print an `i128` into this buffer and return a reference to its string representation.
+    pub fn format_i128(&mut self, num: i128) -> &str {
+        let ptr = self.bytes.as_mut_ptr() as *mut u8;
+        let n = unsafe {
+            ptr.write(b'-');
+            let sign_len = num.is_negative() as usize;
+            sign_len + teju::fmt::print_u128(num.unsigned_abs(), ptr.add(sign_len))
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= self.bytes.len());
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+/// Prints only the type, as the contents are uninitialised or left over from previous calls.
+impl core::fmt::Debug for IntegerBuffer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IntegerBuffer").finish_non_exhaustive()
+    }
+}
+
+impl Default for IntegerBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", IntegerBuffer::new()), "IntegerBuffer { .. }");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = IntegerBuffer::new();
+        assert_eq!(buf.format_u128(0), "0");
+        assert_eq!(buf.format_u128(u128::MAX), u128::MAX.to_string());
+        assert_eq!(buf.format_i128(0), "0");
+        assert_eq!(buf.format_i128(i128::MIN), i128::MIN.to_string());
+        assert_eq!(buf.format_i128(i128::MAX), i128::MAX.to_string());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100_000))]
+
+        #[test]
+        fn u128_std(x in any::<u128>(), shift in 0u32 .. 128) {
+            let x = x >> shift;
+            assert_eq!(IntegerBuffer::new().format_u128(x), format!("{x}"));
+        }
+
+        #[test]
+        fn i128_std(x in any::<i128>(), shift in 0u32 .. 128) {
+            let x = x >> shift;
+            assert_eq!(IntegerBuffer::new().format_i128(x), format!("{x}"));
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Intervals, as `[lo, hi]` or `center±radius`.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Interval> {
+    /// Print an interval from `lo` to `hi` into this buffer as `[lo, hi]`, and return a reference to
+    /// its string representation.
+    ///
+    /// Both ends are formatted as by [Buffer::format]. They are printed as given, even if `lo` is
+    /// greater than `hi`.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_interval(1.2, 3.4), "[1.2, 3.4]");
+    /// assert_eq!(teju::Buffer::new().format_interval(-1e-7, f64::INFINITY), "[-1e-7, inf]");
+    /// ```
+    pub fn format_interval(&mut self, lo: F, hi: F) -> &str {
+        let ptr = <format::Interval as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            ptr.write(b'[');
+            let mut n = 1 + crate::write_general(lo, ptr.add(1));
+            n += teju::fmt::write_str(", ", ptr.add(n));
+            n += crate::write_general(hi, ptr.add(n));
+            ptr.add(n).write(b']');
+            n + 1
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+
+    /// Print `center` plus or minus `radius` into this buffer as `center±radius`, and return a
+    /// reference to its string representation.
+    ///
+    /// Both numbers are formatted as by [Buffer::format], except that the sign of `radius` is
+    /// dropped, so `1.2±0.1` is printed for a radius of `-0.1` too.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_pm(1.2, 0.1), "1.2±0.1");
+    /// assert_eq!(teju::Buffer::new().format_pm(-6.02e23, -1e20), "-6.02e23±1e20");
+    /// ```
+    pub fn format_pm(&mut self, center: F, radius: F) -> &str {
+        let ptr = <format::Interval as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            let mut n = crate::write_general(center, ptr);
+            n += teju::fmt::write_str("±", ptr.add(n));
+            let radius_len = crate::write_general(radius, ptr.add(n));
+            if *ptr.add(n) == b'-' {
+                core::ptr::copy(ptr.add(n + 1), ptr.add(n), radius_len - 1);
+                n + radius_len - 1
+            } else {
+                n + radius_len
+            }
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn interval() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_interval(1.5f64, 2.0), "[1.5, 2.0]");
+        assert_eq!(buf.format_interval(-2.0f64, -1.5), "[-2.0, -1.5]");
+        assert_eq!(buf.format_interval(-0.0f64, 0.0), "[-0.0, 0.0]");
+        assert_eq!(buf.format_interval(f64::NEG_INFINITY, f64::NAN), "[-inf, NaN]");
+        assert_eq!(Buffer::new().format_interval(0.1f32, 0.2), "[0.1, 0.2]");
+    }
+
+    #[test]
+    fn pm() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_pm(1.5f64, 0.25), "1.5±0.25");
+        assert_eq!(buf.format_pm(-1.5f64, -0.25), "-1.5±0.25");
+        assert_eq!(buf.format_pm(0.0f64, -0.0), "0.0±0.0");
+        assert_eq!(buf.format_pm(1e300f64, f64::NEG_INFINITY), "1e300±inf");
+        assert_eq!(buf.format_pm(f64::NAN, f64::NAN), "NaN±NaN");
+        assert_eq!(Buffer::new().format_pm(1e-45f32, 1e-45), "1e-45±1e-45");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        assert_eq!(
+            buf.format_interval(-2.2250738585072014e-308, -1.7976931348623157e-300),
+            "[-2.2250738585072014e-308, -1.7976931348623157e-300]",
+        );
+        assert_eq!(
+            buf.format_pm(-2.2250738585072014e-308, -1.7976931348623157e-300),
+            "-2.2250738585072014e-308±1.7976931348623157e-300",
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn parts(lo in any::<u64>(), hi in any::<u64>()) {
+            let (lo, hi) = (f64::from_bits(lo), f64::from_bits(hi));
+            let expected = format!("[{}, {}]", Buffer::new().format(lo), Buffer::new().format(hi));
+            assert_eq!(Buffer::new().format_interval(lo, hi), expected);
+            let radius = if hi.is_nan() { hi } else { hi.abs() };
+            let expected = format!("{}±{}", Buffer::new().format(lo), Buffer::new().format(radius));
+            assert_eq!(Buffer::new().format_pm(lo, hi), expected);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Writing to a [std::io::Write] sink.
+
+use std::io;
+
+use crate::{Buffer, Float};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Like [Self::format], but writes the string to `w` with a single
+    /// [write_all](io::Write::write_all) instead of returning it, e.g. for writing many numbers
+    /// to a file. Returns the number of bytes written. Requires the `std` feature.
+    ///
+    /// `w` is not buffered, so wrap it in an [io::BufWriter] if it makes a system call per write.
+    ///
+    /// ```
+    /// use std::io::Write;
+    ///
+    /// let mut out = Vec::new();
+    /// let mut buffer = teju::Buffer::new();
+    /// for num in [1.5, f64::NAN, 1e30] {
+    ///     buffer.format_to_writer(num, &mut out)?;
+    ///     out.write_all(b"\n")?;
+    /// }
+    /// assert_eq!(out, b"1.5\nNaN\n1e30\n");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn format_to_writer<W: io::Write + ?Sized>(&mut self, num: F, w: &mut W) -> io::Result<usize> {
+        write_all(self.format(num), w)
+    }
+}
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// Like [Self::format_exp], but writes the string to `w` as by
+    /// [Buffer::format_to_writer]. Returns the number of bytes written.
+    ///
+    /// ```
+    /// let mut out = Vec::new();
+    /// teju::Buffer::new().format_exp_to_writer(1500.0, &mut out)?;
+    /// assert_eq!(out, b"1.5e3");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn format_exp_to_writer<W: io::Write + ?Sized>(&mut self, num: F, w: &mut W) -> io::Result<usize> {
+        write_all(self.format_exp(num), w)
+    }
+}
+
+impl<F: Float> Buffer<F, format::Decimal> {
+    /// Like [Self::format_dec], but writes the string to `w` as by
+    /// [Buffer::format_to_writer]. Returns the number of bytes written.
+    ///
+    /// ```
+    /// let mut out = Vec::new();
+    /// teju::Buffer::new().format_dec_to_writer(1e3, &mut out)?;
+    /// assert_eq!(out, b"1000.0");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn format_dec_to_writer<W: io::Write + ?Sized>(&mut self, num: F, w: &mut W) -> io::Result<usize> {
+        write_all(self.format_dec(num), w)
+    }
+}
+
+/// Write all of `str` to `w`, returning its length.
+fn write_all<W: io::Write + ?Sized>(str: &str, w: &mut W) -> io::Result<usize> {
+    w.write_all(str.as_bytes())?;
+    Ok(str.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A sink which counts its calls to `write`.
+    #[derive(Default)]
+    struct Counting {
+        bytes: Vec<u8>,
+        writes: usize,
+    }
+
+    impl io::Write for Counting {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.writes += 1;
+            self.bytes.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn single_write() {
+        let mut out = Counting::default();
+        assert_eq!(Buffer::new().format_to_writer(-5e-324f64, &mut out).unwrap(), 7);
+        assert_eq!(Buffer::new().format_exp_to_writer(0.0f32, &mut out).unwrap(), 3);
+        assert_eq!(Buffer::new().format_dec_to_writer(1e20f64, &mut out).unwrap(), 23);
+        assert_eq!(out.bytes, b"-5e-3240e0100000000000000000000.0");
+        assert_eq!(out.writes, 3);
+    }
+
+    #[test]
+    fn error() {
+        let mut out = [0u8; 4];
+        let err = Buffer::new().format_to_writer(1.125f64, &mut &mut out[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+        let dyn_out: &mut dyn io::Write = &mut &mut out[..];
+        assert_eq!(Buffer::new().format_to_writer(1.5f64, dyn_out).unwrap(), 3);
+    }
+}
@@ -0,0 +1,73 @@
+//! Numbers inside JSON strings.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer as [Self::format] does, but between double
+    /// quotes, as a JSON string, and return a reference to its string representation.
+    ///
+    /// If `specials_as_null` is `true`, NaN and infinities are formatted as `null`; otherwise as
+    /// the quoted strings `"NaN"`, `"inf"`, and `"-inf"`.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_json_quoted(1.234, false), "\"1.234\"");
+    /// assert_eq!(teju::Buffer::new().format_json_quoted(f64::NAN, false), "\"NaN\"");
+    /// assert_eq!(teju::Buffer::new().format_json_quoted(f64::NAN, true), "null");
+    /// ```
+    pub fn format_json_quoted(&mut self, num: F, specials_as_null: bool) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            _ if specials_as_null => return "null",
+            teju::float::FloatType::PosInf => return "\"inf\"",
+            teju::float::FloatType::NegInf => return "\"-inf\"",
+            teju::float::FloatType::Nan => return "\"NaN\"",
+        }
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            *ptr = b'"';
+            let n = 1 + crate::write_general(num, ptr.add(1));
+            *ptr.add(n) = b'"';
+            n + 1
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn quoted() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_json_quoted(1.5f64, true), "\"1.5\"");
+        assert_eq!(buf.format_json_quoted(-0.0f64, true), "\"-0.0\"");
+        assert_eq!(buf.format_json_quoted(-2.2250738585072014e-308f64, true), "\"-2.2250738585072014e-308\"");
+        assert_eq!(Buffer::new().format_json_quoted(1e30f32, false), "\"1e30\"");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_json_quoted(f64::INFINITY, false), "\"inf\"");
+        assert_eq!(buf.format_json_quoted(f64::NEG_INFINITY, false), "\"-inf\"");
+        assert_eq!(buf.format_json_quoted(f64::NAN, false), "\"NaN\"");
+        assert_eq!(buf.format_json_quoted(f64::INFINITY, true), "null");
+        assert_eq!(buf.format_json_quoted(f64::NEG_INFINITY, true), "null");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn json_same_as_format(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let str = Buffer::new().format_json_quoted(num, false).to_owned();
+            assert_eq!(str, format!("\"{}\"", Buffer::new().format(num)));
+        }
+    }
+}
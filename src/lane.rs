@@ -0,0 +1,63 @@
+//! General notation, right-justified in fixed-width byte arrays.
+
+use crate::{Buffer, CapacityError, Float};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` as [Self::format] does, right-justified in an array of `W`
+    /// bytes padded on the left with `pad`, and return the array by value, e.g. for fixed-width
+    /// lanes of a columnar store. Returns an error if the string is longer than `W` bytes.
+    ///
+    /// A `W` of [required_len](crate::required_len) is always long enough, i.e. 24 for an `f64`
+    /// and 19 for an `f32`.
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(&buffer.format_lane::<8>(-1.5, b' ').unwrap(), b"    -1.5");
+    /// assert_eq!(&buffer.format_lane::<6>(1e300, b'_').unwrap(), b"_1e300");
+    /// assert!(buffer.format_lane::<4>(1.2345, b' ').is_err());
+    /// ```
+    pub fn format_lane<const W: usize>(&mut self, num: F, pad: u8) -> Result<[u8; W], CapacityError> {
+        let str = self.format(num);
+        let start = W.checked_sub(str.len()).ok_or(CapacityError { needed: str.len(), have: W })?;
+        let mut lane = [pad; W];
+        lane[start ..].copy_from_slice(str.as_bytes());
+        Ok(lane)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn lane() {
+        let mut buf = Buffer::new();
+        assert_eq!(&buf.format_lane::<16>(1.5f64, b' ').unwrap(), b"             1.5");
+        assert_eq!(&buf.format_lane::<3>(1.5f64, b' ').unwrap(), b"1.5");
+        assert_eq!(&buf.format_lane::<6>(f64::NAN, b'0').unwrap(), b"000NaN");
+        assert_eq!(&buf.format_lane::<24>(-2.2250738585072014e-308, b' ').unwrap(), b"-2.2250738585072014e-308");
+        assert_eq!(buf.format_lane::<0>(0.0f64, b' '), Err(CapacityError { needed: 3, have: 0 }));
+        assert_eq!(buf.format_lane::<2>(1.5f64, b' '), Err(CapacityError { needed: 3, have: 2 }));
+        assert_eq!(&Buffer::new().format_lane::<16>(f32::MIN, b'.').unwrap(), b"...-3.4028235e38");
+        assert!(Buffer::new().format_lane::<19>(-1000013200000000.0f32, b' ').is_ok());
+        assert!(Buffer::new().format_lane::<24>(-2.2250738585072014e-308f64, b' ').is_ok());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn justified(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let general = Buffer::new().format(num).to_owned();
+            let lane = Buffer::new().format_lane::<24>(num, b' ').unwrap();
+            assert_eq!(lane.as_slice(), format!("{general:>24}").as_bytes());
+            match Buffer::new().format_lane::<16>(num, b' ') {
+                Ok(lane) => assert_eq!(lane.as_slice(), format!("{general:>16}").as_bytes()),
+                Err(err) => assert_eq!(err, CapacityError { needed: general.len(), have: 16 }),
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Numbers less than one without the leading zero, as in `.5`.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// Removes the `0` before the decimal point of the `n` bytes at `ptr`, if any, for a nonzero
+/// number. Returns the new length.
+unsafe fn strip_leading_zero(ptr: *mut u8, n: usize) -> usize {
+    unsafe {
+        let zero = (*ptr == b'-') as usize;
+        if *ptr.add(zero) == b'0' {
+            core::ptr::copy(ptr.add(zero + 1), ptr.add(zero), n - zero - 1);
+            n - 1
+        } else {
+            n
+        }
+    }
+}
+
+impl<F: Float> Buffer<F, format::General> {
+    /// As [Self::format], but without the `0` before the decimal point of numbers less than one in
+    /// magnitude, e.g. `.5` and `-.001` instead of `0.5` and `-0.001`.
+    ///
+    /// Zero is still printed as `"0.0"`, or `"-0.0"`.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_no_leading_zero(0.1234), ".1234");
+    /// assert_eq!(teju::Buffer::new().format_no_leading_zero(-0.0012), "-.0012");
+    /// assert_eq!(teju::Buffer::new().format_no_leading_zero(1.5), "1.5");
+    /// ```
+    pub fn format_no_leading_zero(&mut self, num: F) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { strip_leading_zero(ptr, num.format_general_finite_nonzero(ptr)) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+impl<F: Float> Buffer<F, format::Decimal> {
+    /// As [Self::format_dec], but without the `0` before the decimal point of numbers less than
+    /// one in magnitude, e.g. `.5` and `-.001` instead of `0.5` and `-0.001`.
+    ///
+    /// Zero is still printed as `"0.0"`, or `"-0.0"`.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_dec_no_leading_zero(1e-7), ".0000001");
+    /// assert_eq!(teju::Buffer::new().format_dec_no_leading_zero(1e7), "10000000.0");
+    /// ```
+    pub fn format_dec_no_leading_zero(&mut self, num: F) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let ptr = <format::Decimal as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { strip_leading_zero(ptr, num.format_dec_finite_nonzero(ptr)) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn general() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_no_leading_zero(0.1f64), ".1");
+        assert_eq!(buf.format_no_leading_zero(-0.1f64), "-.1");
+        assert_eq!(buf.format_no_leading_zero(0.0012f64), ".0012");
+        assert_eq!(buf.format_no_leading_zero(0.00001234f64), ".00001234");
+        assert_eq!(buf.format_no_leading_zero(0.000001234f64), "1.234e-6");
+        assert_eq!(buf.format_no_leading_zero(0.99f64), ".99");
+        assert_eq!(buf.format_no_leading_zero(1.0f64), "1.0");
+        assert_eq!(buf.format_no_leading_zero(10.5f64), "10.5");
+        assert_eq!(buf.format_no_leading_zero(-1e-300f64), "-1e-300");
+        assert_eq!(Buffer::new().format_no_leading_zero(0.1f32), ".1");
+    }
+
+    #[test]
+    fn dec() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_dec_no_leading_zero(0.1f64), ".1");
+        assert_eq!(buf.format_dec_no_leading_zero(-0.0012f64), "-.0012");
+        assert_eq!(buf.format_dec_no_leading_zero(1e-10f64), ".0000000001");
+        assert_eq!(buf.format_dec_no_leading_zero(1.0f64), "1.0");
+        assert_eq!(buf.format_dec_no_leading_zero(5e-324f64).len(), 1 + 324);
+        assert_eq!(Buffer::new().format_dec_no_leading_zero(-1e-45f32), "-.000000000000000000000000000000000000000000001");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_no_leading_zero(0.0f64), "0.0");
+        assert_eq!(buf.format_no_leading_zero(-0.0f64), "-0.0");
+        assert_eq!(buf.format_no_leading_zero(f64::NAN), "NaN");
+        assert_eq!(buf.format_no_leading_zero(f64::NEG_INFINITY), "-inf");
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_dec_no_leading_zero(-0.0f64), "-0.0");
+        assert_eq!(buf.format_dec_no_leading_zero(f64::INFINITY), "inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_format(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let expected = |str: &str| match num.abs() < 1.0 && num != 0.0 {
+                true => str.replacen("0.", ".", 1),
+                false => str.to_owned(),
+            };
+            let general = Buffer::new().format(num).to_owned();
+            let general = if general.contains('e') { general } else { expected(&general) };
+            assert_eq!(Buffer::new().format_no_leading_zero(num), general);
+            assert_eq!(Buffer::new().format_dec_no_leading_zero(num), expected(Buffer::new().format_dec(num)));
+        }
+    }
+}
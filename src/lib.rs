@@ -25,45 +25,325 @@
 //! assert_eq!(teju::Buffer::new().format_dec(1e30), "1000000000000000000000000000000.0");
 //! ```
 //!
+//! ## Features
+//!
+//! - `arrayvec`: adds methods returning an owned `ArrayString`, e.g. `format_owned`, the
+//!   `FloatExt` trait, for calling them as e.g. `1.5.teju()`, and the free functions `general`,
+//!   `scientific`, and `decimal`.
+//! - `test-util`: adds the `test_util` module, with assertions for testing formatted floats, and
+//!   `roundtrips`.
+//! - `length-free-print`: prints mantissas with a routine that finds their number of digits
+//!   arithmetically, instead of computing it beforehand by comparisons. Neither is faster across
+//!   the board, depending on the distribution of the numbers, so compare both on your own
+//!   workload, e.g. with the distributions in `benches/`.
+//! - `num-complex`: adds `format_complex`, for printing `num_complex::Complex` numbers.
+//! - `caching-buffer`: adds `CachingBuffer`, which skips formatting a number equal to the last
+//!   one. This only pays off on streams where the same value often repeats consecutively.
+//! - `bytes`: adds methods appending to a `bytes::BufMut`, e.g. `format_to_buf`.
+//! - `alloc`: adds methods appending to a `String`, e.g. `format_append`, and `format_exact`.
+//! - `std`: implies `alloc`, and adds `with_buffer`, for reusing a buffer per thread, and methods
+//!   writing to a `std::io::Write`, e.g. `format_to_writer`.
+//! - `profile`: adds `format_general_instrumented`, which also returns which branch of general
+//!   notation formatted a number, for finding their distribution over some data.
+//! - `ordered-float`: implements [Float] for `ordered_float::OrderedFloat` and
+//!   `ordered_float::NotNan` of `f32` and `f64`, formatting them as the float they wrap.
+//! - `half`: implements [Float] for `half::f16` and `half::bf16`. Their buffers are no smaller
+//!   than those for `f32`, as they are sized by the bytes the mantissa printers touch rather than
+//!   by the output.
+//! - `serde`: adds the `serde` module, with functions for serializing floats with serde, e.g. with
+//!   `#[serde(serialize_with = "teju::serde::serialize")]`, or `serialize_str`, which writes them
+//!   as strings formatted by teju.
+//! - `serde_json`: implies `serde` and `alloc`, and adds `serde::serialize_json`, which serializes floats as
+//!   JSON numbers formatted by teju when serializing with `serde_json`.
+//!
 //! ## Performance
 //! 
 //! ![Microbenchmark chart comparing teju with ryu and std](https://raw.githubusercontent.com/andrepd/teju-jagua-rs/master/microbench.png)
 
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
 use core::marker::PhantomData;
 
 mod teju;
 pub use teju::float::Float;
-use teju::format::{self, Format};
+use format::Format;
+
+/// Marker types for the formats of a [Buffer], which select the methods available on it, e.g.
+/// [Buffer::format_dec] on a `Buffer<F, format::Decimal>`. They are only needed to name the type
+/// of a buffer, e.g. to get its [MAX_LEN](Buffer::MAX_LEN).
+pub mod format {
+    pub use crate::teju::format::{Format, General, Scientific, Decimal, Locale, Bounded, Fixed, Dynamic, Fraction, Interval, Both, Grouped};
+    #[cfg(feature = "num-complex")]
+    pub use crate::teju::format::Complex;
+}
+
+mod fuzz;
+pub use fuzz::fuzz_roundtrip;
+
+mod locale;
+pub use locale::LocaleCfg;
+
+#[cfg(feature = "arrayvec")]
+mod owned;
+#[cfg(feature = "arrayvec")]
+pub use owned::{general, scientific, decimal};
+#[cfg(feature = "arrayvec")]
+mod ext;
+#[cfg(feature = "arrayvec")]
+pub use ext::FloatExt;
+
+mod thresholds;
+pub use thresholds::{GeneralThresholds, InvalidThresholds};
+
+mod round;
+pub use round::RoundingMode;
+
+mod min_frac;
+
+mod compact;
+
+mod integer;
+pub use integer::IntegerBuffer;
+
+mod any;
+pub use any::AnyFloatBuffer;
+
+mod bits;
+pub use bits::subnormals_supported;
+
+mod literal;
+
+mod go;
+
+mod clamped;
+
+mod json;
+
+mod duration;
+
+mod split;
+pub use split::{MAX_SPLIT_EXP_LEN, MAX_SPLIT_MANT_LEN};
+
+mod style;
+pub use style::{Style, required_len};
+
+pub mod math;
+
+mod spans;
+pub use spans::format_with;
+
+mod formatter;
+pub use formatter::format_into_formatter;
+
+mod reverse;
+pub use reverse::format_reverse;
+
+mod specials;
+pub use specials::Specials;
+
+mod option;
+
+mod error;
+pub use error::CapacityError;
+
+mod slice;
+
+mod utf16;
+
+mod field;
+
+mod lossy;
+
+mod interval;
+
+mod bin_exp;
+
+mod grouped;
+
+mod numeric;
+
+mod accounting;
+
+mod both;
+
+mod leading_zero;
+
+mod csv;
+
+mod decade;
+
+mod scaled;
+
+mod flush;
+
+mod swift;
+
+mod fraction;
+
+mod sigdigits;
+
+mod money;
+
+mod symbolic;
+
+mod lane;
+
+mod aligned;
+
+mod toml;
+
+mod fit;
+
+mod shortest;
+pub use shortest::to_decimal;
+
+mod display;
+pub use display::{Displayable, display};
+
+mod write;
+pub use write::write_into;
+
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg(feature = "test-util")]
+pub use test_util::roundtrips;
+
+#[cfg(feature = "num-complex")]
+mod complex;
+
+#[cfg(feature = "caching-buffer")]
+mod caching;
+#[cfg(feature = "caching-buffer")]
+pub use caching::CachingBuffer;
+
+#[cfg(feature = "bytes")]
+mod buf_mut;
+
+#[cfg(feature = "alloc")]
+mod append;
+#[cfg(feature = "alloc")]
+mod exact;
+#[cfg(feature = "alloc")]
+pub use exact::format_exact;
+
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+pub use pool::with_buffer;
+#[cfg(feature = "std")]
+mod io;
+
+#[cfg(feature = "profile")]
+mod profile;
+#[cfg(feature = "profile")]
+pub use profile::BranchId;
+
+#[cfg(feature = "ordered-float")]
+mod ordered;
+
+#[cfg(feature = "serde")]
+pub mod serde;
 
 /// Safe API for formatting floating point numbers to text.
 ///
+/// The `&str` returned by each method carries the length computed while printing, so calling
+/// `len()` on it is free: it reads the length stored in the reference, without measuring the
+/// string again.
+///
+/// The output of [Buffer::format], [Buffer::format_exp], and [Buffer::format_dec] (and their
+/// variants) is always ASCII, which is checked by debug assertions.
+///
 /// ## Example
 ///
 /// ```
 /// let mut buffer = teju::Buffer::new();
 /// let printed = buffer.format(1.234);
 /// assert_eq!(printed, "1.234");
+///
+/// let mut out = [0u8; 64];
+/// let mut cursor = 0;
+/// for num in [1.5, -0.25, 1e30] {
+///     let printed = buffer.format(num);
+///     out[cursor .. cursor + printed.len()].copy_from_slice(printed.as_bytes());
+///     cursor += printed.len();
+/// }
+/// assert_eq!(&out[..cursor], b"1.5-0.251e30");
 /// ```
 #[derive(Clone, Copy)]
 pub struct Buffer<F: Float, Fmt: Format> {
     float: PhantomData<F>,
-    bytes: Fmt::Buffer,
+    bytes: Fmt::Buffer<F>,
 }
 
 const POS_INF: &str = "inf";
 const NEG_INF: &str = "-inf";
 const NAN: &str = "NaN";
+// Zero is written as `1.0` would be, with the digit `1` replaced by `0`. Methods with parameters
+// that change the layout of `1.0` (e.g. a precision) print zero with the same layout, rather than
+// returning these constants.
 const POS_ZERO: &str = "0.0";
 const NEG_ZERO: &str = "-0.0";
 const POS_ZERO_EXP: &str = "0e0";
 const NEG_ZERO_EXP: &str = "-0e0";
 
+/// Print `num` as [Buffer::format] does, to `buf`, and return the number of bytes written.
+///
+/// # Safety
+///
+/// `buf` must be valid for writes of as many bytes as [Buffer::format] may need.
+unsafe fn write_general<F: Float>(num: F, buf: *mut u8) -> usize {
+    let special = match num.classify() {
+        teju::float::FloatType::Finite => match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => return unsafe { num.format_general_finite_nonzero(buf) },
+        },
+        teju::float::FloatType::PosInf => POS_INF,
+        teju::float::FloatType::NegInf => NEG_INF,
+        teju::float::FloatType::Nan => NAN,
+    };
+    unsafe { teju::fmt::write_str(special, buf) }
+}
+
 impl<F: Float, Fmt: Format> Buffer<F, Fmt> {
+    /// Size in bytes of this buffer, which is an upper bound on the length of any string printed
+    /// into it. It depends on both the float type and the format, e.g. `f32`s are printed in
+    /// decimal notation with much fewer digits than `f64`s.
+    ///
+    /// ```
+    /// use teju::{Buffer, format};
+    /// type DecBuffer<F> = Buffer<F, format::Decimal>;
+    ///
+    /// let mut out = [0u8; DecBuffer::<f32>::MAX_LEN];
+    /// let mut buffer = DecBuffer::new();
+    /// let printed = buffer.format_dec(f32::MIN);
+    /// out[..printed.len()].copy_from_slice(printed.as_bytes());
+    /// assert!(DecBuffer::<f32>::MAX_LEN < DecBuffer::<f64>::MAX_LEN);
+    /// ```
+    pub const MAX_LEN: usize = <Fmt::Buffer<F> as teju::format::Bytes>::LEN;
+
     /// This is a cheap operation; you don't need to worry about reusing buffers for efficiency.
     pub fn new() -> Self {
-        Buffer { float: PhantomData, bytes: Fmt::new_buffer() }
+        Buffer { float: PhantomData, bytes: Fmt::new_buffer::<F>() }
+    }
+}
+
+/// Prints only the types, e.g. `Buffer<f64, General> { .. }`, as the contents are uninitialised
+/// or left over from previous calls.
+///
+/// ```
+/// let mut buffer = teju::Buffer::new();
+/// buffer.format(1.5f64);
+/// assert_eq!(format!("{buffer:?}"), "Buffer<f64, General> { .. }");
+/// ```
+impl<F: Float, Fmt: Format> core::fmt::Debug for Buffer<F, Fmt> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Buffer<{}, {}> {{ .. }}", core::any::type_name::<F>(), Fmt::NAME)
     }
 }
 
@@ -84,8 +364,13 @@ impl<F: Float> Buffer<F, format::General> {
     /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
     /// infinity as `"-inf"`, to match [core::fmt].
     ///
+    /// The output is the same as that of `{:?}` in [core::fmt], except that numbers with
+    /// `1e-5 <= |num| < 1e-4` are printed in decimal notation (`0.000015` rather than `1.5e-5`),
+    /// and that a number exactly halfway between two shortest representations is printed with the
+    /// even one (`203492458983362.62` rather than `203492458983362.63`).
+    ///
     /// If `num` is known to be finite, you may get better performance by calling the
-    /// [Self::format_exp_finite] method instead of format to avoid the checks for special cases.
+    /// [Self::format_finite] method instead of format to avoid the checks for special cases.
     ///
     /// ```
     /// assert_eq!(teju::Buffer::new().format(3.14159), "3.14159");
@@ -100,6 +385,18 @@ impl<F: Float> Buffer<F, format::General> {
         }
     }
 
+    /// Like [Self::format], but takes `num` by reference, for iterators over `&F`.
+    ///
+    /// ```
+    /// let data = [1.5, -0.25, 1e30];
+    /// let mut buffer = teju::Buffer::new();
+    /// let strs: Vec<String> = data.iter().map(|x| buffer.format_ref(x).to_owned()).collect();
+    /// assert_eq!(strs, ["1.5", "-0.25", "1e30"]);
+    /// ```
+    pub fn format_ref(&mut self, num: &F) -> &str {
+        self.format(*num)
+    }
+
     /// Print a floating point `num` into this buffer, and return a reference to its string
     /// representation, **provided that `num.is_finite()`**.
     ///
@@ -118,10 +415,61 @@ impl<F: Float> Buffer<F, format::General> {
             teju::float::FiniteFloatType::NegZero => return NEG_ZERO,
             teju::float::FiniteFloatType::Nonzero => (),
         }
-        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr(&mut self.bytes);
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
         let n = unsafe { num.format_general_finite_nonzero(ptr) };
         let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
-        debug_assert!(n <= self.bytes.len());
+        debug_assert!(n <= Self::MAX_LEN);
+        debug_assert!(slice.is_ascii());
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+
+    /// Like [Self::format_finite], but takes `num` by reference, for iterators over `&F`.
+    ///
+    /// ```
+    /// let data = [1.5, -0.25, 1e30];
+    /// let mut buffer = teju::Buffer::new();
+    /// let strs: Vec<String> = data.iter().map(|x| buffer.format_finite_ref(x).to_owned()).collect();
+    /// assert_eq!(strs, ["1.5", "-0.25", "1e30"]);
+    /// ```
+    pub fn format_finite_ref(&mut self, num: &F) -> &str {
+        self.format_finite(*num)
+    }
+
+    /// Print a floating point `num` into this buffer, and return a reference to its string
+    /// representation, which is byte-for-byte identical to the output of [`ryu`]'s
+    /// `Buffer::format`.
+    ///
+    /// For `f64` this is the same as [Self::format]. For `f32`, `ryu` switches to scientific
+    /// notation at different magnitudes: numbers are written as decimals if they are at least
+    /// `1e-6` and less than `1e13`, rather than at least `1e-5` and less than `1e16`.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt] (and `ryu`).
+    ///
+    /// [`ryu`]: https://docs.rs/ryu/
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format(1e14f32), "100000000000000.0");
+    /// assert_eq!(teju::Buffer::new().format_ryu_compatible(1e14f32), "1e14");
+    /// assert_eq!(teju::Buffer::new().format_ryu_compatible(1e14f64), "100000000000000.0");
+    /// ```
+    pub fn format_ryu_compatible(&mut self, num: F) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return POS_INF,
+            teju::float::FloatType::NegInf => return NEG_INF,
+            teju::float::FloatType::Nan => return NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { num.format_ryu_finite_nonzero(ptr) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        debug_assert!(slice.is_ascii());
         unsafe { core::str::from_utf8_unchecked(slice) }
     }
 }
@@ -131,7 +479,9 @@ impl<F: Float> Buffer<F, format::Scientific> {
     /// reference to its string representation.
     ///
     /// The number is always formatted in the form `[mantissa]e[exponent]`, where `mantissa` is a
-    /// number between 1 (inclusive) and 10 (exclusive), even if `exponent` is `0`.
+    /// number between 1 (inclusive) and 10 (exclusive), even if `exponent` is `0`. The mantissa has
+    /// a decimal point only if it has more than one digit, so e.g. powers of ten are printed as
+    /// `1e3` (see [Self::format_exp_with_point] for `1.0e3`).
     /// 
     /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
     /// infinity as `"-inf"`, to match [core::fmt].
@@ -152,6 +502,18 @@ impl<F: Float> Buffer<F, format::Scientific> {
         }
     }
 
+    /// Like [Self::format_exp], but takes `num` by reference, for iterators over `&F`.
+    ///
+    /// ```
+    /// let data = [1.5, -0.25, 1e30];
+    /// let mut buffer = teju::Buffer::new();
+    /// let strs: Vec<String> = data.iter().map(|x| buffer.format_exp_ref(x).to_owned()).collect();
+    /// assert_eq!(strs, ["1.5e0", "-2.5e-1", "1e30"]);
+    /// ```
+    pub fn format_exp_ref(&mut self, num: &F) -> &str {
+        self.format_exp(*num)
+    }
+
     /// Print a floating point `num` into this buffer in scientific notation, and return a
     /// reference to its string representation, provied that `num.is_finite()`.
     /// 
@@ -171,12 +533,71 @@ impl<F: Float> Buffer<F, format::Scientific> {
             teju::float::FiniteFloatType::NegZero => return NEG_ZERO_EXP,
             teju::float::FiniteFloatType::Nonzero => (),
         }
-        let ptr = <format::Scientific as teju::format::Sealed>::buffer_as_ptr(&mut self.bytes);
+        let ptr = <format::Scientific as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
         let n = unsafe { num.format_exp_finite_nonzero(ptr) };
         let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
-        debug_assert!(n <= self.bytes.len());
+        debug_assert!(n <= Self::MAX_LEN);
+        debug_assert!(slice.is_ascii());
         unsafe { core::str::from_utf8_unchecked(slice) }
     }
+
+    /// Like [Self::format_exp_finite], but takes `num` by reference, for iterators over `&F`.
+    ///
+    /// ```
+    /// let data = [1.5, -0.25, 1e30];
+    /// let mut buffer = teju::Buffer::new();
+    /// let strs: Vec<String> = data.iter().map(|x| buffer.format_exp_finite_ref(x).to_owned()).collect();
+    /// assert_eq!(strs, ["1.5e0", "-2.5e-1", "1e30"]);
+    /// ```
+    pub fn format_exp_finite_ref(&mut self, num: &F) -> &str {
+        self.format_exp_finite(*num)
+    }
+
+    /// Print a floating point `num` into this buffer in scientific notation with at least
+    /// `min_sig` significant digits, and return a reference to its string representation.
+    ///
+    /// The mantissa is the shortest one that round-trips, as in [Self::format_exp], padded with
+    /// zeros after the decimal point if it has fewer than `min_sig` digits. Longer mantissas are
+    /// never shortened, and the exponent is unaffected by padding. `min_sig` is capped at 17, since
+    /// no `f64` needs more significant digits than that.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_exp_min_sig(5.0, 2), "5.0e0");
+    /// assert_eq!(teju::Buffer::new().format_exp_min_sig(1.5e-7, 4), "1.500e-7");
+    /// assert_eq!(teju::Buffer::new().format_exp_min_sig(137.035999177, 2), "1.37035999177e2");
+    /// ```
+    pub fn format_exp_min_sig(&mut self, num: F, min_sig: usize) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return POS_INF,
+            teju::float::FloatType::NegInf => return NEG_INF,
+            teju::float::FloatType::Nan => return NAN,
+        }
+        let ptr = <format::Scientific as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { num.format_exp_min_sig_finite(ptr, min_sig.min(teju::format::MAX_MIN_SIG)) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        debug_assert!(slice.is_ascii());
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+
+    /// Like [Self::format_exp], but always printing a decimal point in the mantissa, with a `0`
+    /// after it if there are no other digits, e.g. `"1.0e0"` instead of `"1e0"`.
+    ///
+    /// Only single-digit mantissas are padded, so this is the same as
+    /// [format_exp_min_sig(num, 2)](Self::format_exp_min_sig).
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_exp_with_point(1.0), "1.0e0");
+    /// assert_eq!(teju::Buffer::new().format_exp_with_point(-1e30), "-1.0e30");
+    /// assert_eq!(teju::Buffer::new().format_exp_with_point(1.5), "1.5e0");
+    /// ```
+    pub fn format_exp_with_point(&mut self, num: F) -> &str {
+        self.format_exp_min_sig(num, 2)
+    }
 }
 
 impl<F: Float> Buffer<F, format::Decimal> {
@@ -204,6 +625,18 @@ impl<F: Float> Buffer<F, format::Decimal> {
         }
     }
 
+    /// Like [Self::format_dec], but takes `num` by reference, for iterators over `&F`.
+    ///
+    /// ```
+    /// let data = [1.5, -0.25, 1e3];
+    /// let mut buffer = teju::Buffer::new();
+    /// let strs: Vec<String> = data.iter().map(|x| buffer.format_dec_ref(x).to_owned()).collect();
+    /// assert_eq!(strs, ["1.5", "-0.25", "1000.0"]);
+    /// ```
+    pub fn format_dec_ref(&mut self, num: &F) -> &str {
+        self.format_dec(*num)
+    }
+
     /// Print a floating point `num` into this buffer in decimal notation, and return a reference
     /// to its string representation, provied that `num.is_finite()`.
     /// 
@@ -222,10 +655,81 @@ impl<F: Float> Buffer<F, format::Decimal> {
             teju::float::FiniteFloatType::NegZero => return NEG_ZERO,
             teju::float::FiniteFloatType::Nonzero => (),
         }
-        let ptr = <format::Decimal as teju::format::Sealed>::buffer_as_ptr(&mut self.bytes);
+        let ptr = <format::Decimal as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
         let n = unsafe { num.format_dec_finite_nonzero(ptr) };
         let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
-        debug_assert!(n <= self.bytes.len());
+        debug_assert!(n <= Self::MAX_LEN);
+        debug_assert!(slice.is_ascii());
         unsafe { core::str::from_utf8_unchecked(slice) }
     }
+
+    /// Like [Self::format_dec_finite], but takes `num` by reference, for iterators over `&F`.
+    ///
+    /// ```
+    /// let data = [1.5, -0.25, 1e3];
+    /// let mut buffer = teju::Buffer::new();
+    /// let strs: Vec<String> = data.iter().map(|x| buffer.format_dec_finite_ref(x).to_owned()).collect();
+    /// assert_eq!(strs, ["1.5", "-0.25", "1000.0"]);
+    /// ```
+    pub fn format_dec_finite_ref(&mut self, num: &F) -> &str {
+        self.format_dec_finite(*num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Zero is printed in the same style as `1.0`, in every mode.
+    #[test]
+    fn zero_as_one() {
+        macro_rules! check {
+            ($($method:ident($($arg:expr),*);)*) => {$(
+                for sign in [1.0f64, -1.0] {
+                    let zero = Buffer::new().$method(0.0 * sign, $($arg),*).to_owned();
+                    let one = Buffer::new().$method(sign, $($arg),*).replace('1', "0");
+                    assert_eq!(zero, one, "{} of {}0", stringify!($method), sign);
+                }
+            )*};
+        }
+        check! {
+            format(); format_exp(); format_dec(); format_ryu_compatible(); format_bits();
+            format_exp_min_sig(4); format_exp_with_point(); format_exp_grouped(b'_', 3); format_bin_exp();
+            format_round(3, false); format_round(3, true); format_round(0, false);
+            format_fixed_scale(2); format_min_frac(4); format_compact(8); format_shortest_string();
+            format_as(Style::Scientific); format_with_thresholds(GeneralThresholds::new(-3, 21).unwrap());
+            format_locale(LocaleCfg { decimal_sep: b',', group_sep: b'.', grouping: &[3] });
+            format_clamped(1e-9, 1e9); format_lossy(10); format_go(); format_duration_secs();
+        }
+        assert_eq!(Buffer::new().format_fixed_field(0.0, 4, 2), Some("+0.000e+00"));
+        assert_eq!(Buffer::new().format_fixed_field(1.0, 4, 2), Some("+1.000e+00"));
+    }
+
+    #[test]
+    fn exp_with_point() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_exp_with_point(1.0f64), "1.0e0");
+        assert_eq!(buf.format_exp_with_point(10.0f64), "1.0e1");
+        assert_eq!(buf.format_exp_with_point(1.5f64), "1.5e0");
+        assert_eq!(buf.format_exp_with_point(-1.25e-7f64), "-1.25e-7");
+        assert_eq!(buf.format_exp_with_point(5e-324f64), "5.0e-324");
+        assert_eq!(buf.format_exp_with_point(-0.0f64), "-0.0e0");
+        assert_eq!(buf.format_exp_with_point(f64::NAN), "NaN");
+        assert_eq!(Buffer::new().format_exp_with_point(1e38f32), "1.0e38");
+    }
+
+    /// `f32`s are formatted with their own shortest representation, which is usually not that of
+    /// the same number widened to `f64`.
+    #[test]
+    fn f32_not_widened() {
+        for (num, str, widened) in [
+            (0.1f32, "0.1", "0.10000000149011612"),
+            (3.4028235e38, "3.4028235e38", "3.4028234663852886e38"),
+            (1e-45, "1e-45", "1.401298464324817e-45"),
+            (16777216.0, "16777216.0", "16777216.0"),
+        ] {
+            assert_eq!(Buffer::new().format(num), str);
+            assert_eq!(Buffer::new().format(f64::from(num)), widened);
+        }
+    }
 }
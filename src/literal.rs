@@ -0,0 +1,87 @@
+//! Rust literal syntax, for generating source code.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer as a Rust expression which evaluates to
+    /// `num`, and return a reference to it.
+    ///
+    /// Finite numbers are formatted as by [Self::format], followed by a type suffix, e.g.
+    /// `1.234_f64`. NaN and infinities, which have no literal syntax, are formatted as the paths of
+    /// the corresponding constants, e.g. `f64::NAN`, `f64::INFINITY`, and `f64::NEG_INFINITY`.
+    ///
+    /// Note that negative numbers are printed with a leading `-`, which in Rust is a unary
+    /// operator rather than part of the literal.
+    ///
+    /// Only `f32` and `f64` (and their [ordered-float](https://docs.rs/ordered-float) wrappers)
+    /// have literals; calling this for any other float type, such as `half::f16`, fails to compile.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_rust_literal(1.234), "1.234_f64");
+    /// assert_eq!(teju::Buffer::new().format_rust_literal(-1e30f32), "-1e30_f32");
+    /// assert_eq!(teju::Buffer::new().format_rust_literal(f64::NAN), "f64::NAN");
+    /// assert_eq!(teju::Buffer::new().format_rust_literal(-1. / 0.), "f64::NEG_INFINITY");
+    /// ```
+    #[cfg_attr(feature = "half", doc = "```compile_fail")]
+    #[cfg_attr(feature = "half", doc = "teju::Buffer::new().format_rust_literal(half::f16::ONE);")]
+    #[cfg_attr(feature = "half", doc = "```")]
+    #[cfg_attr(feature = "half", doc = "```compile_fail")]
+    #[cfg_attr(feature = "half", doc = "teju::Buffer::new().format_rust_literal(half::bf16::ONE);")]
+    #[cfg_attr(feature = "half", doc = "```")]
+    pub fn format_rust_literal(&mut self, num: F) -> &str {
+        // `f16` is unstable and is not `half::f16`, and `bf16` is not a suffix at all. The buffer
+        // is sized for a suffix of `_` and 3 bytes.
+        const {
+            assert!(matches!(F::NAME.as_bytes(), b"f32" | b"f64"), "no Rust literal syntax for this float type")
+        };
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            let special = match num.classify() {
+                teju::float::FloatType::Finite => None,
+                teju::float::FloatType::PosInf => Some("::INFINITY"),
+                teju::float::FloatType::NegInf => Some("::NEG_INFINITY"),
+                teju::float::FloatType::Nan => Some("::NAN"),
+            };
+            match special {
+                Some(constant) => {
+                    let n = teju::fmt::write_str(F::NAME, ptr);
+                    n + teju::fmt::write_str(constant, ptr.add(n))
+                },
+                None => {
+                    let n = match num.classify_finite() {
+                        teju::float::FiniteFloatType::PosZero => teju::fmt::write_str(crate::POS_ZERO, ptr),
+                        teju::float::FiniteFloatType::NegZero => teju::fmt::write_str(crate::NEG_ZERO, ptr),
+                        teju::float::FiniteFloatType::Nonzero => num.format_general_finite_nonzero(ptr),
+                    };
+                    *ptr.add(n) = b'_';
+                    n + 1 + teju::fmt::write_str(F::NAME, ptr.add(n + 1))
+                },
+            }
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literals() {
+        assert_eq!(Buffer::new().format_rust_literal(0.0f64), "0.0_f64");
+        assert_eq!(Buffer::new().format_rust_literal(-0.0f32), "-0.0_f32");
+        assert_eq!(Buffer::new().format_rust_literal(1000.0f64), "1000.0_f64");
+        assert_eq!(Buffer::new().format_rust_literal(-2.2250738585072014e-308f64), "-2.2250738585072014e-308_f64");
+        assert_eq!(Buffer::new().format_rust_literal(-1.2345678901234568e-5f64), "-0.000012345678901234568_f64");
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(Buffer::new().format_rust_literal(f64::NAN), "f64::NAN");
+        assert_eq!(Buffer::new().format_rust_literal(f64::INFINITY), "f64::INFINITY");
+        assert_eq!(Buffer::new().format_rust_literal(f32::NEG_INFINITY), "f32::NEG_INFINITY");
+    }
+}
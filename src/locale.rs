@@ -0,0 +1,137 @@
+//! Decimal notation with locale-specific separators.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// The separators used by [Buffer::format_locale].
+///
+/// Separators must be single ASCII bytes, so that the length of the output is bounded and known
+/// up front. To use a non-ASCII separator (e.g. U+202F NARROW NO-BREAK SPACE), format with an ASCII
+/// placeholder and substitute it when copying the result into a wider buffer.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub struct LocaleCfg {
+    /// Written in place of the decimal point.
+    pub decimal_sep: u8,
+    /// Written between groups of digits in the integer part.
+    pub group_sep: u8,
+    /// Number of digits in each group, counting from the decimal point, with the last repeating:
+    /// e.g. `&[3]` for thousands, or `&[3, 2]` for the Indian numbering system (`12,34,567`). If
+    /// empty, digits are not grouped.
+    pub grouping: &'static [u8],
+}
+
+impl<F: Float> Buffer<F, format::Locale> {
+    /// Print a floating point `num` into this buffer in decimal notation, using the decimal and
+    /// digit group separators given by `cfg`, and return a reference to its string representation.
+    ///
+    /// The number is formatted as by [Buffer::format_dec], except that the decimal point is
+    /// replaced by `cfg.decimal_sep`, and `cfg.group_sep` is inserted between the groups of digits
+    /// of the integer part given by `cfg.grouping`.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// use teju::LocaleCfg;
+    /// let de = LocaleCfg { decimal_sep: b',', group_sep: b'.', grouping: &[3] };
+    /// let en = LocaleCfg { decimal_sep: b'.', group_sep: b',', grouping: &[3] };
+    /// let in_ = LocaleCfg { decimal_sep: b'.', group_sep: b',', grouping: &[3, 2] };
+    /// assert_eq!(teju::Buffer::new().format_locale(1234567.89, de), "1.234.567,89");
+    /// assert_eq!(teju::Buffer::new().format_locale(1234567.89, en), "1,234,567.89");
+    /// assert_eq!(teju::Buffer::new().format_locale(1234567.89, in_), "12,34,567.89");
+    /// assert_eq!(teju::Buffer::new().format_locale(-0.5, de), "-0,5");
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `cfg.decimal_sep` or `cfg.group_sep` is not ASCII, or if any group size in
+    /// `cfg.grouping` is `0`.
+    pub fn format_locale(&mut self, num: F, cfg: LocaleCfg) -> &str {
+        assert!(cfg.decimal_sep.is_ascii() && cfg.group_sep.is_ascii(), "Separators must be ASCII");
+        assert!(!cfg.grouping.contains(&0), "Group sizes must be nonzero");
+
+        let ptr = <format::Locale as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        // Write the decimal string at the end of the buffer, then move it to the front while
+        // inserting separators.
+        let src = unsafe { ptr.add(format::len_locale(F::LIMITS) - format::len_dec(F::LIMITS)) };
+        let len = match num.classify() {
+            teju::float::FloatType::Finite => match num.classify_finite() {
+                teju::float::FiniteFloatType::PosZero => unsafe { teju::fmt::write_str(crate::POS_ZERO, src) },
+                teju::float::FiniteFloatType::NegZero => unsafe { teju::fmt::write_str(crate::NEG_ZERO, src) },
+                teju::float::FiniteFloatType::Nonzero => unsafe { num.format_dec_finite_nonzero(src) },
+            },
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        };
+
+        let n = unsafe {
+            teju::fmt::localise(src, len, ptr, cfg.decimal_sep, cfg.group_sep, cfg.grouping)
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DE: LocaleCfg = LocaleCfg { decimal_sep: b',', group_sep: b'.', grouping: &[3] };
+    const IN: LocaleCfg = LocaleCfg { decimal_sep: b'.', group_sep: b',', grouping: &[3, 2] };
+    const NONE: LocaleCfg = LocaleCfg { decimal_sep: b'.', group_sep: b',', grouping: &[] };
+
+    #[test]
+    fn separators() {
+        assert_eq!(Buffer::new().format_locale(1234567.5f64, DE), "1.234.567,5");
+        assert_eq!(Buffer::new().format_locale(1234567.5f32, DE), "1.234.567,5");
+        assert_eq!(Buffer::new().format_locale(-1234567.5f64, DE), "-1.234.567,5");
+        assert_eq!(Buffer::new().format_locale(123.0f64, DE), "123,0");
+        assert_eq!(Buffer::new().format_locale(0.001234f64, DE), "0,001234");
+        assert_eq!(Buffer::new().format_locale(1e30f64, DE), "1.000.000.000.000.000.000.000.000.000.000,0");
+        assert_eq!(Buffer::new().format_locale(0.0f64, DE), "0,0");
+        assert_eq!(Buffer::new().format_locale(-0.0f64, DE), "-0,0");
+    }
+
+    #[test]
+    fn indian() {
+        assert_eq!(Buffer::new().format_locale(1234567.0f64, IN), "12,34,567.0");
+        assert_eq!(Buffer::new().format_locale(-123456789.5f64, IN), "-12,34,56,789.5");
+        assert_eq!(Buffer::new().format_locale(1234.5f64, IN), "1,234.5");
+        assert_eq!(Buffer::new().format_locale(999.0f64, IN), "999.0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_group() {
+        let cfg = LocaleCfg { decimal_sep: b'.', group_sep: b',', grouping: &[3, 0] };
+        Buffer::new().format_locale(1234567.0f64, cfg);
+    }
+
+    #[test]
+    fn no_grouping() {
+        for num in [1234567.5, 1e300, 5e-324, f64::MAX] {
+            assert_eq!(Buffer::new().format_locale(num, NONE), Buffer::new().format_dec(num));
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        let cfg = LocaleCfg { decimal_sep: b'.', group_sep: b'_', grouping: &[1] };
+        let mut buf = Buffer::new();
+        let str = buf.format_locale(-f64::MAX, cfg);
+        assert_eq!(str.len(), 1 + 309 + 308 + 2);
+        assert_eq!(str.replace('_', ""), Buffer::new().format_dec(-f64::MAX));
+        assert_eq!(Buffer::new().format_locale(-5e-324, cfg), Buffer::new().format_dec(-5e-324));
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(Buffer::new().format_locale(f64::NAN, DE), "NaN");
+        assert_eq!(Buffer::new().format_locale(f64::INFINITY, DE), "inf");
+        assert_eq!(Buffer::new().format_locale(f64::NEG_INFINITY, DE), "-inf");
+    }
+}
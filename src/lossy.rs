@@ -0,0 +1,191 @@
+//! Lossy formatting, with fewer digits than needed to round-trip exactly.
+
+use core::str::FromStr;
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float + FromStr> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer with the fewest significant digits which parse
+    /// back to within `max_ulps` units in the last place of `num`, and return a reference to its
+    /// string representation. The output is otherwise formatted as by [Self::format].
+    ///
+    /// The candidates are the shortest representation of `num` (as printed by [Self::format])
+    /// rounded to 1, 2, ... significant digits, with ties rounding to even; each is parsed back with
+    /// [str::parse] and the first within `max_ulps` is returned. A candidate which parses to a
+    /// different sign or to infinity is never accepted. With `max_ulps = 0` the output is the same
+    /// as that of [Self::format].
+    ///
+    /// This is much slower than [Self::format], as it may parse up to 16 candidates.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_lossy(0.30000000000000004, 0), "0.30000000000000004");
+    /// assert_eq!(buffer.format_lossy(0.30000000000000004, 1), "0.3");
+    /// assert_eq!(buffer.format_lossy(3.14159, 1 << 42), "3.14");
+    /// assert_eq!(teju::Buffer::new().format_lossy(1.0000001f32, 1), "1.0");
+    /// ```
+    pub fn format_lossy(&mut self, num: F, max_ulps: u64) -> &str {
+        self.format_fewest_digits(num, |parsed| parsed.to_bits_u64().abs_diff(num.to_bits_u64()) <= max_ulps)
+    }
+
+    /// Print `num` as [Self::format], but with the fewest significant digits for which `accept`
+    /// returns `true` for the parsed string. `accept` is only called with finite numbers of the
+    /// same sign as `num`, and if no shorter candidate is accepted, the shortest representation is
+    /// printed.
+    fn format_fewest_digits(&mut self, num: F, accept: impl Fn(F) -> bool) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+
+        let decimal = unsafe { num.to_decimal_finite() };
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let within = |n: usize| {
+            let str = unsafe { core::str::from_utf8_unchecked(core::slice::from_raw_parts(ptr, n)) };
+            str.parse::<F>().is_ok_and(|parsed| {
+                matches!(parsed.classify(), teju::float::FloatType::Finite)
+                    && parsed.is_sign_negative() == num.is_sign_negative()
+                    && accept(parsed)
+            })
+        };
+
+        let len = teju::fmt::len_u64(decimal.mant);
+        let n = (1 .. len)
+            .map(|digits| unsafe { F::format_general_decimal(decimal.round_sig(digits), ptr) })
+            .find(|&n| within(n));
+        let n = match n {
+            Some(n) => n,
+            None => unsafe { F::format_general_decimal(decimal, ptr) },
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+impl<F: Float + FromStr + Into<f64>> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer with the fewest significant digits which parse
+    /// back to within a relative error of `rel_tol` of `num`, and return a reference to its string
+    /// representation. The output is otherwise formatted as by [Self::format].
+    ///
+    /// The candidates are the same as for [Self::format_lossy], and each is accepted if, parsed
+    /// back to `parsed`, `|parsed - num| <= rel_tol * |num|` (computed in `f64`). So e.g. a
+    /// `rel_tol` of `1e-6` keeps about 6 significant digits, but fewer if they happen to be
+    /// zeros. With `rel_tol = 0.0`, or NaN, the output is the same as that of [Self::format].
+    ///
+    /// This is much slower than [Self::format], as it may parse up to 16 candidates.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_rel(3.14159265, 1e-7), "3.1415926");
+    /// assert_eq!(buffer.format_rel(3.14159265, 1e-6), "3.14159");
+    /// assert_eq!(buffer.format_rel(3.14159265, 1e-2), "3.14");
+    /// assert_eq!(buffer.format_rel(-2.0000001e-30, 1e-6), "-2e-30");
+    /// assert_eq!(buffer.format_rel(0.1 + 0.2, 0.0), "0.30000000000000004");
+    /// ```
+    pub fn format_rel(&mut self, num: F, rel_tol: f64) -> &str {
+        let num_f64: f64 = num.into();
+        self.format_fewest_digits(num, |parsed| {
+            let parsed: f64 = parsed.into();
+            (parsed - num_f64).abs() <= rel_tol * num_f64.abs()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn lossy() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_lossy(0.1f64 + 0.2, 0), "0.30000000000000004");
+        assert_eq!(buf.format_lossy(0.1f64 + 0.2, 1), "0.3");
+        assert_eq!(buf.format_lossy(-123.456f64, u64::MAX), "-100.0");
+        assert_eq!(buf.format_lossy(9.96f64, u64::MAX), "10.0");
+        assert_eq!(buf.format_lossy(1e-300f64, 3), "1e-300");
+        assert_eq!(buf.format_lossy(5e-324f64, 0), "5e-324");
+        // `1.8e308`, ..., `1.7977e308` parse to infinity.
+        assert_eq!(buf.format_lossy(f64::MAX, u64::MAX), "1.79769e308");
+        assert_eq!(Buffer::new().format_lossy(16777215.0f32, 1), "16777215.0");
+        assert_eq!(Buffer::new().format_lossy(16777215.0f32, 3), "16777220.0");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_lossy(-0.0f64, 10), "-0.0");
+        assert_eq!(buf.format_lossy(f64::INFINITY, 10), "inf");
+        assert_eq!(buf.format_lossy(f64::NAN, 10), "NaN");
+        assert_eq!(buf.format_rel(-0.0f64, 0.1), "-0.0");
+        assert_eq!(buf.format_rel(f64::NEG_INFINITY, 0.1), "-inf");
+        assert_eq!(buf.format_rel(f64::NAN, 0.1), "NaN");
+    }
+
+    #[test]
+    fn rel() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_rel(0.1f64 + 0.2, 1e-15), "0.3");
+        assert_eq!(buf.format_rel(123456.0f64, 1e-3), "123500.0");
+        assert_eq!(buf.format_rel(123456.0f64, 1e-2), "123000.0");
+        assert_eq!(buf.format_rel(123456.0f64, 3e-2), "120000.0");
+        assert_eq!(buf.format_rel(123456.0f64, 0.5), "100000.0");
+        assert_eq!(buf.format_rel(-9.96f64, 0.01), "-10.0");
+        assert_eq!(buf.format_rel(f64::MAX, 1.0), "1.79769e308");
+        assert_eq!(buf.format_rel(5e-324f64, 0.0), "5e-324");
+        assert_eq!(buf.format_rel(1.2345f64, f64::NAN), "1.2345");
+        assert_eq!(buf.format_rel(1.2345f64, -1.0), "1.2345");
+        assert_eq!(Buffer::new().format_rel(16777215.0f32, 1e-7), "16777215.0");
+        assert_eq!(Buffer::new().format_rel(16777215.0f32, 3e-7), "16777220.0");
+        assert_eq!(Buffer::new().format_rel(0.1f32, 0.0), "0.1");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100_000))]
+
+        #[test]
+        fn exact_shortest(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            assert_eq!(Buffer::new().format_lossy(num, 0), Buffer::new().format(num));
+        }
+
+        #[test]
+        fn within_ulps(bits in any::<u64>(), max_ulps in 0u64 .. 1000) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite());
+            let str = Buffer::new().format_lossy(num, max_ulps).to_owned();
+            let parsed = str.parse::<f64>().unwrap();
+            assert!(parsed.to_bits().abs_diff(bits) <= max_ulps, "{str} for {num:?}");
+            let digits = str.split('e').next().unwrap().trim_end_matches(".0").bytes()
+                .skip_while(|&c| matches!(c, b'-' | b'0' | b'.'))
+                .filter(u8::is_ascii_digit)
+                .count();
+            assert!(digits <= Buffer::new().format(num).bytes().filter(u8::is_ascii_digit).count());
+        }
+
+        #[test]
+        fn within_rel(bits in any::<u64>(), tol_exp in -17i32 .. 1) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite());
+            let rel_tol = 10f64.powi(tol_exp);
+            let str = Buffer::new().format_rel(num, rel_tol).to_owned();
+            let parsed = str.parse::<f64>().unwrap();
+            assert!((parsed - num).abs() <= rel_tol * num.abs(), "{str} for {num:?}");
+        }
+    }
+}
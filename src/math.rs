@@ -0,0 +1,31 @@
+//! The integer approximations of logarithms which [teju](crate) uses internally, for crates which
+//! need to stay consistent with it.
+
+pub use crate::teju::common::{EXP_LOG10_POW2_BOUNDS, exp_log10_pow2, exp_log10_pow2_residual};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log10_pow2() {
+        for e in EXP_LOG10_POW2_BOUNDS {
+            let expected = (e as f64 * core::f64::consts::LOG10_2).floor() as i32;
+            assert_eq!(exp_log10_pow2(e), expected, "{e}");
+        }
+    }
+
+    #[test]
+    fn residual() {
+        let mut e0 = *EXP_LOG10_POW2_BOUNDS.start();
+        for e in EXP_LOG10_POW2_BOUNDS {
+            if exp_log10_pow2(e) != exp_log10_pow2(e0) {
+                e0 = e;
+            }
+            // The first block may be cut short by the start of the range.
+            if e0 != *EXP_LOG10_POW2_BOUNDS.start() {
+                assert_eq!(exp_log10_pow2_residual(e), (e - e0) as u32, "{e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Decimal notation with a minimum number of digits after the decimal point.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Fixed> {
+    /// Print a floating point `num` into this buffer in decimal notation, with at least
+    /// `min_frac` digits after the decimal point, and return a reference to its string
+    /// representation.
+    ///
+    /// The shortest representation of `num` (as printed by [Buffer::format_dec]) is padded with
+    /// zeros up to `min_frac` fractional digits. Unlike [Buffer::format_round], it is never
+    /// rounded: if `num` needs more than `min_frac` digits, all of them are printed. At least one
+    /// digit is always printed after the decimal point, so if `min_frac` is `0` or `1` this is the
+    /// same as [Buffer::format_dec].
+    ///
+    /// `min_frac` is capped at 324: no `f64` has significant digits past that.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_min_frac(1.5, 2), "1.50");
+    /// assert_eq!(teju::Buffer::new().format_min_frac(1.2345, 2), "1.2345");
+    /// assert_eq!(teju::Buffer::new().format_min_frac(3.0, 2), "3.00");
+    /// ```
+    pub fn format_min_frac(&mut self, num: F, min_frac: usize) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        let decimal = unsafe { num.to_decimal_finite() }.trim_trailing_zeros();
+        let precision = min_frac.clamp(1, format::MAX_PRECISION).max((-decimal.exp).max(0) as usize);
+
+        let ptr = <format::Fixed as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { decimal.format_fixed(ptr, precision) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn pad() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_min_frac(1.5f64, 2), "1.50");
+        assert_eq!(buf.format_min_frac(1.2345f64, 2), "1.2345");
+        assert_eq!(buf.format_min_frac(-3.0f64, 2), "-3.00");
+        assert_eq!(buf.format_min_frac(1e20f64, 1), "100000000000000000000.0");
+        assert_eq!(buf.format_min_frac(1.5e-5f64, 8), "0.00001500");
+        assert_eq!(buf.format_min_frac(0.0f64, 2), "0.00");
+        assert_eq!(buf.format_min_frac(-0.0f64, 0), "-0.0");
+        assert_eq!(Buffer::new().format_min_frac(0.1f32, 3), "0.100");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        let str = buf.format_min_frac(-f64::MAX, 1000);
+        assert_eq!(str.len(), 1 + 309 + 1 + 324);
+        assert_eq!(buf.format_min_frac(5e-324f64, 2), format!("0.{}5", "0".repeat(323)));
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(Buffer::new().format_min_frac(f64::NAN, 2), "NaN");
+        assert_eq!(Buffer::new().format_min_frac(f64::NEG_INFINITY, 2), "-inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn pads_dec(bits in any::<u64>(), min_frac in 0usize .. 20) {
+            let num = f64::from_bits(bits);
+            let str = Buffer::new().format_min_frac(num, min_frac).to_owned();
+            let dec = Buffer::new().format_dec(num).to_owned();
+            if let Some((_, frac)) = dec.split_once('.') {
+                let zeros = min_frac.saturating_sub(frac.len());
+                assert_eq!(str, dec + &"0".repeat(zeros));
+            } else {
+                assert_eq!(str, dec);
+            }
+        }
+    }
+}
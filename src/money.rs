@@ -0,0 +1,122 @@
+//! Monetary amounts, rounded to the cent and grouped in thousands, as in `1,234.57`.
+
+use crate::{Buffer, Float, RoundingMode, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Fixed> {
+    /// Print a floating point `num` into this buffer as a monetary amount, with exactly two digits
+    /// after the decimal point and a `,` between every three digits of the integer part, and return
+    /// a reference to its string representation.
+    ///
+    /// The number is rounded to the cent with ties resolved by `mode`. As in
+    /// [Self::format_round], ties are decided on the exact value of `num`, so e.g. `2.675` is not a
+    /// tie, since the closest `f64` is slightly below it, and rounds to `2.67`. Negative amounts
+    /// are printed with a minus sign (see [Self::format_accounting] for parentheses); negative zero,
+    /// and negative amounts which round to zero, are printed as `0.00`.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// use teju::RoundingMode;
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_money(1234567.891, RoundingMode::HalfEven), "1,234,567.89");
+    /// assert_eq!(buffer.format_money(0.125, RoundingMode::HalfEven), "0.12");
+    /// assert_eq!(buffer.format_money(0.125, RoundingMode::HalfAwayFromZero), "0.13");
+    /// assert_eq!(buffer.format_money(-2.675, RoundingMode::HalfAwayFromZero), "-2.67");
+    /// ```
+    pub fn format_money(&mut self, num: F, mode: RoundingMode) -> &str {
+        const SCALE: usize = 2;
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        let mut decimal = mode.round(num, SCALE);
+        decimal.sign |= decimal.mant == 0;
+
+        let ptr = <format::Fixed as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        // Write the fixed-point string after room for the separators, then move it to the front
+        // while inserting them.
+        let n = unsafe {
+            let src = ptr.add(format::money_separators(F::LIMITS));
+            let len = decimal.format_fixed(src, SCALE);
+            teju::fmt::localise(src, len, ptr, b'.', b',', &[3])
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn money() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_money(0.0f64, RoundingMode::HalfEven), "0.00");
+        assert_eq!(buf.format_money(1.5f64, RoundingMode::HalfEven), "1.50");
+        assert_eq!(buf.format_money(999.999f64, RoundingMode::HalfEven), "1,000.00");
+        assert_eq!(buf.format_money(-1234.5f64, RoundingMode::HalfEven), "-1,234.50");
+        assert_eq!(buf.format_money(123456.0f64, RoundingMode::HalfEven), "123,456.00");
+        assert_eq!(buf.format_money(1e20f64, RoundingMode::HalfEven), "100,000,000,000,000,000,000.00");
+        assert_eq!(buf.format_money(0.004f64, RoundingMode::HalfAwayFromZero), "0.00");
+        assert_eq!(Buffer::new().format_money(-1234.5f32, RoundingMode::HalfEven), "-1,234.50");
+    }
+
+    #[test]
+    fn ties() {
+        let mut buf = Buffer::new();
+        for (num, even, away) in [
+            (0.125f64, "0.12", "0.13"), (0.135, "0.14", "0.14"), (-0.125, "-0.12", "-0.13"),
+            (1234.625, "1,234.62", "1,234.63"),
+            // Ties in the shortest representation, but not in binary.
+            (2.675, "2.67", "2.67"), (2.665, "2.67", "2.67"), (0.005, "0.01", "0.01"),
+            (-0.005, "-0.01", "-0.01"), (1234.565, "1,234.57", "1,234.57"),
+        ] {
+            assert_eq!(buf.format_money(num, RoundingMode::HalfEven), even);
+            assert_eq!(buf.format_money(num, RoundingMode::HalfAwayFromZero), away);
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        let str = buf.format_money(-f64::MAX, RoundingMode::HalfEven);
+        assert_eq!(str.len(), 1 + 309 + 102 + 3);
+        assert!(str.starts_with("-179,769,313,486,231,570,000,") && str.ends_with(",000.00"), "{str}");
+        assert_eq!(buf.format_money(-5e-324f64, RoundingMode::HalfAwayFromZero), "0.00");
+        assert_eq!(Buffer::new().format_money(f32::MAX, RoundingMode::HalfEven).len(), 39 + 12 + 3);
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_money(-0.0f64, RoundingMode::HalfEven), "0.00");
+        assert_eq!(buf.format_money(f64::NAN, RoundingMode::HalfEven), "NaN");
+        assert_eq!(buf.format_money(f64::INFINITY, RoundingMode::HalfEven), "inf");
+        assert_eq!(buf.format_money(f64::NEG_INFINITY, RoundingMode::HalfEven), "-inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_fixed_scale(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite());
+            let fixed = Buffer::new().format_fixed_scale(num, 2).to_owned();
+            let fixed = if fixed == "-0.00" { "0.00" } else { &fixed };
+            let mut buf = Buffer::new();
+            let money = buf.format_money(num, RoundingMode::HalfEven);
+            assert_eq!(money.replace(',', ""), fixed);
+            let int = money.trim_start_matches('-').split('.').next().unwrap();
+            assert!(int.split(',').skip(1).all(|g| g.len() == 3), "{money}");
+            assert!((1 ..= 3).contains(&int.split(',').next().unwrap().len()), "{money}");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Decimal notation for SQL `NUMERIC` columns.
+
+use crate::{Buffer, Float, RoundingMode, teju};
+use teju::format;
+
+/// Maximum number of digits of a SQL `NUMERIC` column, as in e.g. `NUMERIC(38, 2)`.
+const NUMERIC_PRECISION: usize = 38;
+
+impl<F: Float> Buffer<F, format::Fixed> {
+    /// Print a floating point `num` into this buffer as a literal for a SQL `NUMERIC(38, scale)`
+    /// column, with at most `max_scale` digits after the decimal point, and return a reference to
+    /// its string representation.
+    ///
+    /// The number is always printed in decimal notation, rounded to `max_scale` digits with ties
+    /// rounding to even, as by [Self::format_round], and without trailing zeros after the decimal
+    /// point, nor the decimal point itself for integers. Zero, and numbers which round to zero, are
+    /// printed as `0`, without a sign.
+    ///
+    /// Returns `None` if `num` is NaN or infinite, or if it has more than `38 - max_scale` digits
+    /// before the decimal point, which the column cannot hold: the integer digits are never
+    /// truncated.
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_numeric(1234.5678, 2), Some("1234.57"));
+    /// assert_eq!(buffer.format_numeric(-2.5, 0), Some("-2"));
+    /// assert_eq!(buffer.format_numeric(1e20, 4), Some("100000000000000000000"));
+    /// assert_eq!(buffer.format_numeric(1e36, 4), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max_scale` is greater than 38.
+    pub fn format_numeric(&mut self, num: F, max_scale: usize) -> Option<&str> {
+        assert!(max_scale <= NUMERIC_PRECISION, "Scale must be at most {NUMERIC_PRECISION}");
+        if !matches!(num.classify(), teju::float::FloatType::Finite) {
+            return None
+        }
+        let decimal = RoundingMode::HalfEven.round(num, max_scale).trim_trailing_zeros();
+        if decimal.mant == 0 {
+            return Some("0")
+        }
+
+        let int_len = teju::fmt::len_u64(decimal.mant) as i32 + decimal.exp;
+        if int_len > (NUMERIC_PRECISION - max_scale) as i32 {
+            return None
+        }
+        let precision = (-decimal.exp).max(0) as usize;
+
+        let ptr = <format::Fixed as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { decimal.format_fixed(ptr, precision) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        Some(unsafe { core::str::from_utf8_unchecked(slice) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn numeric() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_numeric(1.5f64, 4), Some("1.5"));
+        assert_eq!(buf.format_numeric(100.0f64, 4), Some("100"));
+        assert_eq!(buf.format_numeric(0.125f64, 2), Some("0.12"));
+        assert_eq!(buf.format_numeric(0.135f64, 2), Some("0.14"));
+        assert_eq!(buf.format_numeric(9.999f64, 2), Some("10"));
+        assert_eq!(buf.format_numeric(-0.001f64, 2), Some("0"));
+        assert_eq!(buf.format_numeric(-0.0f64, 2), Some("0"));
+        assert_eq!(buf.format_numeric(1e-30f64, 38), Some("0.000000000000000000000000000001"));
+        assert_eq!(Buffer::new().format_numeric(0.1f32, 10), Some("0.1"));
+    }
+
+    #[test]
+    fn precision() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_numeric(1e37f64, 0), Some("10000000000000000000000000000000000000"));
+        assert_eq!(buf.format_numeric(1e38f64, 0), None);
+        assert_eq!(buf.format_numeric(-1e35f64, 2), Some("-100000000000000000000000000000000000"));
+        assert_eq!(buf.format_numeric(-1e36f64, 2), None);
+        assert_eq!(buf.format_numeric(0.5f64, 38), Some("0.5"));
+        assert_eq!(buf.format_numeric(1.0f64, 38), None);
+        assert_eq!(buf.format_numeric(9.99f64, 37), Some("9.99"));
+        assert_eq!(buf.format_numeric(99.99f64, 37), None);
+        // Rounding carries into a new integer digit.
+        assert_eq!(buf.format_numeric(9.99f64, 1), Some("10"));
+        assert_eq!(buf.format_numeric(999.99f64, 1), Some("1000"));
+        assert_eq!(buf.format_numeric(f64::MAX, 0), None);
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_numeric(f64::NAN, 2), None);
+        assert_eq!(buf.format_numeric(f64::INFINITY, 2), None);
+        assert_eq!(buf.format_numeric(f64::NEG_INFINITY, 2), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_too_large() {
+        Buffer::new().format_numeric(1.5f64, 39);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_round(bits in any::<u64>(), max_scale in 0usize ..= 38) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite());
+            let rounded = Buffer::new().format_round(num, max_scale, true).to_owned();
+            let rounded = rounded.strip_suffix(".0").unwrap_or(&rounded);
+            let rounded = if rounded == "-0" { "0" } else { rounded };
+            let int_part = rounded.trim_start_matches('-').split('.').next().unwrap();
+            let int_len = if int_part == "0" { 0 } else { int_part.len() };
+            let expected = (int_len <= 38 - max_scale).then_some(rounded);
+            assert_eq!(Buffer::new().format_numeric(num, max_scale), expected);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Optional numbers, with a string for missing values.
+
+use crate::{Buffer, Float};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// As [Self::format] for `Some` numbers, and `none_token` for `None`, e.g. `""`, `"NA"`, or
+    /// `"null"` for missing values in tabular data.
+    ///
+    /// ```
+    /// let column = [Some(1.5), None, Some(-0.0)];
+    /// let mut buffer = teju::Buffer::new();
+    /// let cells: Vec<String> = column.iter().map(|&x| buffer.format_opt(x, "NA").to_owned()).collect();
+    /// assert_eq!(cells, ["1.5", "NA", "-0.0"]);
+    /// ```
+    pub fn format_opt<'a>(&'a mut self, value: Option<F>, none_token: &'a str) -> &'a str {
+        match value {
+            Some(num) => self.format(num),
+            None => none_token,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn some_and_none() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_opt(Some(1e30f64), ""), "1e30");
+        assert_eq!(buf.format_opt(None::<f64>, ""), "");
+        assert_eq!(buf.format_opt(None::<f64>, "null"), "null");
+        assert_eq!(buf.format_opt(Some(f64::NAN), "null"), "NaN");
+        assert_eq!(Buffer::new().format_opt(Some(0.1f32), "NA"), "0.1");
+    }
+}
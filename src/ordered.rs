@@ -0,0 +1,160 @@
+//! [Float] for the wrappers of `ordered-float`, delegating to the wrapped float.
+
+use ordered_float::{NotNan, OrderedFloat};
+
+use crate::teju::{common::SignedDecimal, float::{FiniteFloatType, FloatType, Sealed}};
+
+macro_rules! delegate {
+    ($ty:ty, $inner:ty, |$x:ident| $unwrap:expr) => {
+        impl crate::Float for $ty {}
+
+        impl Sealed for $ty {
+            const NAME: &'static str = <$inner as Sealed>::NAME;
+            const LIMITS: crate::teju::format::Limits = <$inner as Sealed>::LIMITS;
+            const EXACT_INT_LIMIT_BITS: u64 = <$inner as Sealed>::EXACT_INT_LIMIT_BITS;
+            const QUIET_NAN_BIT: u64 = <$inner as Sealed>::QUIET_NAN_BIT;
+
+            type BufferExp = <$inner as Sealed>::BufferExp;
+            type BufferDec = <$inner as Sealed>::BufferDec;
+            type BufferLocale = <$inner as Sealed>::BufferLocale;
+            type BufferFixed = <$inner as Sealed>::BufferFixed;
+
+            #[inline]
+            fn classify(&self) -> FloatType {
+                let $x = *self;
+                Sealed::classify(&$unwrap)
+            }
+
+            #[inline]
+            fn is_sign_negative(&self) -> bool {
+                let $x = *self;
+                Sealed::is_sign_negative(&$unwrap)
+            }
+
+            #[inline]
+            fn to_bits_u64(&self) -> u64 {
+                let $x = *self;
+                Sealed::to_bits_u64(&$unwrap)
+            }
+
+            #[inline]
+            fn classify_finite(&self) -> FiniteFloatType {
+                let $x = *self;
+                Sealed::classify_finite(&$unwrap)
+            }
+
+            #[inline]
+            fn classify_bits(&self) -> FloatType {
+                let $x = *self;
+                Sealed::classify_bits(&$unwrap)
+            }
+
+            #[inline]
+            fn classify_finite_bits(&self) -> FiniteFloatType {
+                let $x = *self;
+                Sealed::classify_finite_bits(&$unwrap)
+            }
+
+            #[inline]
+            unsafe fn to_decimal_finite(self) -> SignedDecimal {
+                let $x = self;
+                unsafe { Sealed::to_decimal_finite($unwrap) }
+            }
+
+            #[inline]
+            unsafe fn format_general_decimal(decimal: SignedDecimal, buf: *mut u8) -> usize {
+                unsafe { <$inner as Sealed>::format_general_decimal(decimal, buf) }
+            }
+
+            #[inline]
+            unsafe fn format_exp_decimal(decimal: SignedDecimal, buf: *mut u8) -> usize {
+                unsafe { <$inner as Sealed>::format_exp_decimal(decimal, buf) }
+            }
+
+            #[inline]
+            unsafe fn format_general_finite_nonzero(self, buf: *mut u8) -> usize {
+                let $x = self;
+                unsafe { Sealed::format_general_finite_nonzero($unwrap, buf) }
+            }
+
+            #[inline]
+            unsafe fn format_exp_finite_nonzero(self, buf: *mut u8) -> usize {
+                let $x = self;
+                unsafe { Sealed::format_exp_finite_nonzero($unwrap, buf) }
+            }
+
+            #[inline]
+            unsafe fn format_dec_finite_nonzero(self, buf: *mut u8) -> usize {
+                let $x = self;
+                unsafe { Sealed::format_dec_finite_nonzero($unwrap, buf) }
+            }
+
+            #[inline]
+            unsafe fn format_ryu_finite_nonzero(self, buf: *mut u8) -> usize {
+                let $x = self;
+                unsafe { Sealed::format_ryu_finite_nonzero($unwrap, buf) }
+            }
+
+            #[inline]
+            unsafe fn format_general_bounded_finite_nonzero(self, buf: *mut u8, low: i32, high: i32) -> usize {
+                let $x = self;
+                unsafe { Sealed::format_general_bounded_finite_nonzero($unwrap, buf, low, high) }
+            }
+
+            #[inline]
+            unsafe fn format_exp_min_sig_finite(self, buf: *mut u8, min_sig: usize) -> usize {
+                let $x = self;
+                unsafe { Sealed::format_exp_min_sig_finite($unwrap, buf, min_sig) }
+            }
+
+            #[inline]
+            unsafe fn to_binary_finite_nonzero(self) -> (u64, i32) {
+                let $x = self;
+                unsafe { Sealed::to_binary_finite_nonzero($unwrap) }
+            }
+
+            #[inline]
+            unsafe fn format_bin_exp_finite_nonzero(self, buf: *mut u8) -> usize {
+                let $x = self;
+                unsafe { Sealed::format_bin_exp_finite_nonzero($unwrap, buf) }
+            }
+        }
+    };
+}
+
+delegate!(OrderedFloat<f64>, f64, |x| x.0);
+delegate!(OrderedFloat<f32>, f32, |x| x.0);
+delegate!(NotNan<f64>, f64, |x| x.into_inner());
+delegate!(NotNan<f32>, f32, |x| x.into_inner());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+
+    #[test]
+    fn ordered_float() {
+        assert_eq!(Buffer::new().format(OrderedFloat(1.5f64)), "1.5");
+        assert_eq!(Buffer::new().format_exp(OrderedFloat(-1500.0f32)), "-1.5e3");
+        assert_eq!(Buffer::new().format_dec(OrderedFloat(1e-7f64)), "0.0000001");
+        assert_eq!(Buffer::new().format(OrderedFloat(f64::NAN)), "NaN");
+        assert_eq!(Buffer::new().format(OrderedFloat(-0.0f64)), "-0.0");
+    }
+
+    #[test]
+    fn not_nan() {
+        assert_eq!(Buffer::new().format(NotNan::new(1.5f64).unwrap()), "1.5");
+        assert_eq!(Buffer::new().format_finite(NotNan::new(0.1f32).unwrap()), "0.1");
+        assert_eq!(Buffer::new().format_round(NotNan::new(2.675f64).unwrap(), 2, false), "2.67");
+        assert_eq!(Buffer::new().format(NotNan::new(f64::NEG_INFINITY).unwrap()), "-inf");
+    }
+
+    #[test]
+    fn same_as_inner() {
+        for num in [0.1, -2.5e-300, 1e300, f64::MIN_POSITIVE, 5e-324] {
+            assert_eq!(Buffer::new().format(OrderedFloat(num)), Buffer::new().format(num));
+            assert_eq!(Buffer::new().format_both(NotNan::new(num).unwrap()), Buffer::new().format_both(num));
+            assert_eq!(Buffer::new().format_rust_literal(OrderedFloat(-num)), Buffer::new().format_rust_literal(-num));
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Formatting to owned, fixed-capacity strings.
+
+use arrayvec::ArrayString;
+
+use crate::{Buffer, Float};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Like [Self::format], but returns the string by value instead of borrowing this buffer.
+    ///
+    /// ```
+    /// let strs: Vec<_> = [1.5, 1e30].into_iter().map(|x| teju::Buffer::new().format_owned(x)).collect();
+    /// assert_eq!(strs[0].as_str(), "1.5");
+    /// assert_eq!(strs[1].as_str(), "1e30");
+    /// ```
+    pub fn format_owned(&mut self, num: F) -> ArrayString<{format::LEN_EXP}> {
+        let mut str = ArrayString::new();
+        str.push_str(self.format(num));
+        str
+    }
+}
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// Like [Self::format_exp], but returns the string by value instead of borrowing this buffer.
+    ///
+    /// ```
+    /// let strs: Vec<_> = [1.5, 1e30].into_iter().map(|x| teju::Buffer::new().format_exp_owned(x)).collect();
+    /// assert_eq!(strs[0].as_str(), "1.5e0");
+    /// assert_eq!(strs[1].as_str(), "1e30");
+    /// ```
+    pub fn format_exp_owned(&mut self, num: F) -> ArrayString<{format::LEN_EXP}> {
+        let mut str = ArrayString::new();
+        str.push_str(self.format_exp(num));
+        str
+    }
+}
+
+impl<F: Float> Buffer<F, format::Decimal> {
+    /// Like [Self::format_dec], but returns the string by value instead of borrowing this buffer.
+    ///
+    /// ```
+    /// let strs: Vec<_> = [1.5, 1e30].into_iter().map(|x| teju::Buffer::new().format_dec_owned(x)).collect();
+    /// assert_eq!(strs[0].as_str(), "1.5");
+    /// assert_eq!(strs[1].as_str(), "1000000000000000000000000000000.0");
+    /// ```
+    pub fn format_dec_owned(&mut self, num: F) -> ArrayString<{format::LEN_DEC}> {
+        let mut str = ArrayString::new();
+        str.push_str(self.format_dec(num));
+        str
+    }
+}
+
+/// Format `num` as [Buffer::format], into an owned string.
+///
+/// This creates a [Buffer] on the stack, so it can be called from any thread without sharing
+/// state. Reusing a [Buffer] is no faster, as creating one is free.
+///
+/// ```
+/// assert_eq!(teju::general(1.5).as_str(), "1.5");
+/// assert_eq!(teju::general(1e30f32).as_str(), "1e30");
+/// ```
+pub fn general<F: Float>(num: F) -> ArrayString<{format::LEN_EXP}> {
+    Buffer::new().format_owned(num)
+}
+
+/// Format `num` as [Buffer::format_exp], into an owned string.
+///
+/// ```
+/// assert_eq!(teju::scientific(1.5).as_str(), "1.5e0");
+/// ```
+pub fn scientific<F: Float>(num: F) -> ArrayString<{format::LEN_EXP}> {
+    Buffer::new().format_exp_owned(num)
+}
+
+/// Format `num` as [Buffer::format_dec], into an owned string.
+///
+/// ```
+/// assert_eq!(teju::decimal(1e30).as_str(), "1000000000000000000000000000000.0");
+/// ```
+pub fn decimal<F: Float>(num: F) -> ArrayString<{format::LEN_DEC}> {
+    Buffer::new().format_dec_owned(num)
+}
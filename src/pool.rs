@@ -0,0 +1,69 @@
+//! A buffer per thread, for reusing across calls.
+
+use core::cell::RefCell;
+
+use crate::{Buffer, format};
+
+std::thread_local! {
+    static BUFFER: RefCell<Buffer<f64, format::General>> = RefCell::new(Buffer::new());
+}
+
+/// Call `f` with this thread's [Buffer] for `f64`s in general notation, and return its result.
+///
+/// Each thread has its own buffer, so there is no contention between threads. If `f` itself calls
+/// `with_buffer`, the inner call gets a new buffer instead. Requires the `std` feature.
+///
+/// This does not make formatting faster: [Buffer::new] does not initialise its bytes, so creating
+/// a buffer on the stack for each number costs nothing, and the thread-local access is an extra
+/// cost. On the distributions in `benches/`, formatting through `with_buffer` was ~5-15% slower
+/// than with a new buffer per number. It is only a convenience, e.g. for code which cannot easily
+/// keep a buffer around.
+///
+/// ```
+/// let len = teju::with_buffer(|buffer| buffer.format(1.5).len());
+/// assert_eq!(len, 3);
+///
+/// let mut out = String::new();
+/// teju::with_buffer(|buffer| out.push_str(buffer.format(-0.25)));
+/// assert_eq!(out, "-0.25");
+/// ```
+pub fn with_buffer<R>(f: impl FnOnce(&mut Buffer<f64, format::General>) -> R) -> R {
+    BUFFER.with(|buffer| match buffer.try_borrow_mut() {
+        Ok(mut buffer) => f(&mut buffer),
+        Err(_) => f(&mut Buffer::new()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pooled() {
+        assert_eq!(with_buffer(|buf| buf.format(1e30).to_owned()), "1e30");
+        assert_eq!(with_buffer(|buf| buf.format(f64::NAN).to_owned()), "NaN");
+    }
+
+    #[test]
+    fn nested() {
+        let (outer, inner) = with_buffer(|outer| {
+            let outer = outer.format(1.5);
+            let inner = with_buffer(|inner| inner.format(2.5).to_owned());
+            (outer.to_owned(), inner)
+        });
+        assert_eq!((outer.as_str(), inner.as_str()), ("1.5", "2.5"));
+    }
+
+    #[test]
+    fn threads() {
+        let handles: Vec<_> = (0 .. 8)
+            .map(|i| std::thread::spawn(move || {
+                (0 .. 1000).all(|j| {
+                    let num = (i * 1000 + j) as f64 / 8.0;
+                    with_buffer(|buf| buf.format(num) == Buffer::new().format(num))
+                })
+            }))
+            .collect();
+        assert!(handles.into_iter().all(|h| h.join().unwrap()));
+    }
+}
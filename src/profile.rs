@@ -0,0 +1,123 @@
+//! Which branch of general notation formats each number, for profiling.
+//!
+//! Requires the `profile` feature.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// The branch of [Buffer::format] which formatted a number, as returned by
+/// [Buffer::format_general_instrumented].
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+#[derive(Hash)]
+pub enum BranchId {
+    /// NaN or an infinity.
+    Special,
+    /// Positive or negative zero.
+    Zero,
+    /// An integer from 1 to 9, e.g. `7.0`, which skips the shortest digits search.
+    SingleDigitInteger,
+    /// An integer of at most 16 digits, e.g. `12340000000.0`.
+    Integer,
+    /// A number with at most 16 digits before the decimal point, e.g. `123.4`.
+    Fraction,
+    /// A number with at most 4 zeros after the decimal point, e.g. `0.001234`.
+    LeadingZeros,
+    /// Scientific notation with a single-digit mantissa, e.g. `1e30`.
+    SingleDigitExp,
+    /// Scientific notation, e.g. `1.234e33`.
+    Exp,
+}
+
+impl<F: Float> Buffer<F, format::General> {
+    /// As [Self::format], but also returns which branch formatted `num`. This is slower than
+    /// [Self::format], and is only meant for finding the distribution of branches over some data.
+    ///
+    /// ```
+    /// use teju::BranchId;
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_general_instrumented(123.4), ("123.4", BranchId::Fraction));
+    /// assert_eq!(buffer.format_general_instrumented(1e30), ("1e30", BranchId::SingleDigitExp));
+    /// ```
+    pub fn format_general_instrumented(&mut self, num: F) -> (&str, BranchId) {
+        let branch = match (num.classify(), num.classify_finite()) {
+            (teju::float::FloatType::Finite, teju::float::FiniteFloatType::Nonzero) => {
+                // Same conditions as `format_general_finite_nonzero`.
+                let (low, high) = (format::GENERAL_LOW, format::GENERAL_HIGH);
+                let decimal = unsafe { num.to_decimal_finite() };
+                let mant_len = teju::fmt::len_u64(decimal.mant);
+                let decimal_exp = mant_len as i32 + decimal.exp;
+                if decimal.exp == 0 && decimal.mant < 10 {
+                    BranchId::SingleDigitInteger
+                } else if decimal.exp >= 0 && decimal_exp <= high {
+                    BranchId::Integer
+                } else if 0 < decimal_exp && decimal_exp <= high {
+                    BranchId::Fraction
+                } else if low < decimal_exp && decimal_exp <= 0 {
+                    BranchId::LeadingZeros
+                } else if mant_len == 1 {
+                    BranchId::SingleDigitExp
+                } else {
+                    BranchId::Exp
+                }
+            },
+            (teju::float::FloatType::Finite, _) => BranchId::Zero,
+            _ => BranchId::Special,
+        };
+        (self.format(num), branch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// The branch which must have printed `str`, judging by its shape.
+    fn branch_of(str: &str) -> BranchId {
+        let unsigned = str.trim_start_matches('-');
+        match unsigned.split_once('e') {
+            _ if matches!(str, "NaN" | "inf" | "-inf") => BranchId::Special,
+            _ if unsigned == "0.0" => BranchId::Zero,
+            Some((mant, _)) if mant.len() == 1 => BranchId::SingleDigitExp,
+            Some(_) => BranchId::Exp,
+            None if unsigned.len() == 3 && unsigned.ends_with(".0") => BranchId::SingleDigitInteger,
+            None if unsigned.ends_with(".0") => BranchId::Integer,
+            None if unsigned.starts_with("0.") => BranchId::LeadingZeros,
+            None => BranchId::Fraction,
+        }
+    }
+
+    #[test]
+    fn branches() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_general_instrumented(f64::NAN), ("NaN", BranchId::Special));
+        assert_eq!(buf.format_general_instrumented(-0.0f64), ("-0.0", BranchId::Zero));
+        assert_eq!(buf.format_general_instrumented(-7.0f64), ("-7.0", BranchId::SingleDigitInteger));
+        assert_eq!(buf.format_general_instrumented(1234e7f64), ("12340000000.0", BranchId::Integer));
+        assert_eq!(buf.format_general_instrumented(0.5f64), ("0.5", BranchId::LeadingZeros));
+        assert_eq!(buf.format_general_instrumented(1.5f64), ("1.5", BranchId::Fraction));
+        assert_eq!(buf.format_general_instrumented(1234e-6f64), ("0.001234", BranchId::LeadingZeros));
+        assert_eq!(Buffer::new().format_general_instrumented(1e-7f32), ("1e-7", BranchId::SingleDigitExp));
+        assert_eq!(buf.format_general_instrumented(1234e30f64), ("1.234e33", BranchId::Exp));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn shape_f64(bits in any::<u64>()) {
+            let mut buf = Buffer::new();
+            let (str, branch) = buf.format_general_instrumented(f64::from_bits(bits));
+            assert_eq!(branch, branch_of(str), "{str}");
+        }
+
+        #[test]
+        fn shape_f32(bits in any::<u32>()) {
+            let mut buf = Buffer::new();
+            let (str, branch) = buf.format_general_instrumented(f32::from_bits(bits));
+            assert_eq!(branch, branch_of(str), "{str}");
+        }
+    }
+}
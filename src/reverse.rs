@@ -0,0 +1,162 @@
+//! Formatting back to front, into the tail of a buffer.
+
+use crate::{Float, teju};
+use teju::format;
+
+/// Writes bytes into `out` back to front.
+struct RevWriter<'a> {
+    out: &'a mut [u8],
+    /// Index of the first byte written so far.
+    pos: usize,
+}
+
+impl RevWriter<'_> {
+    fn byte(&mut self, byte: u8) {
+        self.pos -= 1;
+        self.out[self.pos] = byte;
+    }
+
+    fn str(&mut self, str: &str) {
+        self.pos -= str.len();
+        self.out[self.pos .. self.pos + str.len()].copy_from_slice(str.as_bytes());
+    }
+
+    fn zeros(&mut self, n: usize) {
+        self.pos -= n;
+        self.out[self.pos .. self.pos + n].fill(b'0');
+    }
+
+    /// Write the digits of `x` from least to most significant, with a decimal point before the
+    /// last `point` digits if `point` is not `0`.
+    fn digits(&mut self, mut x: u64, point: usize) {
+        let mut len = 0;
+        loop {
+            if len == point && point != 0 {
+                self.byte(b'.');
+            }
+            self.byte(b'0' + (x % 10) as u8);
+            x /= 10;
+            len += 1;
+            if x == 0 {
+                break
+            }
+        }
+    }
+}
+
+/// Print a floating point `num` as [Buffer::format](crate::Buffer::format) does, but right-aligned
+/// at the end of `out`, and return the index in `out` where the string starts.
+///
+/// The string is written back to front, from the least significant digit, so its length doesn't
+/// need to be known in advance; this suits serialisers which fill their buffers from the end.
+///
+/// ```
+/// let mut out = [b' '; 16];
+/// let start = teju::format_reverse(-1.25e-30, &mut out);
+/// assert_eq!(&out[start..], b"-1.25e-30");
+/// assert_eq!(&out, b"       -1.25e-30");
+/// ```
+///
+/// ## Panics
+///
+/// Panics if `out` is too short for the output. A buffer of 24 bytes is always long enough.
+pub fn format_reverse<F: Float>(num: F, out: &mut [u8]) -> usize {
+    let mut w = RevWriter { pos: out.len(), out };
+    match num.classify() {
+        teju::float::FloatType::Finite => (),
+        teju::float::FloatType::PosInf => { w.str(crate::POS_INF); return w.pos }
+        teju::float::FloatType::NegInf => { w.str(crate::NEG_INF); return w.pos }
+        teju::float::FloatType::Nan => { w.str(crate::NAN); return w.pos }
+    }
+    match num.classify_finite() {
+        teju::float::FiniteFloatType::PosZero => { w.str(crate::POS_ZERO); return w.pos }
+        teju::float::FiniteFloatType::NegZero => { w.str(crate::NEG_ZERO); return w.pos }
+        teju::float::FiniteFloatType::Nonzero => (),
+    }
+
+    let decimal = unsafe { num.to_decimal_finite() }.trim_trailing_zeros();
+    let mant_len = teju::fmt::len_u64(decimal.mant);
+    let decimal_exp = mant_len as i32 + decimal.exp;
+    if !format::is_general_dec(decimal_exp) {
+        // `1234e20 -> 1.234e23`
+        let exp = decimal_exp - 1;
+        w.digits(exp.unsigned_abs() as u64, 0);
+        if exp < 0 {
+            w.byte(b'-');
+        }
+        w.byte(b'e');
+        w.digits(decimal.mant, mant_len - 1);
+    } else if decimal.exp >= 0 {
+        // `1234e2 -> 123400.0`
+        w.str(".0");
+        w.zeros(decimal.exp as usize);
+        w.digits(decimal.mant, 0);
+    } else if decimal_exp > 0 {
+        // `1234e-1 -> 123.4`
+        w.digits(decimal.mant, -decimal.exp as usize);
+    } else {
+        // `1234e-6 -> 0.001234`
+        w.digits(decimal.mant, 0);
+        w.zeros(-decimal_exp as usize);
+        w.str("0.");
+    }
+    if !decimal.sign {
+        w.byte(b'-');
+    }
+    w.pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+    use proptest::prelude::*;
+
+    fn reverse<F: Float>(num: F) -> String {
+        let mut out = [0u8; 24];
+        let start = format_reverse(num, &mut out);
+        String::from_utf8(out[start..].to_vec()).unwrap()
+    }
+
+    #[test]
+    fn notations() {
+        assert_eq!(reverse(1.5f64), "1.5");
+        assert_eq!(reverse(-1234.0f64), "-1234.0");
+        assert_eq!(reverse(0.00001234f64), "0.00001234");
+        assert_eq!(reverse(1e15f64), "1000000000000000.0");
+        assert_eq!(reverse(1e16f64), "1e16");
+        assert_eq!(reverse(-1.7976931348623157e308f64), "-1.7976931348623157e308");
+        assert_eq!(reverse(5e-324f64), "5e-324");
+        assert_eq!(reverse(0.1f32), "0.1");
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(reverse(0.0f64), "0.0");
+        assert_eq!(reverse(-0.0f64), "-0.0");
+        assert_eq!(reverse(f64::NAN), "NaN");
+        assert_eq!(reverse(f64::NEG_INFINITY), "-inf");
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_short() {
+        format_reverse(1.25f64, &mut [0u8; 3]);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_format_f64(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            assert_eq!(reverse(num), Buffer::new().format(num));
+        }
+
+        #[test]
+        fn same_as_format_f32(bits in any::<u32>()) {
+            let num = f32::from_bits(bits);
+            assert_eq!(reverse(num), Buffer::new().format(num));
+        }
+    }
+}
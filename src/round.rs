@@ -0,0 +1,277 @@
+//! Decimal notation with a fixed number of digits after the decimal point.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// How numbers halfway between two candidates are rounded, e.g. by [Buffer::format_round_with].
+///
+/// Ties are decided on the exact binary value of a number, so e.g. `0.125` is a tie, but `2.675`,
+/// which is actually stored as `2.67499999...`, is not, and rounds down to `2.67`. If any digits of
+/// the shortest representation of a number (the digits printed by [Buffer::format_dec]) are
+/// dropped, the result is the same as IEEE 754's conversion to decimal with the rounding attribute
+/// of the same name.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Ties round to even, e.g. `0.125` to `0.12`, as in banker's rounding and IEEE 754's
+    /// roundTiesToEven.
+    HalfEven,
+    /// Ties round away from zero, e.g. `0.125` to `0.13` and `-0.125` to `-0.13`, as in IEEE 754's
+    /// roundTiesToAway.
+    HalfAwayFromZero,
+}
+
+impl RoundingMode {
+    /// Round a finite `num` to `precision` digits after the decimal point.
+    pub(crate) fn round<F: Float>(self, num: F, precision: usize) -> teju::common::SignedDecimal {
+        let exact = match num.classify_finite() {
+            teju::float::FiniteFloatType::Nonzero => Some(unsafe { num.to_binary_finite_nonzero() }),
+            _ => None,
+        };
+        let ties_away = self == RoundingMode::HalfAwayFromZero;
+        unsafe { num.to_decimal_finite() }.round_exact(precision, ties_away, exact)
+    }
+}
+
+impl<F: Float> Buffer<F, format::Fixed> {
+    /// Print a floating point `num` into this buffer in decimal notation with `precision` digits
+    /// after the decimal point, and return a reference to its string representation.
+    ///
+    /// The number is rounded to `precision` digits, with ties rounding to even. The digits are
+    /// those of the shortest representation of `num` (the digits printed by [Buffer::format_dec]),
+    /// padded with zeros if it has fewer than `precision` digits after the decimal point, but ties
+    /// are decided on its exact binary value (see [RoundingMode]), so e.g. `0.125` rounds to
+    /// `"0.12"` and `2.675`, which is actually stored as `2.67499999...`, to `"2.67"`. If
+    /// `precision` is `0`, no decimal point is printed, as in [core::fmt].
+    ///
+    /// So the output is the same as that of [core::fmt] with the same precision, unless the
+    /// shortest representation has at most `precision` digits after the decimal point and is not
+    /// exact: [core::fmt] then prints digits of the exact value past the shortest ones, e.g.
+    /// `"0.10000000000000000555"` for `0.1` with precision 20, where this prints
+    /// `"0.10000000000000000000"`.
+    ///
+    /// If `trim_trailing_zeros` is `true`, zeros at the end of the fractional part are removed,
+    /// but at least one digit is kept after the decimal point (unless `precision` is `0`), as in
+    /// [Buffer::format_dec].
+    ///
+    /// `precision` is capped at 324: no `f64` has significant digits past that.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_round(1.5, 4, false), "1.5000");
+    /// assert_eq!(teju::Buffer::new().format_round(1.5, 4, true), "1.5");
+    /// assert_eq!(teju::Buffer::new().format_round(2.0, 4, true), "2.0");
+    /// assert_eq!(teju::Buffer::new().format_round(3.14159, 2, false), "3.14");
+    /// assert_eq!(teju::Buffer::new().format_round(2.5, 0, false), "2");
+    /// ```
+    pub fn format_round(&mut self, num: F, precision: usize, trim_trailing_zeros: bool) -> &str {
+        self.format_round_with(num, precision, trim_trailing_zeros, RoundingMode::HalfEven)
+    }
+
+    /// As [Self::format_round], but with ties rounding as given by `mode`.
+    ///
+    /// ```
+    /// use teju::RoundingMode;
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_round_with(2.5, 0, false, RoundingMode::HalfEven), "2");
+    /// assert_eq!(buffer.format_round_with(2.5, 0, false, RoundingMode::HalfAwayFromZero), "3");
+    /// assert_eq!(buffer.format_round_with(-0.125, 2, false, RoundingMode::HalfAwayFromZero), "-0.13");
+    /// ```
+    pub fn format_round_with(&mut self, num: F, precision: usize, trim_trailing_zeros: bool, mode: RoundingMode) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        let precision = precision.min(format::MAX_PRECISION);
+        let mut decimal = mode.round(num, precision);
+        let precision = if trim_trailing_zeros && precision > 0 {
+            decimal = decimal.trim_trailing_zeros();
+            (-decimal.exp).max(1) as usize
+        } else {
+            precision
+        };
+
+        let ptr = <format::Fixed as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { decimal.format_fixed(ptr, precision) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+
+    /// Print a floating point `num` into this buffer in fixed-point notation with exactly `scale`
+    /// digits after the decimal point, e.g. for amounts stored as integers scaled by `10^scale`.
+    ///
+    /// This is [Self::format_round] without trimming trailing zeros: the number is rounded (ties
+    /// to even, carrying into the integer part if needed) and padded with zeros to `scale` digits.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_fixed_scale(1.5, 18), "1.500000000000000000");
+    /// assert_eq!(teju::Buffer::new().format_fixed_scale(9.9999, 2), "10.00");
+    /// assert_eq!(teju::Buffer::new().format_fixed_scale(0.125, 2), "0.12");
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `scale` is greater than 324, which is more digits than this buffer can hold.
+    pub fn format_fixed_scale(&mut self, num: F, scale: u32) -> &str {
+        assert!(scale as usize <= format::MAX_PRECISION, "Scale must be at most {}", format::MAX_PRECISION);
+        self.format_round(num, scale as usize, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn round() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_round(1.5f64, 4, false), "1.5000");
+        assert_eq!(Buffer::new().format_round(1.5f32, 4, false), "1.5000");
+        assert_eq!(buf.format_round(0.125f64, 2, false), "0.12");
+        assert_eq!(buf.format_round(0.375f64, 2, false), "0.38");
+        assert_eq!(buf.format_round(-9.996f64, 2, false), "-10.00");
+        assert_eq!(buf.format_round(999.5f64, 0, false), "1000");
+        assert_eq!(buf.format_round(0.5f64, 0, false), "0");
+        assert_eq!(buf.format_round(-0.001f64, 2, false), "-0.00");
+        assert_eq!(buf.format_round(1e20f64, 2, false), "100000000000000000000.00");
+        assert_eq!(buf.format_round(1.5e-5f64, 6, false), "0.000015");
+        assert_eq!(buf.format_round(1.5e-5f64, 10, false), "0.0000150000");
+        assert_eq!(buf.format_round(123.456f64, 1, false), "123.5");
+    }
+
+    #[test]
+    fn trim() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_round(1.5f64, 4, true), "1.5");
+        assert_eq!(buf.format_round(1.25f64, 1, true), "1.2");
+        assert_eq!(buf.format_round(1.96f64, 1, true), "2.0");
+        assert_eq!(buf.format_round(100.0f64, 3, true), "100.0");
+        assert_eq!(buf.format_round(100.0f64, 0, true), "100");
+        assert_eq!(buf.format_round(0.001f64, 2, true), "0.0");
+        assert_eq!(buf.format_round(1.5e-5f64, 10, true), "0.000015");
+    }
+
+    #[test]
+    fn zero() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_round(0.0f64, 3, false), "0.000");
+        assert_eq!(buf.format_round(-0.0f64, 3, false), "-0.000");
+        assert_eq!(buf.format_round(0.0f64, 0, false), "0");
+        assert_eq!(Buffer::new().format_round(0.0f32, 3, true), "0.0");
+    }
+
+    #[test]
+    fn matches_std() {
+        // `core::fmt` rounds the exact binary value, so only compare numbers whose shortest
+        // representation is exact.
+        for num in [0.5, 1.25, 1e15, 123456.75, 0.0625, 3.0, 1e22] {
+            for precision in [0, 1, 2, 3, 10] {
+                assert_eq!(Buffer::new().format_round(num, precision, false), format!("{num:.precision$}"));
+                assert_eq!(Buffer::new().format_round(-num, precision, false), format!("{:.precision$}", -num));
+            }
+        }
+    }
+
+    #[test]
+    fn modes() {
+        // Ties which are exact in binary, so this is also IEEE 754's conversion to decimal with
+        // roundTiesToEven and roundTiesToAway.
+        for (num, precision, even, away) in [
+            (0.5f64, 0, "0", "1"), (1.5, 0, "2", "2"), (2.5, 0, "2", "3"), (-2.5, 0, "-2", "-3"),
+            (0.125, 2, "0.12", "0.13"), (0.375, 2, "0.38", "0.38"), (-0.0625, 3, "-0.062", "-0.063"),
+            (1023.5, 0, "1024", "1024"), (4503599627370495.5, 0, "4503599627370496", "4503599627370496"),
+            (0.25, 3, "0.250", "0.250"), (2.4999, 0, "2", "2"),
+        ] {
+            let mut buf = Buffer::new();
+            assert_eq!(buf.format_round_with(num, precision, false, RoundingMode::HalfEven), even);
+            assert_eq!(buf.format_round_with(num, precision, false, RoundingMode::HalfAwayFromZero), away);
+            assert_eq!(format!("{num:.precision$}"), even);
+        }
+        // Not a tie in binary, but a tie in the shortest representation.
+        let mut buf = Buffer::new();
+        for (num, precision, str) in [
+            (2.675f64, 2, "2.67"), (-2.675, 2, "-2.67"), (2.665, 2, "2.67"), (1.005, 2, "1.00"),
+            (0.155, 2, "0.15"), (8.345, 2, "8.35"), (1.25e-5, 5, "0.00001"),
+        ] {
+            assert_eq!(buf.format_round_with(num, precision, false, RoundingMode::HalfEven), str);
+            assert_eq!(buf.format_round_with(num, precision, false, RoundingMode::HalfAwayFromZero), str);
+            assert_eq!(format!("{num:.precision$}"), str);
+        }
+        assert_eq!(Buffer::new().format_round_with(0.15f32, 1, false, RoundingMode::HalfEven), "0.2");
+        assert_eq!(Buffer::new().format_round_with(0.35f32, 1, false, RoundingMode::HalfAwayFromZero), "0.3");
+        assert_eq!(buf.format_round_with(9.5f64, 0, true, RoundingMode::HalfAwayFromZero), "10");
+        assert_eq!(Buffer::new().format_round_with(0.5f32, 0, false, RoundingMode::HalfAwayFromZero), "1");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        let str = buf.format_round(-f64::MAX, 1000, false);
+        assert_eq!(str.len(), 1 + 309 + 1 + 324);
+        assert!(str.starts_with(Buffer::new().format_dec(-f64::MAX).trim_end_matches(".0")));
+        let str = buf.format_round(5e-324f64, 1000, false);
+        assert_eq!(str, format!("0.{}5", "0".repeat(323)));
+        assert_eq!(buf.format_round(5e-324f64, 323, false), format!("0.{}", "0".repeat(323)));
+        assert_eq!(buf.format_round(f64::MAX, 0, true), Buffer::new().format_dec(f64::MAX).trim_end_matches(".0"));
+    }
+
+    #[test]
+    fn fixed_scale() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_fixed_scale(1.5f64, 18), "1.500000000000000000");
+        assert_eq!(buf.format_fixed_scale(-2.5f64, 0), "-2");
+        assert_eq!(buf.format_fixed_scale(0.996f64, 2), "1.00");
+        assert_eq!(buf.format_fixed_scale(-0.0f64, 3), "-0.000");
+        assert_eq!(buf.format_fixed_scale(1e-20f64, 18), "0.000000000000000000");
+        assert_eq!(buf.format_fixed_scale(5e-324f64, 324), format!("0.{}5", "0".repeat(323)));
+        assert_eq!(Buffer::new().format_fixed_scale(1.5f32, 18), "1.500000000000000000");
+        assert_eq!(buf.format_fixed_scale(f64::INFINITY, 18), "inf");
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixed_scale_too_large() {
+        Buffer::new().format_fixed_scale(1.5f64, 325);
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(Buffer::new().format_round(f64::NAN, 2, false), "NaN");
+        assert_eq!(Buffer::new().format_round(f64::INFINITY, 2, false), "inf");
+        assert_eq!(Buffer::new().format_round(f64::NEG_INFINITY, 2, true), "-inf");
+    }
+
+    fn shortest_frac_len<F: Float>(num: F) -> usize {
+        let str = Buffer::new().format_dec(num).to_owned();
+        str.split_once('.').map_or(0, |(_, frac)| frac.trim_end_matches('0').len())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100_000))]
+
+        /// Numbers whose shortest representation has `frac_len` digits after the decimal point
+        /// (or fewer, after trailing zeros), ending in `5` half of the time, so many are ties in
+        /// the shortest representation but not in binary.
+        #[test]
+        fn same_as_std(mant in 0u64 .. 100_000_000_000_000, tie in any::<bool>(), frac_len in 1usize .. 25, drop in 1usize .. 4) {
+            let mant = if tie { mant / 10 * 10 + 5 } else { mant };
+            let num: f64 = format!("{mant}e-{frac_len}").parse().unwrap();
+            let precision = frac_len.saturating_sub(drop);
+            for num in [num, -num] {
+                if shortest_frac_len(num) > precision {
+                    assert_eq!(Buffer::new().format_round(num, precision, false), format!("{num:.precision$}"));
+                }
+                if shortest_frac_len(num as f32) > precision {
+                    assert_eq!(Buffer::new().format_round(num as f32, precision, false), format!("{:.precision$}", num as f32));
+                }
+            }
+        }
+    }
+}
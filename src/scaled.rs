@@ -0,0 +1,110 @@
+//! Decimal notation with the decimal point moved by a power of ten.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+impl<F: Float> Buffer<F, format::Fixed> {
+    /// Print a floating point `num` divided by `10^scale` into this buffer in decimal notation,
+    /// and return a reference to its string representation, e.g. for showing a value in
+    /// thousands.
+    ///
+    /// This is exact: the decimal point of the shortest representation of `num` is moved `scale`
+    /// places to the left, or to the right if `scale` is negative, with no floating point
+    /// arithmetic. The result is printed as by [Buffer::format_dec], with at least one digit after
+    /// the decimal point.
+    ///
+    /// This function formats zero as `"0.0"`, NaN as the string `"NaN"`, positive infinity as
+    /// `"inf"`, and negative infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_scaled(12345.0, 3), "12.345");
+    /// assert_eq!(buffer.format_scaled(0.1, -2), "10.0");
+    /// assert_eq!(buffer.format_scaled(-1.5, 6), "-0.0000015");
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `scale` is greater than 256 or less than -256.
+    pub fn format_scaled(&mut self, num: F, scale: i32) -> &str {
+        assert!(scale.unsigned_abs() as usize <= format::MAX_SCALE, "Scale must be at most {} in magnitude", format::MAX_SCALE);
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+
+        let mut decimal = unsafe { num.to_decimal_finite() }.trim_trailing_zeros();
+        decimal.exp -= scale;
+        let precision = (-decimal.exp).max(1) as usize;
+
+        let ptr = <format::Fixed as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe { decimal.format_fixed(ptr, precision) };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn scaled() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_scaled(12345.0f64, 0), "12345.0");
+        assert_eq!(buf.format_scaled(12345.0f64, 5), "0.12345");
+        assert_eq!(buf.format_scaled(12345.0f64, 7), "0.0012345");
+        assert_eq!(buf.format_scaled(12345.0f64, -2), "1234500.0");
+        assert_eq!(buf.format_scaled(1.5f64, 1), "0.15");
+        assert_eq!(buf.format_scaled(0.001f64, -3), "1.0");
+        assert_eq!(buf.format_scaled(-0.125f64, -2), "-12.5");
+        assert_eq!(Buffer::new().format_scaled(0.1f32, -1), "1.0");
+    }
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_scaled(f64::MAX, -256).len(), 309 + 256 + 2);
+        assert_eq!(buf.format_scaled(-5e-324f64, 256).len(), 1 + 2 + 323 + 256 + 1);
+        assert_eq!(buf.format_scaled(-2.2250738585072014e-308f64, 256).len(), 1 + 2 + 307 + 256 + 17);
+        assert_eq!(Buffer::new().format_scaled(f32::MAX, -256).len(), 39 + 256 + 2);
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_scaled(0.0f64, 3), "0.0");
+        assert_eq!(buf.format_scaled(-0.0f64, -3), "-0.0");
+        assert_eq!(buf.format_scaled(f64::NAN, 3), "NaN");
+        assert_eq!(buf.format_scaled(f64::NEG_INFINITY, 3), "-inf");
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_too_large() {
+        Buffer::new().format_scaled(1.5f64, -257);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100_000))]
+
+        #[test]
+        fn exact(bits in any::<u64>(), scale in -256i32 ..= 256) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite());
+            let str = Buffer::new().format_scaled(num, scale).to_owned();
+            // Parsing with the exponent added back gives `num` again.
+            let back: f64 = format!("{str}e{scale}").parse().unwrap();
+            assert_eq!(back.to_bits(), bits, "{str}");
+        }
+    }
+}
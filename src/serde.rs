@@ -0,0 +1,278 @@
+//! Functions for serializing floats with serde, for `#[serde(serialize_with = "...")]`.
+
+use ::serde::{Serializer, ser::Error};
+
+use crate::{Buffer, Float, teju};
+
+/// Serialize a floating point `num` as a number, for use as
+/// `#[serde(serialize_with = "teju::serde::serialize")]`. Returns an error if `num` is NaN or
+/// infinite, so that the output is always a valid JSON number. Requires the `serde` feature.
+///
+/// This works with any serializer, which writes the number in its own way, as serde has no way to
+/// pass a formatted number on: `half` floats are serialized as `f32`s. With `serde_json`, use
+/// [serialize_json] (with the `serde_json` feature) to have the number formatted as by
+/// [Buffer::format]. Use [serialize_lossy] to serialize NaN and infinities as none instead.
+///
+/// ```
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     #[serde(serialize_with = "teju::serde::serialize")]
+///     x: f64,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&Point { x: 1.5 }).unwrap(), r#"{"x":1.5}"#);
+/// assert!(serde_json::to_string(&Point { x: f64::NAN }).is_err());
+/// ```
+pub fn serialize<F: Float, S: Serializer>(num: &F, serializer: S) -> Result<S::Ok, S::Error> {
+    match num.classify() {
+        teju::float::FloatType::Finite => serialize_native(*num, serializer),
+        _ => Err(S::Error::custom("cannot serialize NaN or infinity as a number")),
+    }
+}
+
+/// Like [serialize], but serializes NaN and infinities as none, e.g. `null` in JSON, instead of
+/// returning an error. Requires the `serde` feature.
+///
+/// ```
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     #[serde(serialize_with = "teju::serde::serialize_lossy")]
+///     x: f64,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&Point { x: 1.5 }).unwrap(), r#"{"x":1.5}"#);
+/// assert_eq!(serde_json::to_string(&Point { x: f64::NAN }).unwrap(), r#"{"x":null}"#);
+/// ```
+pub fn serialize_lossy<F: Float, S: Serializer>(num: &F, serializer: S) -> Result<S::Ok, S::Error> {
+    match num.classify() {
+        teju::float::FloatType::Finite => serialize_native(*num, serializer),
+        _ => serializer.serialize_none(),
+    }
+}
+
+/// Like [serialize], but serializes `num` as a JSON number formatted as by [Buffer::format], e.g.
+/// `{"x":1e30}`, by passing it on to `serde_json` as a [RawValue](serde_json::value::RawValue).
+/// Requires the `serde_json` feature.
+///
+/// This only makes sense with `serde_json`'s serializers: others see a struct wrapping the string.
+///
+/// ```
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     #[serde(serialize_with = "teju::serde::serialize_json")]
+///     x: f64,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&Point { x: 1e30 }).unwrap(), r#"{"x":1e30}"#);
+/// assert!(serde_json::to_string(&Point { x: f64::NAN }).is_err());
+/// ```
+#[cfg(feature = "serde_json")]
+pub fn serialize_json<F: Float, S: Serializer>(num: &F, serializer: S) -> Result<S::Ok, S::Error> {
+    match num.classify() {
+        teju::float::FloatType::Finite => serialize_raw(*num, serializer),
+        _ => Err(S::Error::custom("cannot serialize NaN or infinity as a number")),
+    }
+}
+
+/// Like [serialize_json], but serializes NaN and infinities as `null` instead of returning an
+/// error. Requires the `serde_json` feature.
+///
+/// ```
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     #[serde(serialize_with = "teju::serde::serialize_json_lossy")]
+///     x: f64,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&Point { x: 1.5 }).unwrap(), r#"{"x":1.5}"#);
+/// assert_eq!(serde_json::to_string(&Point { x: f64::NAN }).unwrap(), r#"{"x":null}"#);
+/// ```
+#[cfg(feature = "serde_json")]
+pub fn serialize_json_lossy<F: Float, S: Serializer>(num: &F, serializer: S) -> Result<S::Ok, S::Error> {
+    match num.classify() {
+        teju::float::FloatType::Finite => serialize_raw(*num, serializer),
+        _ => serializer.serialize_none(),
+    }
+}
+
+/// Like [serialize], but serializes `num` as a string, e.g. `{"x":"1e30"}` in JSON, as by
+/// [Buffer::format_json_quoted](crate::Buffer::format_json_quoted). Requires the `serde` feature.
+///
+/// ```
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     #[serde(serialize_with = "teju::serde::serialize_str")]
+///     x: f64,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&Point { x: 1e30 }).unwrap(), r#"{"x":"1e30"}"#);
+/// assert!(serde_json::to_string(&Point { x: f64::NAN }).is_err());
+/// ```
+pub fn serialize_str<F: Float, S: Serializer>(num: &F, serializer: S) -> Result<S::Ok, S::Error> {
+    match num.classify() {
+        teju::float::FloatType::Finite => serializer.serialize_str(Buffer::new().format(*num)),
+        _ => Err(S::Error::custom("cannot serialize NaN or infinity as a number")),
+    }
+}
+
+/// Like [serialize_str], but serializes NaN and infinities as none, e.g. `null` in JSON, instead of
+/// returning an error. Requires the `serde` feature.
+///
+/// ```
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     #[serde(serialize_with = "teju::serde::serialize_str_lossy")]
+///     x: f64,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&Point { x: 1.5 }).unwrap(), r#"{"x":"1.5"}"#);
+/// assert_eq!(serde_json::to_string(&Point { x: f64::NAN }).unwrap(), r#"{"x":null}"#);
+/// ```
+pub fn serialize_str_lossy<F: Float, S: Serializer>(num: &F, serializer: S) -> Result<S::Ok, S::Error> {
+    match num.classify() {
+        teju::float::FloatType::Finite => serializer.serialize_str(Buffer::new().format(*num)),
+        _ => serializer.serialize_none(),
+    }
+}
+
+/// Serialize `num` as the primitive float it is, or wraps, or as an `f32` for `half` floats.
+fn serialize_native<F: Float, S: Serializer>(num: F, serializer: S) -> Result<S::Ok, S::Error> {
+    let bits = num.to_bits_u64();
+    match F::NAME {
+        "f64" => serializer.serialize_f64(f64::from_bits(bits)),
+        #[cfg(feature = "half")]
+        "f16" => serializer.serialize_f32(half::f16::from_bits(bits as u16).to_f32()),
+        #[cfg(feature = "half")]
+        "bf16" => serializer.serialize_f32(half::bf16::from_bits(bits as u16).to_f32()),
+        _ => serializer.serialize_f32(f32::from_bits(bits as u32)),
+    }
+}
+
+/// Serialize a finite `num` as a [RawValue](serde_json::value::RawValue).
+#[cfg(feature = "serde_json")]
+fn serialize_raw<F: Float, S: Serializer>(num: F, serializer: S) -> Result<S::Ok, S::Error> {
+    use ::serde::Serialize;
+    let str = alloc::string::String::from(Buffer::new().format(num));
+    let raw = serde_json::value::RawValue::from_string(str).map_err(S::Error::custom)?;
+    raw.serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[derive(::serde::Serialize)]
+    struct Native<F: Float> {
+        #[serde(serialize_with = "serialize")]
+        x: F,
+        #[serde(serialize_with = "serialize_lossy")]
+        y: F,
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[derive(::serde::Serialize)]
+    struct Json<F: Float> {
+        #[serde(serialize_with = "serialize_json")]
+        x: F,
+        #[serde(serialize_with = "serialize_json_lossy")]
+        y: F,
+    }
+
+    #[derive(::serde::Serialize)]
+    struct Strict<F: Float> {
+        #[serde(serialize_with = "serialize_str")]
+        x: F,
+    }
+
+    #[derive(::serde::Serialize)]
+    struct Lossy<F: Float> {
+        #[serde(serialize_with = "serialize_str_lossy")]
+        x: F,
+    }
+
+    #[test]
+    fn native() {
+        assert_eq!(serde_json::to_string(&Native { x: 0.1f32, y: f32::NAN }).unwrap(), r#"{"x":0.1,"y":null}"#);
+        assert_eq!(serde_json::to_string(&Native { x: -1.25f64, y: 5e-324 }).unwrap(), r#"{"x":-1.25,"y":5e-324}"#);
+        for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let err = serde_json::to_string(&Native { x, y: x }).unwrap_err();
+            assert_eq!(err.to_string(), "cannot serialize NaN or infinity as a number");
+        }
+    }
+
+    /// Other serializers get the number as a float, rather than as something only `serde_json`
+    /// understands.
+    #[test]
+    fn native_toml() {
+        assert_eq!(toml::to_string(&Native { x: 1.5f64, y: -0.25 }).unwrap(), "x = 1.5\ny = -0.25\n");
+        assert_eq!(toml::to_string(&Native { x: 0.5f32, y: 3.0 }).unwrap(), "x = 0.5\ny = 3.0\n");
+        #[cfg(feature = "half")]
+        assert_eq!(toml::to_string(&Native { x: half::f16::from_f32(0.5), y: half::f16::ONE }).unwrap(), "x = 0.5\ny = 1.0\n");
+        assert!(toml::to_string(&Native { x: f64::NAN, y: 1.0 }).is_err());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn json() {
+        assert_eq!(serde_json::to_string(&Json { x: -0.0f64, y: 1e-7 }).unwrap(), r#"{"x":-0.0,"y":1e-7}"#);
+        assert_eq!(serde_json::to_string(&Json { x: 0.1f32, y: f32::NAN }).unwrap(), r#"{"x":0.1,"y":null}"#);
+        assert_eq!(serde_json::to_string(&Json { x: 5e-324f64, y: f64::INFINITY }).unwrap(), r#"{"x":5e-324,"y":null}"#);
+        assert_eq!(serde_json::to_string_pretty(&[Json { x: 1.5f64, y: -1e300 }]).unwrap(), "[\n  {\n    \"x\": 1.5,\n    \"y\": -1e300\n  }\n]");
+        for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let err = serde_json::to_string(&Json { x, y: x }).unwrap_err();
+            assert_eq!(err.to_string(), "cannot serialize NaN or infinity as a number");
+        }
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn json_value() {
+        let value = serde_json::to_value(Json { x: 1e30f64, y: f64::NAN }).unwrap();
+        assert_eq!(value, serde_json::json!({ "x": 1e30, "y": null }));
+    }
+
+    #[test]
+    fn strict() {
+        assert_eq!(serde_json::to_string(&Strict { x: -0.0f64 }).unwrap(), r#"{"x":"-0.0"}"#);
+        assert_eq!(serde_json::to_string(&Strict { x: 0.1f32 }).unwrap(), r#"{"x":"0.1"}"#);
+        assert_eq!(serde_json::to_string(&Strict { x: 5e-324f64 }).unwrap(), r#"{"x":"5e-324"}"#);
+        for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let err = serde_json::to_string(&Strict { x }).unwrap_err();
+            assert_eq!(err.to_string(), "cannot serialize NaN or infinity as a number");
+        }
+    }
+
+    #[test]
+    fn lossy() {
+        assert_eq!(serde_json::to_string(&Lossy { x: 1e-7f64 }).unwrap(), r#"{"x":"1e-7"}"#);
+        for x in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert_eq!(serde_json::to_string(&Lossy { x }).unwrap(), r#"{"x":null}"#);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[cfg(feature = "serde_json")]
+        #[test]
+        fn json_number(bits in any::<u64>()) {
+            let x = f64::from_bits(bits);
+            prop_assume!(x.is_finite());
+            let json = serde_json::to_string(&Json { x, y: x }).unwrap();
+            assert_eq!(json, format!(r#"{{"x":{str},"y":{str}}}"#, str = Buffer::new().format(x)));
+            assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok(), "{json}");
+        }
+
+        #[test]
+        fn json_number_str(bits in any::<u64>()) {
+            let x = f64::from_bits(bits);
+            prop_assume!(x.is_finite());
+            let json = serde_json::to_value(Strict { x }).unwrap();
+            let str = json["x"].as_str().unwrap();
+            // The contents of the string are a JSON number, with the same value.
+            assert!(serde_json::from_str::<serde_json::Number>(str).is_ok(), "{str}");
+            assert_eq!(str.parse::<f64>().unwrap().to_bits(), bits);
+        }
+    }
+}
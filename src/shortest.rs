@@ -0,0 +1,83 @@
+//! The shortest decimal representation of a float, as numbers rather than as a string.
+
+use crate::{Float, teju};
+
+/// The shortest decimal representation of a finite floating point `num`, as a tuple
+/// `(negative, mant, exp)`, or `None` if `num` is NaN or infinite. `negative` is `true` if the sign
+/// bit of `num` is set.
+///
+/// `±mant * 10^exp` is the shortest decimal which rounds to `num`, not its exact value: e.g. `0.1`
+/// is `(false, 1, -1)`, although no float equals one tenth. Parsing it back gives `num`, but
+/// `mant` and `exp` cannot be used to compute with `num` exactly.
+///
+/// These are the digits printed by [Buffer::format](crate::Buffer::format) and the other
+/// shortest-representation methods, so this is for writing custom formats without parsing a
+/// string. It is a free function rather than a method of [Buffer](crate::Buffer), as it prints
+/// nothing, so needs no buffer. Trailing zeros are removed from `mant`, so e.g. `1200.0` is
+/// `(false, 12, 2)`. Zero is `(false, 0, 0)`, and negative zero is `(true, 0, 0)`.
+///
+/// ```
+/// assert_eq!(teju::to_decimal(1.5), Some((false, 15, -1)));
+/// assert_eq!(teju::to_decimal(-1200.0), Some((true, 12, 2)));
+/// assert_eq!(teju::to_decimal(5e-324), Some((false, 5, -324)));
+/// assert_eq!(teju::to_decimal(0.1f32), Some((false, 1, -1)));
+/// assert_eq!(teju::to_decimal(f64::NAN), None);
+/// ```
+pub fn to_decimal<F: Float>(num: F) -> Option<(bool, u64, i32)> {
+    if !matches!(num.classify(), teju::float::FloatType::Finite) {
+        return None
+    }
+    let teju::common::SignedDecimal { sign, mant, exp } = unsafe { num.to_decimal_finite() }.trim_trailing_zeros();
+    Some((!sign, mant, exp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+    use proptest::prelude::*;
+
+    #[test]
+    fn digits() {
+        assert_eq!(to_decimal(1.0f64), Some((false, 1, 0)));
+        assert_eq!(to_decimal(-0.25f64), Some((true, 25, -2)));
+        assert_eq!(to_decimal(1e23f64), Some((false, 1, 23)));
+        assert_eq!(to_decimal(123456789.0f64), Some((false, 123456789, 0)));
+        assert_eq!(to_decimal(f64::MAX), Some((false, 17976931348623157, 292)));
+        assert_eq!(to_decimal(f32::MAX), Some((false, 34028235, 31)));
+        assert_eq!(to_decimal(0.0f64), Some((false, 0, 0)));
+        assert_eq!(to_decimal(-0.0f64), Some((true, 0, 0)));
+        assert_eq!(to_decimal(f64::INFINITY), None);
+        assert_eq!(to_decimal(f32::NEG_INFINITY), None);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100_000))]
+
+        #[test]
+        fn same_as_exp(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            match to_decimal(num) {
+                Some((negative, mant, exp)) => {
+                    assert!(mant == 0 || mant % 10 != 0);
+                    let str = format!("{}{mant}e{exp}", if negative { "-" } else { "" });
+                    assert_eq!(str.parse::<f64>().unwrap().to_bits(), bits);
+                    // Same digits as in scientific notation.
+                    let exp_str = Buffer::new().format_exp(num).replace('.', "");
+                    assert!(exp_str.starts_with(&str[.. str.find('e').unwrap()]), "{str} {exp_str}");
+                },
+                None => assert!(!num.is_finite()),
+            }
+        }
+
+        #[test]
+        fn no_trailing_zeros_f32(bits in any::<u32>()) {
+            let num = f32::from_bits(bits);
+            if let Some((negative, mant, exp)) = to_decimal(num) {
+                assert!(mant == 0 || mant % 10 != 0);
+                let str = format!("{}{mant}e{exp}", if negative { "-" } else { "" });
+                assert_eq!(str.parse::<f32>().unwrap().to_bits(), bits);
+            }
+        }
+    }
+}
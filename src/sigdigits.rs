@@ -0,0 +1,90 @@
+//! General notation, along with the number of significant digits.
+
+use crate::{Buffer, Float, teju};
+use teju::{fmt, format};
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer as by [Self::format], and return a reference
+    /// to its string representation along with its number of significant digits.
+    ///
+    /// This is the number of digits of the shortest decimal representation of `num`, without
+    /// leading or trailing zeros, e.g. 3 for both `"1230.0"` and `"0.00123"`, and 2 for `"1500.0"`.
+    /// It is at most 17 for an `f64`, and at most 9 for an `f32`. Zero, NaN, and infinities have no
+    /// significant digits.
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_with_sigdigits(1500.0), ("1500.0", 2));
+    /// assert_eq!(buffer.format_with_sigdigits(1230.0), ("1230.0", 3));
+    /// assert_eq!(buffer.format_with_sigdigits(-0.00123), ("-0.00123", 3));
+    /// assert_eq!(buffer.format_with_sigdigits(0.1 + 0.2), ("0.30000000000000004", 17));
+    /// assert_eq!(buffer.format_with_sigdigits(0.0), ("0.0", 0));
+    /// ```
+    pub fn format_with_sigdigits(&mut self, num: F) -> (&str, u8) {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return (crate::POS_INF, 0),
+            teju::float::FloatType::NegInf => return (crate::NEG_INF, 0),
+            teju::float::FloatType::Nan => return (crate::NAN, 0),
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return (crate::POS_ZERO, 0),
+            teju::float::FiniteFloatType::NegZero => return (crate::NEG_ZERO, 0),
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let (n, decimal) = unsafe {
+            let decimal = num.to_decimal_finite();
+            (F::format_general_decimal(decimal, ptr), decimal.trim_trailing_zeros())
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        (unsafe { core::str::from_utf8_unchecked(slice) }, fmt::len_u64(decimal.mant) as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn sigdigits() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_with_sigdigits(1.0f64), ("1.0", 1));
+        assert_eq!(buf.format_with_sigdigits(1e300f64), ("1e300", 1));
+        assert_eq!(buf.format_with_sigdigits(-123.456f64), ("-123.456", 6));
+        assert_eq!(buf.format_with_sigdigits(1e-7f64), ("1e-7", 1));
+        assert_eq!(buf.format_with_sigdigits(f64::MAX), ("1.7976931348623157e308", 17));
+        assert_eq!(buf.format_with_sigdigits(5e-324f64), ("5e-324", 1));
+        assert_eq!(Buffer::new().format_with_sigdigits(0.1f32), ("0.1", 1));
+        assert_eq!(Buffer::new().format_with_sigdigits(16777215.0f32), ("16777215.0", 8));
+        assert_eq!(Buffer::new().format_with_sigdigits(f32::MAX), ("3.4028235e38", 8));
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_with_sigdigits(0.0f64), ("0.0", 0));
+        assert_eq!(buf.format_with_sigdigits(-0.0f64), ("-0.0", 0));
+        assert_eq!(buf.format_with_sigdigits(f64::NAN), ("NaN", 0));
+        assert_eq!(buf.format_with_sigdigits(f64::NEG_INFINITY), ("-inf", 0));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_exp(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let mut buf = Buffer::new();
+            let (str, sig) = buf.format_with_sigdigits(num);
+            assert_eq!(str, Buffer::new().format(num));
+            let exp = Buffer::new().format_exp(num).to_owned();
+            let mant = exp.trim_start_matches('-').split('e').next().unwrap();
+            let expected = if num.is_finite() && num != 0.0 { mant.replace('.', "").len() } else { 0 };
+            assert_eq!(sig as usize, expected, "{exp}");
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Formatting into caller-provided byte slices.
+
+use crate::{Buffer, CapacityError, Float, teju};
+use teju::format;
+
+/// Copy `str` to the start of `out`, returning its length.
+fn copy_to(str: &str, out: &mut [u8]) -> Result<usize, CapacityError> {
+    let have = out.len();
+    let dst = out.get_mut(..str.len()).ok_or(CapacityError { needed: str.len(), have })?;
+    dst.copy_from_slice(str.as_bytes());
+    Ok(str.len())
+}
+
+/// Copy `str` to the start of `arena`, returning the copy.
+fn copy_in<'a>(str: &str, arena: &'a mut [u8]) -> Result<&'a str, CapacityError> {
+    let n = copy_to(str, arena)?;
+    // SAFETY: a copy of the bytes of a `str`.
+    Ok(unsafe { core::str::from_utf8_unchecked(&arena[..n]) })
+}
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Like [Self::format_finite], but prints the string to the start of `out` and returns it
+    /// there, e.g. to place it in a larger message buffer. Returns an error if `out` is shorter
+    /// than the string, in which case `out` is unchanged; the rest of `out` is always unchanged.
+    /// As for [Self::format_finite], if `num` is not finite, the contents of the string are
+    /// unspecified.
+    ///
+    /// The string is printed into a scratch buffer and then copied, rather than printed into `out`
+    /// directly, as the mantissa printers write uninitialised bytes past its end, which must not
+    /// end up in a `&mut [u8]`. So, unlike [Self::format_in], this needs no buffer, but still
+    /// copies.
+    ///
+    /// ```
+    /// let mut out = [0u8; 8];
+    /// let str = teju::Buffer::format_finite_to(1.5, &mut out).unwrap();
+    /// assert_eq!(str, "1.5");
+    /// assert!(teju::Buffer::format_finite_to(1.5, &mut [0u8; 2]).is_err());
+    /// ```
+    pub fn format_finite_to(num: F, out: &mut [u8]) -> Result<&str, CapacityError> {
+        copy_in(Self::new().format_finite(num), out)
+    }
+
+    /// Like [Self::format], but copies the string to the start of `out` instead of returning it.
+    /// Returns its length, or an error if `out` is too short, in which case `out` is unchanged.
+    ///
+    /// A slice of [required_len](crate::required_len) bytes is always long enough.
+    ///
+    /// ```
+    /// let mut out = [0u8; 8];
+    /// let n = teju::Buffer::new().format_to_slice(1.5, &mut out).unwrap();
+    /// assert_eq!(&out[..n], b"1.5");
+    /// ```
+    pub fn format_to_slice(&mut self, num: F, out: &mut [u8]) -> Result<usize, CapacityError> {
+        copy_to(self.format(num), out)
+    }
+
+    /// Like [Self::format_to_slice], but returns the string in `arena`, which borrows `arena`
+    /// rather than this buffer, e.g. for arena-allocated output that outlives the buffer. Returns
+    /// an error if `arena` is too short, in which case `arena` is unchanged.
+    ///
+    /// ```
+    /// let mut arena = [0u8; 64];
+    /// let str = teju::Buffer::new().format_in(1.5, &mut arena).unwrap();
+    /// assert_eq!(str, "1.5");
+    /// ```
+    pub fn format_in<'a>(&mut self, num: F, arena: &'a mut [u8]) -> Result<&'a str, CapacityError> {
+        copy_in(self.format(num), arena)
+    }
+}
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// Like [Self::format_exp_finite], but prints the string to the start of `out` and returns it
+    /// there, through a scratch buffer, as by [Buffer::format_finite_to]. Returns an error if `out`
+    /// is shorter than the string, in which case `out` is unchanged.
+    ///
+    /// ```
+    /// let mut out = [0u8; 8];
+    /// let str = teju::Buffer::format_exp_finite_to(1500.0, &mut out).unwrap();
+    /// assert_eq!(str, "1.5e3");
+    /// ```
+    pub fn format_exp_finite_to(num: F, out: &mut [u8]) -> Result<&str, CapacityError> {
+        copy_in(Self::new().format_exp_finite(num), out)
+    }
+
+    /// Like [Self::format_exp], but copies the string to the start of `out` instead of returning
+    /// it. Returns its length, or an error if `out` is too short, in which case `out` is
+    /// unchanged.
+    ///
+    /// ```
+    /// let mut out = [0u8; 8];
+    /// let n = teju::Buffer::new().format_exp_to_slice(1500.0, &mut out).unwrap();
+    /// assert_eq!(&out[..n], b"1.5e3");
+    /// ```
+    pub fn format_exp_to_slice(&mut self, num: F, out: &mut [u8]) -> Result<usize, CapacityError> {
+        copy_to(self.format_exp(num), out)
+    }
+
+    /// Like [Self::format_exp_to_slice], but returns the string in `arena`, which borrows `arena`
+    /// rather than this buffer. Returns an error if `arena` is too short, in which case `arena` is
+    /// unchanged.
+    ///
+    /// ```
+    /// let mut arena = [0u8; 64];
+    /// let str = teju::Buffer::new().format_exp_in(1500.0, &mut arena).unwrap();
+    /// assert_eq!(str, "1.5e3");
+    /// ```
+    pub fn format_exp_in<'a>(&mut self, num: F, arena: &'a mut [u8]) -> Result<&'a str, CapacityError> {
+        copy_in(self.format_exp(num), arena)
+    }
+}
+
+impl<F: Float> Buffer<F, format::Decimal> {
+    /// Like [Self::format_dec_finite], but prints the string to the start of `out` and returns it
+    /// there, through a scratch buffer, as by [Buffer::format_finite_to]. Returns an error if `out`
+    /// is shorter than the string, in which case `out` is unchanged.
+    ///
+    /// ```
+    /// let mut out = [0u8; 8];
+    /// let str = teju::Buffer::format_dec_finite_to(1e3, &mut out).unwrap();
+    /// assert_eq!(str, "1000.0");
+    /// ```
+    pub fn format_dec_finite_to(num: F, out: &mut [u8]) -> Result<&str, CapacityError> {
+        copy_in(Self::new().format_dec_finite(num), out)
+    }
+
+    /// Like [Self::format_dec], but copies the string to the start of `out` instead of returning
+    /// it. Returns its length, or an error if `out` is too short, in which case `out` is
+    /// unchanged.
+    ///
+    /// ```
+    /// let mut out = [0u8; 8];
+    /// let n = teju::Buffer::new().format_dec_to_slice(1e3, &mut out).unwrap();
+    /// assert_eq!(&out[..n], b"1000.0");
+    /// ```
+    pub fn format_dec_to_slice(&mut self, num: F, out: &mut [u8]) -> Result<usize, CapacityError> {
+        copy_to(self.format_dec(num), out)
+    }
+
+    /// Like [Self::format_dec_to_slice], but returns the string in `arena`, which borrows `arena`
+    /// rather than this buffer. Returns an error if `arena` is too short, in which case `arena` is
+    /// unchanged.
+    ///
+    /// ```
+    /// let mut arena = [0u8; 64];
+    /// let str = teju::Buffer::new().format_dec_in(1e3, &mut arena).unwrap();
+    /// assert_eq!(str, "1000.0");
+    /// ```
+    pub fn format_dec_in<'a>(&mut self, num: F, arena: &'a mut [u8]) -> Result<&'a str, CapacityError> {
+        copy_in(self.format_dec(num), arena)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Style;
+
+    #[test]
+    fn fits() {
+        let mut out = [b'x'; 8];
+        assert_eq!(Buffer::new().format_to_slice(-1e-7f64, &mut out), Ok(5));
+        assert_eq!(&out, b"-1e-7xxx");
+        assert_eq!(Buffer::new().format_exp_to_slice(f32::NAN, &mut out), Ok(3));
+        assert_eq!(Buffer::new().format_dec_to_slice(0.0f64, &mut out[..3]), Ok(3));
+        assert_eq!(&out, b"0.0-7xxx");
+    }
+
+    #[test]
+    fn too_short() {
+        let mut out = [b'x'; 4];
+        assert_eq!(Buffer::new().format_dec_to_slice(1e30f64, &mut out), Err(CapacityError { needed: 33, have: 4 }));
+        assert_eq!(Buffer::new().format_to_slice(0.0f64, &mut []), Err(CapacityError { needed: 3, have: 0 }));
+        assert_eq!(&out, b"xxxx");
+    }
+
+    #[test]
+    fn arena() {
+        let mut arena = [b'x'; 16];
+        let (first, rest) = arena.split_at_mut(8);
+        // The strings outlive the buffers.
+        let strs = [
+            Buffer::new().format_in(-1e-7f64, first).unwrap(),
+            Buffer::new().format_exp_in(f32::NAN, rest).unwrap(),
+        ];
+        assert_eq!(strs, ["-1e-7", "NaN"]);
+        assert_eq!(&arena, b"-1e-7xxxNaNxxxxx");
+    }
+
+    #[test]
+    fn arena_too_short() {
+        let mut arena = [b'x'; 4];
+        assert_eq!(Buffer::new().format_dec_in(1e30f64, &mut arena), Err(CapacityError { needed: 33, have: 4 }));
+        assert_eq!(Buffer::new().format_in(0.0f64, &mut []), Err(CapacityError { needed: 3, have: 0 }));
+        assert_eq!(&arena, b"xxxx");
+    }
+
+    #[test]
+    fn required_len_fits() {
+        let mut out = [0u8; crate::required_len(64, Style::Decimal)];
+        assert!(Buffer::new().format_dec_to_slice(-f64::MIN_POSITIVE, &mut out).is_ok());
+        let mut out = [0u8; crate::required_len(64, Style::Scientific)];
+        assert!(Buffer::new().format_exp_to_slice(-f64::MIN_POSITIVE, &mut out).is_ok());
+    }
+
+    #[test]
+    fn finite_to() {
+        let mut out = [b'x'; 40];
+        assert_eq!(Buffer::format_finite_to(-1e-7f64, &mut out), Ok("-1e-7"));
+        assert_eq!(Buffer::format_finite_to(-0.0f64, &mut out), Ok("-0.0"));
+        assert_eq!(Buffer::format_exp_finite_to(0.0f32, &mut out), Ok("0e0"));
+        assert_eq!(Buffer::format_exp_finite_to(-f64::MAX, &mut out), Ok("-1.7976931348623157e308"));
+        let mut out = [b'x'; 327];
+        assert_eq!(Buffer::format_dec_finite_to(-f64::MIN_POSITIVE, &mut out).unwrap().len(), 327);
+        assert_eq!(Buffer::format_dec_finite_to(0.1f32, &mut out[..3]), Ok("0.1"));
+    }
+
+    #[test]
+    fn finite_to_rest_unchanged() {
+        let mut out = [b'x'; 352];
+        let n = Buffer::format_finite_to(1.2345f64, &mut out).unwrap().len();
+        assert!(out[n..].iter().all(|&b| b == b'x'));
+        let mut out = [b'x'; 352];
+        let n = Buffer::format_exp_finite_to(-1.5e-300f64, &mut out).unwrap().len();
+        assert!(out[n..].iter().all(|&b| b == b'x'));
+        let mut out = [b'x'; 352];
+        let n = Buffer::format_dec_finite_to(123.4f64, &mut out).unwrap().len();
+        assert_eq!(&out[..n], b"123.4");
+        assert!(out[n..].iter().all(|&b| b == b'x'));
+    }
+
+    #[test]
+    fn finite_to_too_short() {
+        let mut out = [b'x'; 4];
+        assert_eq!(Buffer::format_finite_to(1.25f64, &mut out[..3]), Err(CapacityError { needed: 4, have: 3 }));
+        assert_eq!(Buffer::format_exp_finite_to(-1e-7f32, &mut out), Err(CapacityError { needed: 5, have: 4 }));
+        assert_eq!(Buffer::format_dec_finite_to(1e30f64, &mut out), Err(CapacityError { needed: 33, have: 4 }));
+        assert_eq!(Buffer::format_dec_finite_to(0.0f64, &mut []), Err(CapacityError { needed: 3, have: 0 }));
+        assert_eq!(out, [b'x'; 4]);
+    }
+
+    #[test]
+    fn fmt_error() {
+        let err: core::fmt::Error = CapacityError { needed: 1, have: 0 }.into();
+        assert_eq!(err, core::fmt::Error);
+    }
+}
@@ -0,0 +1,151 @@
+//! Formatting to a callback, one part of the number at a time.
+
+use crate::{Float, teju};
+use teju::format;
+
+/// Format `num` as [Buffer::format](crate::Buffer::format) does, but instead of returning a
+/// string, call `emit` with each of its parts in order: the sign (`-`, if negative), the integer
+/// digits, the decimal point, the fractional digits, the `e`, and the exponent (with its sign).
+/// Parts which are not present are skipped, e.g. the fractional digits in `1e30`, so `emit` is
+/// never called with an empty slice.
+///
+/// NaN is emitted as a single part `"NaN"`, and infinities as the sign (if negative) followed by
+/// `"inf"`.
+///
+/// ```
+/// let mut parts = vec![];
+/// teju::format_with(-1.25e-30, |part| parts.push(std::str::from_utf8(part).unwrap().to_owned()));
+/// assert_eq!(parts, ["-", "1", ".", "25", "e", "-30"]);
+///
+/// let mut str = vec![];
+/// teju::format_with(3.5, |part| str.extend_from_slice(part));
+/// assert_eq!(str, b"3.5");
+/// ```
+pub fn format_with<F: Float, E: FnMut(&[u8])>(num: F, mut emit: E) {
+    let class = num.classify();
+    if let teju::float::FloatType::Nan = class {
+        return emit(crate::NAN.as_bytes())
+    }
+    if num.is_sign_negative() {
+        emit(b"-");
+    }
+    if !matches!(class, teju::float::FloatType::Finite) {
+        return emit(crate::POS_INF.as_bytes())
+    }
+    let decimal = match num.classify_finite() {
+        teju::float::FiniteFloatType::Nonzero => unsafe { num.to_decimal_finite() }.trim_trailing_zeros(),
+        _ => {
+            emit(b"0");
+            emit(b".");
+            emit(b"0");
+            return
+        },
+    };
+
+    let mut buf = [0u8; 40];
+    let mant_len = teju::fmt::len_u64(decimal.mant);
+    let decimal_exp = mant_len as i32 + decimal.exp;
+    if !format::is_general_dec(decimal_exp) {
+        // `1234e20 -> 1.234e23`
+        let start = digits(&mut buf, decimal.mant, 0, 0);
+        let (first, rest) = buf[start ..].split_at(1);
+        emit(first);
+        if !rest.is_empty() {
+            emit(b".");
+            emit(rest);
+        }
+        emit(b"e");
+        let exp = decimal_exp - 1;
+        let mut start = digits(&mut buf, exp.unsigned_abs() as u64, 0, 0);
+        if exp < 0 {
+            start -= 1;
+            buf[start] = b'-';
+        }
+        emit(&buf[start ..]);
+    } else if decimal.exp >= 0 {
+        // `1234e2 -> 123400.0`
+        let start = digits(&mut buf, decimal.mant, 0, decimal.exp as usize);
+        emit(&buf[start ..]);
+        emit(b".");
+        emit(b"0");
+    } else if decimal_exp > 0 {
+        // `1234e-1 -> 123.4`
+        let start = digits(&mut buf, decimal.mant, 0, 0);
+        let (int, frac) = buf[start ..].split_at(decimal_exp as usize);
+        emit(int);
+        emit(b".");
+        emit(frac);
+    } else {
+        // `1234e-6 -> 0.001234`
+        let start = digits(&mut buf, decimal.mant, -decimal_exp as usize, 0);
+        emit(b"0");
+        emit(b".");
+        emit(&buf[start ..]);
+    }
+}
+
+/// Write `mant` with `before` zeros before it and `after` zeros after it to the end of `buf`, and
+/// return the index of the first byte written.
+fn digits(buf: &mut [u8; 40], mut mant: u64, before: usize, after: usize) -> usize {
+    let mut start = buf.len() - after;
+    buf[start ..].fill(b'0');
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (mant % 10) as u8;
+        mant /= 10;
+        if mant == 0 {
+            break
+        }
+    }
+    buf[start - before .. start].fill(b'0');
+    start - before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+    use proptest::prelude::*;
+
+    fn parts<F: Float>(num: F) -> Vec<String> {
+        let mut parts = vec![];
+        format_with(num, |part| parts.push(String::from_utf8(part.to_vec()).unwrap()));
+        parts
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(parts(1.5f64), ["1", ".", "5"]);
+        assert_eq!(parts(-0.001f64), ["-", "0", ".", "001"]);
+        assert_eq!(parts(1e30f64), ["1", "e", "30"]);
+        assert_eq!(parts(-1.5e-7f32), ["-", "1", ".", "5", "e", "-7"]);
+        assert_eq!(parts(-0.0f64), ["-", "0", ".", "0"]);
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(parts(f64::NAN), ["NaN"]);
+        assert_eq!(parts(f64::INFINITY), ["inf"]);
+        assert_eq!(parts(f64::NEG_INFINITY), ["-", "inf"]);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn concat(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let parts = parts(num);
+            assert!(parts.iter().all(|part| !part.is_empty()));
+            assert_eq!(parts.concat(), Buffer::new().format(num));
+        }
+
+        #[test]
+        fn concat_f32(bits in any::<u32>()) {
+            let num = f32::from_bits(bits);
+            let parts = parts(num);
+            assert!(parts.iter().all(|part| !part.is_empty()));
+            assert_eq!(parts.concat(), Buffer::new().format(num));
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Custom strings for NaN and infinities.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// The strings written for non-finite numbers by [Buffer::format_specials] and friends.
+///
+/// Any string may be used, including empty or single-character ones, e.g. for fixed-width
+/// columns.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub struct Specials<'a> {
+    /// Written for NaN, of any sign or payload.
+    pub nan: &'a str,
+    /// Written for positive infinity.
+    pub pos_inf: &'a str,
+    /// Written for negative infinity.
+    pub neg_inf: &'a str,
+}
+
+impl Specials<'static> {
+    /// The strings written by [Buffer::format] and friends: `"NaN"`, `"inf"`, and `"-inf"`.
+    pub const DEFAULT: Self = Specials { nan: crate::NAN, pos_inf: crate::POS_INF, neg_inf: crate::NEG_INF };
+
+    /// Single-character strings: `"*"` for NaN, `"+"` for positive infinity, and `"-"` for
+    /// negative infinity.
+    pub const SINGLE_CHAR: Self = Specials { nan: "*", pos_inf: "+", neg_inf: "-" };
+}
+
+impl Default for Specials<'static> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<F: Float> Buffer<F, format::General> {
+    /// As [Self::format], but formatting NaN and infinities as the strings given by `specials`.
+    ///
+    /// ```
+    /// use teju::Specials;
+    /// assert_eq!(teju::Buffer::new().format_specials(f64::NAN, Specials::SINGLE_CHAR), "*");
+    /// assert_eq!(teju::Buffer::new().format_specials(-1. / 0., Specials::SINGLE_CHAR), "-");
+    /// assert_eq!(teju::Buffer::new().format_specials(1.5, Specials::SINGLE_CHAR), "1.5");
+    /// ```
+    pub fn format_specials<'a>(&'a mut self, num: F, specials: Specials<'a>) -> &'a str {
+        match num.classify() {
+            teju::float::FloatType::Finite => self.format_finite(num),
+            teju::float::FloatType::PosInf => specials.pos_inf,
+            teju::float::FloatType::NegInf => specials.neg_inf,
+            teju::float::FloatType::Nan => specials.nan,
+        }
+    }
+}
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// As [Self::format_exp], but formatting NaN and infinities as the strings given by
+    /// `specials`.
+    ///
+    /// ```
+    /// let specials = teju::Specials { nan: "", pos_inf: "Infinity", neg_inf: "-Infinity" };
+    /// assert_eq!(teju::Buffer::new().format_exp_specials(f64::NAN, specials), "");
+    /// assert_eq!(teju::Buffer::new().format_exp_specials(1. / 0., specials), "Infinity");
+    /// assert_eq!(teju::Buffer::new().format_exp_specials(150.0, specials), "1.5e2");
+    /// ```
+    pub fn format_exp_specials<'a>(&'a mut self, num: F, specials: Specials<'a>) -> &'a str {
+        match num.classify() {
+            teju::float::FloatType::Finite => self.format_exp_finite(num),
+            teju::float::FloatType::PosInf => specials.pos_inf,
+            teju::float::FloatType::NegInf => specials.neg_inf,
+            teju::float::FloatType::Nan => specials.nan,
+        }
+    }
+}
+
+impl<F: Float> Buffer<F, format::Decimal> {
+    /// As [Self::format_dec], but formatting NaN and infinities as the strings given by
+    /// `specials`.
+    ///
+    /// ```
+    /// use teju::Specials;
+    /// assert_eq!(teju::Buffer::new().format_dec_specials(1. / 0., Specials::SINGLE_CHAR), "+");
+    /// assert_eq!(teju::Buffer::new().format_dec_specials(1e7, Specials::SINGLE_CHAR), "10000000.0");
+    /// ```
+    pub fn format_dec_specials<'a>(&'a mut self, num: F, specials: Specials<'a>) -> &'a str {
+        match num.classify() {
+            teju::float::FloatType::Finite => self.format_dec_finite(num),
+            teju::float::FloatType::PosInf => specials.pos_inf,
+            teju::float::FloatType::NegInf => specials.neg_inf,
+            teju::float::FloatType::Nan => specials.nan,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn specials() {
+        let specials = Specials { nan: "?", pos_inf: "∞", neg_inf: "-∞" };
+        assert_eq!(Buffer::new().format_specials(-f64::NAN, specials), "?");
+        assert_eq!(Buffer::new().format_specials(f64::INFINITY, specials), "∞");
+        assert_eq!(Buffer::new().format_exp_specials(f32::NEG_INFINITY, specials), "-∞");
+        assert_eq!(Buffer::new().format_dec_specials(f32::NAN, specials), "?");
+        assert_eq!(Buffer::new().format_specials(-0.0f64, specials), "-0.0");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn default_same_as_format(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            assert_eq!(Buffer::new().format_specials(num, Specials::DEFAULT), Buffer::new().format(num));
+            assert_eq!(Buffer::new().format_exp_specials(num, Specials::DEFAULT), Buffer::new().format_exp(num));
+            assert_eq!(Buffer::new().format_dec_specials(num, Specials::DEFAULT), Buffer::new().format_dec(num));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Scientific notation with the mantissa and exponent written to separate buffers.
+
+use crate::{Buffer, Float, teju};
+use teju::{fmt, format};
+
+/// Length of the longest mantissa written by [Buffer::format_exp_split], e.g.
+/// `-1.7976931348623157`.
+pub const MAX_SPLIT_MANT_LEN: usize = 1 + 17 + 1;
+
+/// Length of the longest exponent written by [Buffer::format_exp_split], e.g. `324`.
+pub const MAX_SPLIT_EXP_LEN: usize = 3;
+
+impl<F: Float> Buffer<F, format::Scientific> {
+    /// Print a floating point `num` in scientific notation, as [Self::format_exp], but write the
+    /// mantissa (with its sign and decimal point) to `mant_buf` and the digits of the exponent to
+    /// `exp_buf`. Returns the number of bytes written to each, and whether the exponent is
+    /// negative.
+    ///
+    /// NaN and infinities are written to `mant_buf` as `"NaN"`, `"inf"`, and `"-inf"`, with an
+    /// empty exponent. Zero is written as `0` with exponent `0`.
+    ///
+    /// ```
+    /// let mut mant = [0u8; teju::MAX_SPLIT_MANT_LEN];
+    /// let mut exp = [0u8; teju::MAX_SPLIT_EXP_LEN];
+    /// let (mant_len, exp_len, exp_negative) = teju::Buffer::new().format_exp_split(1.37e-2, &mut mant, &mut exp);
+    /// assert_eq!(&mant[..mant_len], b"1.37");
+    /// assert_eq!(&exp[..exp_len], b"2");
+    /// assert!(exp_negative);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `mant_buf` or `exp_buf` is too short for the output. Buffers of
+    /// [MAX_SPLIT_MANT_LEN] and [MAX_SPLIT_EXP_LEN] bytes are always long enough.
+    pub fn format_exp_split(&mut self, num: F, mant_buf: &mut [u8], exp_buf: &mut [u8]) -> (usize, usize, bool) {
+        let special = match num.classify() {
+            teju::float::FloatType::Finite => None,
+            teju::float::FloatType::PosInf => Some(crate::POS_INF),
+            teju::float::FloatType::NegInf => Some(crate::NEG_INF),
+            teju::float::FloatType::Nan => Some(crate::NAN),
+        };
+        if let Some(str) = special {
+            mant_buf[..str.len()].copy_from_slice(str.as_bytes());
+            return (str.len(), 0, false)
+        }
+
+        let decimal = unsafe { num.to_decimal_finite() };
+        let mant_len = fmt::len_u64(decimal.mant);
+        // Exponent of the first digit.
+        let exp = decimal.exp + mant_len as i32 - 1;
+        let exp_len = fmt::len_u64(exp.unsigned_abs() as u64);
+
+        // The mantissa printers write past the end of the mantissa, so it is printed into this
+        // buffer and then copied.
+        let ptr = <format::Scientific as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            ptr.write(b'-');
+            let buf = ptr.add(!decimal.sign as usize);
+            let len = if mant_len == 1 {
+                *buf = b'0' + decimal.mant as u8;
+                1
+            } else {
+                fmt::print_u64_mantissa_with_point(decimal.mant, buf, mant_len, 1)
+            };
+            !decimal.sign as usize + len
+        };
+        debug_assert!(n <= MAX_SPLIT_MANT_LEN);
+        mant_buf[..n].copy_from_slice(unsafe { core::slice::from_raw_parts(ptr, n) });
+
+        // The exponent printer writes only the digits, given a nonnegative exponent.
+        let exp_buf = &mut exp_buf[..exp_len];
+        unsafe { fmt::print_i32_exp(exp.abs(), exp_buf.as_mut_ptr()) };
+        (n, exp_len, exp < 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split<F: Float>(num: F) -> (String, String, bool) {
+        let mut mant = [0u8; MAX_SPLIT_MANT_LEN];
+        let mut exp = [0u8; MAX_SPLIT_EXP_LEN];
+        let (mant_len, exp_len, exp_negative) = Buffer::new().format_exp_split(num, &mut mant, &mut exp);
+        let mant = String::from_utf8(mant[..mant_len].to_vec()).unwrap();
+        let exp = String::from_utf8(exp[..exp_len].to_vec()).unwrap();
+        (mant, exp, exp_negative)
+    }
+
+    #[test]
+    fn parts() {
+        assert_eq!(split(137.0f64), ("1.37".into(), "2".into(), false));
+        assert_eq!(split(-1e-7f32), ("-1".into(), "7".into(), true));
+        assert_eq!(split(0.0f64), ("0".into(), "0".into(), false));
+        assert_eq!(split(-f64::MAX), ("-1.7976931348623157".into(), "308".into(), false));
+        assert_eq!(split(5e-324f64), ("5".into(), "324".into(), true));
+        assert_eq!(split(-0.0f32), ("-0".into(), "0".into(), false));
+    }
+
+    #[test]
+    fn same_as_format_exp() {
+        for num in [1.0, -1.5, 123456.789, 1e-7, 9.999e99, f64::MIN_POSITIVE, 5e-324, f64::MAX] {
+            let (mant, exp, exp_negative) = split(num);
+            let sign = if exp_negative { "-" } else { "" };
+            assert_eq!(format!("{mant}e{sign}{exp}"), Buffer::new().format_exp(num));
+        }
+    }
+
+    #[test]
+    fn specials() {
+        assert_eq!(split(f64::NAN), ("NaN".into(), "".into(), false));
+        assert_eq!(split(f64::NEG_INFINITY), ("-inf".into(), "".into(), false));
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_short() {
+        let mut mant = [0u8; 3];
+        let mut exp = [0u8; 3];
+        Buffer::new().format_exp_split(1.25f64, &mut mant, &mut exp);
+    }
+}
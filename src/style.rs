@@ -0,0 +1,153 @@
+//! Notation chosen at runtime.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// The notation used by [Buffer::format_as].
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub enum Style {
+    /// As [Buffer::format].
+    General,
+    /// As [Buffer::format_exp].
+    Scientific,
+    /// As [Buffer::format_dec].
+    Decimal,
+}
+
+/// Length of the longest string printed by [Buffer::format_as] in notation `style`, for floats of
+/// `f_bits` bits, i.e. `16` for `f16` and `bf16`, `32` for `f32`, and `64` for `f64`. This is a
+/// `const fn`, so it can be used to size arrays to which the output is copied.
+///
+/// ```
+/// use teju::Style;
+/// assert_eq!(teju::required_len(64, Style::Scientific), "-2.2250738585072014e-308".len());
+///
+/// let mut out = [0u8; teju::required_len(32, Style::Decimal)];
+/// let mut buffer = teju::Buffer::new();
+/// let printed = buffer.format_as(-f32::MIN_POSITIVE, Style::Decimal);
+/// out[..printed.len()].copy_from_slice(printed.as_bytes());
+/// ```
+///
+/// ## Panics
+///
+/// Panics if `f_bits` is not `16`, `32` or `64`.
+pub const fn required_len(f_bits: u32, style: Style) -> usize {
+    match f_bits {
+        16 => max(len(format::F16, style), len(format::BF16, style)),
+        32 => len(format::F32, style),
+        64 => len(format::F64, style),
+        _ => panic!("Float width must be 16, 32 or 64 bits"),
+    }
+}
+
+const fn len(l: format::Limits, style: Style) -> usize {
+    match style {
+        Style::General => format::general_len(l),
+        Style::Scientific => format::exp_len(l),
+        Style::Decimal => format::dec_len(l),
+    }
+}
+
+const fn max(a: usize, b: usize) -> usize { if a > b { a } else { b } }
+
+impl<F: Float> Buffer<F, format::Dynamic> {
+    /// Print a floating point `num` into this buffer in the notation given by `style`, and return
+    /// a reference to its string representation.
+    ///
+    /// This is for when the notation is only known at runtime; the buffer is large enough for any
+    /// of them.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// use teju::Style;
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_as(1e3, Style::General), "1000.0");
+    /// assert_eq!(buffer.format_as(1e3, Style::Scientific), "1e3");
+    /// assert_eq!(buffer.format_as(1e30, Style::Decimal), "1000000000000000000000000000000.0");
+    /// ```
+    pub fn format_as(&mut self, num: F, style: Style) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match (num.classify_finite(), style) {
+            (teju::float::FiniteFloatType::PosZero, Style::Scientific) => return crate::POS_ZERO_EXP,
+            (teju::float::FiniteFloatType::NegZero, Style::Scientific) => return crate::NEG_ZERO_EXP,
+            (teju::float::FiniteFloatType::PosZero, _) => return crate::POS_ZERO,
+            (teju::float::FiniteFloatType::NegZero, _) => return crate::NEG_ZERO,
+            (teju::float::FiniteFloatType::Nonzero, _) => (),
+        }
+        let ptr = <format::Dynamic as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            match style {
+                Style::General => num.format_general_finite_nonzero(ptr),
+                Style::Scientific => num.format_exp_finite_nonzero(ptr),
+                Style::Decimal => num.format_dec_finite_nonzero(ptr),
+            }
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_as_static() {
+        for num in [0.0, -0.0, 1.5, -1e-7, 1e30, f64::MAX, 5e-324, f64::NAN, f64::NEG_INFINITY] {
+            assert_eq!(Buffer::new().format_as(num, Style::General), Buffer::new().format(num));
+            assert_eq!(Buffer::new().format_as(num, Style::Scientific), Buffer::new().format_exp(num));
+            assert_eq!(Buffer::new().format_as(num, Style::Decimal), Buffer::new().format_dec(num));
+            let num = num as f32;
+            assert_eq!(Buffer::new().format_as(num, Style::General), Buffer::new().format(num));
+            assert_eq!(Buffer::new().format_as(num, Style::Scientific), Buffer::new().format_exp(num));
+            assert_eq!(Buffer::new().format_as(num, Style::Decimal), Buffer::new().format_dec(num));
+        }
+    }
+
+    #[test]
+    fn required_len_extremes() {
+        for style in [Style::General, Style::Scientific, Style::Decimal] {
+            let mut max_len = 0;
+            for num in [f64::MAX, f64::MIN_POSITIVE, 5e-324, 1.2345678901234567e-5, 1.2345678901234567e-100] {
+                max_len = max_len.max(Buffer::new().format_as(-num, style).len());
+            }
+            assert_eq!(max_len, required_len(64, style));
+            for bits in (0 .. u32::MAX).step_by(9973) {
+                assert!(Buffer::new().format_as(f32::from_bits(bits), style).len() <= required_len(32, style));
+            }
+        }
+        assert_eq!(Buffer::new().format(-1000013200000000.0f32).len(), required_len(32, Style::General));
+        assert_eq!(Buffer::new().format_exp(-1.00303895e-36f32).len(), required_len(32, Style::Scientific));
+        assert_eq!(Buffer::new().format_dec(-f32::MIN_POSITIVE).len(), required_len(32, Style::Decimal));
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn required_len_half() {
+        use half::{bf16, f16};
+        for style in [Style::General, Style::Scientific, Style::Decimal] {
+            let mut max_len = 0;
+            for bits in 0 ..= u16::MAX {
+                max_len = max_len.max(Buffer::new().format_as(f16::from_bits(bits), style).len());
+                max_len = max_len.max(Buffer::new().format_as(bf16::from_bits(bits), style).len());
+            }
+            assert_eq!(max_len, required_len(16, style));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn required_len_f128() {
+        required_len(128, Style::General);
+    }
+}
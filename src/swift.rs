@@ -0,0 +1,201 @@
+//! Output identical to Swift's `description` of `Double` and `Float`.
+
+use crate::{Buffer, Float, teju};
+use crate::go::format_signed_exp_decimal;
+use teju::common::SignedDecimal;
+use teju::{float::abs_bits, format};
+
+/// Print `decimal`, which must be nonzero and have no trailing zeros, as Swift's `description`,
+/// in exponential notation if `exponential` or if its magnitude is less than `0.0001`, and return
+/// the number of bytes written.
+unsafe fn format_swift_decimal(decimal: SignedDecimal, exponential: bool, buf: *mut u8) -> usize {
+    let dec_exps = if exponential {0 .. 0} else {-4 .. i32::MAX};
+    unsafe { format_signed_exp_decimal(decimal, dec_exps, true, buf) }
+}
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer, and return a reference to its string
+    /// representation, in the style of Swift's `String(describing: num)` for a `Double` (or
+    /// `Float`, for `f32`). Both print the shortest digits that round-trip, so the output is
+    /// identical, except where two such representations are equally short.
+    ///
+    /// As in Swift, numbers are written in exponential notation if their magnitude is less than
+    /// `0.0001`, or greater than `2^(MANTISSA_DIGITS + 1)`, e.g. `2^54` for `f64` and `2^25` for
+    /// `f32`, above which not every integer can be represented; and in decimal notation otherwise,
+    /// with a trailing `.0` for integers. Unlike [Self::format], the exponent always has a sign and
+    /// at least two digits.
+    ///
+    /// This function formats zero as `"0.0"`, positive infinity as `"inf"`, negative infinity as
+    /// `"-inf"`, and NaN as `"nan"`, with a `-` if its sign bit is set, and as `"snan"` if it is a
+    /// signaling NaN, to match Swift.
+    ///
+    /// ```
+    /// assert_eq!(teju::Buffer::new().format_swift(1e16), "10000000000000000.0");
+    /// assert_eq!(teju::Buffer::new().format_swift(1e17), "1e+17");
+    /// assert_eq!(teju::Buffer::new().format_swift(0.0001), "0.0001");
+    /// assert_eq!(teju::Buffer::new().format_swift(0.00001), "1e-05");
+    /// assert_eq!(teju::Buffer::new().format_swift(f64::NAN), "nan");
+    /// ```
+    pub fn format_swift(&mut self, num: F) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => {
+                let quiet = num.to_bits_u64() & F::QUIET_NAN_BIT != 0;
+                return match (num.is_sign_negative(), quiet) {
+                    (false, true) => "nan",
+                    (true, true) => "-nan",
+                    (false, false) => "snan",
+                    (true, false) => "-snan",
+                }
+            },
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let exponential = abs_bits(num) > F::EXACT_INT_LIMIT_BITS;
+        let ptr = <format::General as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            let decimal = num.to_decimal_finite().trim_trailing_zeros();
+            format_swift_decimal(decimal, exponential, ptr)
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Expected outputs of `String(describing: x)` for `Double`, derived from Swift's documented
+    /// rules for `description`.
+    #[test]
+    fn swift_corpus() {
+        let mut buf = Buffer::new();
+        for (num, str) in [
+            (1.0, "1.0"),
+            (-1.5, "-1.5"),
+            (100.0, "100.0"),
+            (0.1, "0.1"),
+            (0.001, "0.001"),
+            (0.00012, "0.00012"),
+            (0.00001234, "1.234e-05"),
+            (123.456, "123.456"),
+            (9007199254740992.0, "9007199254740992.0"),
+            (18000000000000000.0, "18000000000000000.0"),
+            (19000000000000000.0, "1.9e+16"),
+            (1e20, "1e+20"),
+            (1.5e300, "1.5e+300"),
+            (f64::MAX, "1.7976931348623157e+308"),
+            (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+            (5e-324, "5e-324"),
+            (0.0, "0.0"),
+            (-0.0, "-0.0"),
+        ] {
+            assert_eq!(buf.format_swift(num), str);
+        }
+    }
+
+    /// Expected outputs of `String(describing: x)` for `Float`, derived from Swift's documented
+    /// rules for `description`.
+    #[test]
+    fn swift_corpus_f32() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_swift(0.1f32), "0.1");
+        assert_eq!(buf.format_swift(16777216.0f32), "16777216.0");
+        assert_eq!(buf.format_swift(33000000.0f32), "33000000.0");
+        assert_eq!(buf.format_swift(34000000.0f32), "3.4e+07");
+        assert_eq!(buf.format_swift(f32::MAX), "3.4028235e+38");
+        assert_eq!(buf.format_swift(1e-45f32), "1e-45");
+    }
+
+    /// `format_swift` only takes exponential notation above `2^53`, but the exponent of numbers in
+    /// `[1, 10)` is still printed as `+00`, never as `-00`.
+    #[test]
+    fn zero_exponent() {
+        let mut buf = [0u8; 32];
+        let mut check = |sign, mant, exp, str: &str| {
+            let len = unsafe { format_swift_decimal(SignedDecimal { sign, mant, exp }, true, buf.as_mut_ptr()) };
+            assert_eq!(&buf[..len], str.as_bytes());
+        };
+        check(true, 15, -1, "1.5e+00");
+        check(false, 15, -1, "-1.5e+00");
+        check(true, 9, 0, "9e+00");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_swift(f64::INFINITY), "inf");
+        assert_eq!(buf.format_swift(f64::NEG_INFINITY), "-inf");
+        assert_eq!(buf.format_swift(f64::from_bits(0x7ff8_0000_0000_0000)), "nan");
+        assert_eq!(buf.format_swift(f64::from_bits(0xfff8_0000_0000_0000)), "-nan");
+        assert_eq!(buf.format_swift(f64::from_bits(0x7ff4_0000_0000_0000)), "snan");
+        assert_eq!(buf.format_swift(f64::from_bits(0xfff0_0000_0000_0001)), "-snan");
+        assert_eq!(Buffer::new().format_swift(f32::from_bits(0x7fa0_0000)), "snan");
+        assert_eq!(Buffer::new().format_swift(-f32::NAN), "-nan");
+    }
+
+    /// `half` types, whose exact integers end at `2^12` for `f16` and `2^9` for `bf16`.
+    #[cfg(feature = "half")]
+    #[test]
+    fn half() {
+        use half::{bf16, f16};
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_swift(f16::from_f32(4096.0)), "4096.0");
+        assert_eq!(buf.format_swift(f16::from_f32(4100.0)), "4.1e+03");
+        assert_eq!(buf.format_swift(f16::MAX), "6.55e+04");
+        assert_eq!(buf.format_swift(f16::NAN), "nan");
+        assert_eq!(buf.format_swift(-f16::NAN), "-nan");
+        assert_eq!(buf.format_swift(f16::from_bits(0x7c01)), "snan");
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_swift(bf16::from_f32(512.0)), "512.0");
+        assert_eq!(buf.format_swift(bf16::from_f32(516.0)), "5.16e+02");
+        assert_eq!(buf.format_swift(bf16::from_f32(9.96921e37)), "1e+38");
+        assert_eq!(buf.format_swift(bf16::NAN), "nan");
+        assert_eq!(buf.format_swift(bf16::from_bits(0xff81)), "-snan");
+
+        for bits in 0 ..= u16::MAX {
+            let num = f16::from_bits(bits);
+            if num.is_finite() && num != f16::ZERO {
+                let large = num.to_f32().abs() > 4096.0;
+                assert_eq!(Buffer::new().format_swift(num), expected(num, large));
+            }
+            let num = bf16::from_bits(bits);
+            if num.is_finite() && num != bf16::ZERO {
+                let large = num.to_f32().abs() > 512.0;
+                assert_eq!(Buffer::new().format_swift(num), expected(num, large));
+            }
+        }
+    }
+
+    /// The Swift description of the finite, nonzero `num`, from its scientific and decimal
+    /// notations, where `large` is whether it is above the range of exact integers.
+    fn expected<F: Float>(num: F, large: bool) -> String {
+        let exp_str = Buffer::new().format_exp(num).to_owned();
+        let (mant, exp) = exp_str.split_once('e').unwrap();
+        let exp: i32 = exp.parse().unwrap();
+        if exp >= -4 && !large {
+            Buffer::new().format_dec(num).to_owned()
+        } else {
+            format!("{mant}e{}{:02}", if exp < 0 {'-'} else {'+'}, exp.abs())
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100_000))]
+
+        #[test]
+        fn same_as_exp_and_dec(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            prop_assume!(num.is_finite() && num != 0.0);
+            assert_eq!(Buffer::new().format_swift(num), expected(num, num.abs() > 2f64.powi(54)));
+        }
+    }
+}
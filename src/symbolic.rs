@@ -0,0 +1,129 @@
+//! General notation, with common mathematical constants shown as symbols.
+
+use crate::{Buffer, Float, teju};
+use teju::{float::abs_bits, format};
+
+use core::{f32::consts as c32, f64::consts as c64};
+
+/// The constants recognised by [Buffer::format_symbolic], as `(f64, f32, symbol, negated symbol)`.
+const SYMBOLS: [(f64, f32, &str, &str); 9] = [
+    (c64::PI, c32::PI, "π", "-π"),
+    (c64::TAU, c32::TAU, "τ", "-τ"),
+    (c64::FRAC_PI_2, c32::FRAC_PI_2, "π/2", "-π/2"),
+    (c64::FRAC_PI_4, c32::FRAC_PI_4, "π/4", "-π/4"),
+    (c64::E, c32::E, "e", "-e"),
+    (c64::SQRT_2, c32::SQRT_2, "√2", "-√2"),
+    (c64::FRAC_1_SQRT_2, c32::FRAC_1_SQRT_2, "1/√2", "-1/√2"),
+    (c64::LN_2, c32::LN_2, "ln 2", "-ln 2"),
+    (c64::LN_10, c32::LN_10, "ln 10", "-ln 10"),
+];
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer as [Self::format] does, unless it is one of
+    /// the constants below, in which case its symbol is returned, and return a reference to its
+    /// string representation.
+    ///
+    /// | Constant                                          | Symbol  |
+    /// |---------------------------------------------------|---------|
+    /// | [PI](core::f64::consts::PI)                       | `π`     |
+    /// | [TAU](core::f64::consts::TAU)                     | `τ`     |
+    /// | [FRAC_PI_2](core::f64::consts::FRAC_PI_2)         | `π/2`   |
+    /// | [FRAC_PI_4](core::f64::consts::FRAC_PI_4)         | `π/4`   |
+    /// | [E](core::f64::consts::E)                         | `e`     |
+    /// | [SQRT_2](core::f64::consts::SQRT_2)               | `√2`    |
+    /// | [FRAC_1_SQRT_2](core::f64::consts::FRAC_1_SQRT_2) | `1/√2`  |
+    /// | [LN_2](core::f64::consts::LN_2)                   | `ln 2`  |
+    /// | [LN_10](core::f64::consts::LN_10)                 | `ln 10` |
+    ///
+    /// Their negations are printed with a `-` before the symbol. A constant is only recognised if
+    /// `num` has exactly its bits (those of the `f32` constant for an `f32`), however it was
+    /// computed: e.g. `"3.141592653589793".parse()` is printed as `π`, but `355.0 / 113.0` and the
+    /// next float after `PI` are not. The output is UTF-8, and not ASCII.
+    ///
+    /// Only `f64` and `f32` constants are recognised: `f16` and `bf16` numbers are always printed as
+    /// by [Self::format].
+    ///
+    /// ```
+    /// use std::f64::consts::{PI, SQRT_2};
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_symbolic(PI), "π");
+    /// assert_eq!(buffer.format_symbolic(-SQRT_2), "-√2");
+    /// assert_eq!(buffer.format_symbolic(3.14), "3.14");
+    /// ```
+    pub fn format_symbolic(&mut self, num: F) -> &str {
+        let bits = num.to_bits_u64();
+        let f64 = match core::mem::size_of::<F>() {
+            8 => true,
+            4 => false,
+            _ => return self.format(num),
+        };
+        for (c64, c32, pos, neg) in SYMBOLS {
+            let c = if f64 { c64.to_bits() } else { c32.to_bits() as u64 };
+            if bits == c {
+                return pos
+            } else if abs_bits(num) == c {
+                return neg
+            }
+        }
+        self.format(num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn symbolic() {
+        let mut buf = Buffer::new();
+        for (c64, _, pos, neg) in SYMBOLS {
+            assert_eq!(buf.format_symbolic(c64), pos);
+            assert_eq!(buf.format_symbolic(-c64), neg);
+        }
+        let mut buf = Buffer::new();
+        for (_, c32, pos, neg) in SYMBOLS {
+            assert_eq!(buf.format_symbolic(c32), pos);
+            assert_eq!(buf.format_symbolic(-c32), neg);
+        }
+    }
+
+    #[test]
+    fn exact() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_symbolic(f64::from_bits(c64::PI.to_bits() + 1)), "3.1415926535897936");
+        assert_eq!(buf.format_symbolic(c32::PI as f64), "3.1415927410125732");
+        assert_eq!(buf.format_symbolic(2.0f64.sqrt()), "√2");
+        assert_eq!(buf.format_symbolic(1.0f64), "1.0");
+        assert_eq!(Buffer::new().format_symbolic(c64::E as f32), "e");
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn half() {
+        use half::{bf16, f16};
+        assert_eq!(Buffer::new().format_symbolic(f16::PI), "3.14");
+        assert_eq!(Buffer::new().format_symbolic(-f16::E), "-2.719");
+        assert_eq!(Buffer::new().format_symbolic(bf16::PI), "3.14");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_symbolic(0.0f64), "0.0");
+        assert_eq!(buf.format_symbolic(-0.0f64), "-0.0");
+        assert_eq!(buf.format_symbolic(f64::NAN), "NaN");
+        assert_eq!(buf.format_symbolic(f64::NEG_INFINITY), "-inf");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_format(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            prop_assume!(SYMBOLS.iter().all(|c| c.0.to_bits() != bits && (-c.0).to_bits() != bits));
+            assert_eq!(Buffer::new().format_symbolic(num), Buffer::new().format(num));
+        }
+    }
+}
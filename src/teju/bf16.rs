@@ -0,0 +1,210 @@
+use half::bf16;
+
+super::mk_impl::mk_impl! {
+    float = bf16,
+    mant = u16,
+    mant_signed = i16,
+    mant_double = u32,
+    len_mantissa = crate::teju::fmt::len_u64,
+    print_mantissa = crate::teju::fmt::print_u64_mantissa,
+    print_mantissa_known_len = crate::teju::fmt::print_u64_mantissa_known_len,
+    print_mantissa_with_point = crate::teju::fmt::print_u64_mantissa_with_point,
+    ryu_general_bounds = (-6, 13),
+    limits = crate::teju::format::BF16,
+    tests = {
+        pi = {
+            dec = "3.14",
+            exp = "3.14e0",
+            decimal = Decimal{ exp: -2, mant: 3_14 },
+        },
+        e = {
+            dec = "2.72",
+            exp = "2.72e0",
+            decimal = Decimal{ exp: -2, mant: 2_72 },
+        },
+        ln2 = {
+            dec = "0.69",
+            exp = "6.9e-1",
+            decimal = Decimal{ exp: -2, mant: 0_69 },
+        },
+        min_subnormal = {
+            general = "1e-40",
+            dec = "0.0000000000000000000000000000000000000001",
+            exp = "1e-40",
+            decimal = Decimal{ exp: -40, mant: 1 },
+        },
+        min_normal = {
+            general = "1.18e-38",
+            dec = "0.0000000000000000000000000000000000000118",
+            exp = "1.18e-38",
+            decimal = Decimal{ exp: -38-2, mant: 1_18 },
+        },
+        max = {
+            general = "3.39e38",
+            dec = "339000000000000000000000000000000000000.0",
+            exp = "3.39e38",
+            decimal = Decimal{ exp: 38-2, mant: 3_39 },
+        },
+    }
+}
+
+pub mod lut {
+    use crate::teju::common::{Multiplier, Multipliers, MultInverse, MultInverses};
+    use super::Mant;
+
+    // TODO: generate with crabtime
+
+    pub const MULTIPLIERS: Multipliers<Mant, 78, {super::Binary::MIN_EXP}> = Multipliers::new([
+        Multiplier{ hi: 0x92ef, lo: 0xd1b9 },
+        Multiplier{ hi: 0xeb19, lo: 0x4f8f },
+        Multiplier{ hi: 0xbc14, lo: 0x3fa5 },
+        Multiplier{ hi: 0x9676, lo: 0x9951 },
+        Multiplier{ hi: 0xf0bd, lo: 0xc21b },
+        Multiplier{ hi: 0xc097, lo: 0xce7c },
+        Multiplier{ hi: 0x9a13, lo: 0x0b97 },
+        Multiplier{ hi: 0xf684, lo: 0xdf57 },
+        Multiplier{ hi: 0xc537, lo: 0x1913 },
+        Multiplier{ hi: 0x9dc5, lo: 0xada9 },
+        Multiplier{ hi: 0xfc6f, lo: 0x7c41 },
+        Multiplier{ hi: 0xc9f2, lo: 0xc9ce },
+        Multiplier{ hi: 0xa18f, lo: 0x07d8 },
+        Multiplier{ hi: 0x813f, lo: 0x3979 },
+        Multiplier{ hi: 0xcecb, lo: 0x8f28 },
+        Multiplier{ hi: 0xa56f, lo: 0xa5ba },
+        Multiplier{ hi: 0x8459, lo: 0x5162 },
+        Multiplier{ hi: 0xd3c2, lo: 0x1bcf },
+        Multiplier{ hi: 0xa968, lo: 0x1640 },
+        Multiplier{ hi: 0x8786, lo: 0x7833 },
+        Multiplier{ hi: 0xd8d7, lo: 0x26b8 },
+        Multiplier{ hi: 0xad78, lo: 0xebc6 },
+        Multiplier{ hi: 0x8ac7, lo: 0x2305 },
+        Multiplier{ hi: 0xde0b, lo: 0x6b3b },
+        Multiplier{ hi: 0xb1a2, lo: 0xbc2f },
+        Multiplier{ hi: 0x8e1b, lo: 0xc9c0 },
+        Multiplier{ hi: 0xe35f, lo: 0xa932 },
+        Multiplier{ hi: 0xb5e6, lo: 0x20f5 },
+        Multiplier{ hi: 0x9184, lo: 0xe72b },
+        Multiplier{ hi: 0xe8d4, lo: 0xa511 },
+        Multiplier{ hi: 0xba43, lo: 0xb741 },
+        Multiplier{ hi: 0x9502, lo: 0xf901 },
+        Multiplier{ hi: 0xee6b, lo: 0x2801 },
+        Multiplier{ hi: 0xbebc, lo: 0x2001 },
+        Multiplier{ hi: 0x9896, lo: 0x8001 },
+        Multiplier{ hi: 0xf424, lo: 0x0001 },
+        Multiplier{ hi: 0xc350, lo: 0x0001 },
+        Multiplier{ hi: 0x9c40, lo: 0x0001 },
+        Multiplier{ hi: 0xfa00, lo: 0x0001 },
+        Multiplier{ hi: 0xc800, lo: 0x0001 },
+        Multiplier{ hi: 0xa000, lo: 0x0001 },
+        Multiplier{ hi: 0x8000, lo: 0x0001 },
+        Multiplier{ hi: 0xcccc, lo: 0xcccd },
+        Multiplier{ hi: 0xa3d7, lo: 0x0a3e },
+        Multiplier{ hi: 0x8312, lo: 0x6e98 },
+        Multiplier{ hi: 0xd1b7, lo: 0x1759 },
+        Multiplier{ hi: 0xa7c5, lo: 0xac48 },
+        Multiplier{ hi: 0x8637, lo: 0xbd06 },
+        Multiplier{ hi: 0xd6bf, lo: 0x94d6 },
+        Multiplier{ hi: 0xabcc, lo: 0x7712 },
+        Multiplier{ hi: 0x8970, lo: 0x5f42 },
+        Multiplier{ hi: 0xdbe6, lo: 0xfecf },
+        Multiplier{ hi: 0xafeb, lo: 0xff0c },
+        Multiplier{ hi: 0x8cbc, lo: 0xcc0a },
+        Multiplier{ hi: 0xe12e, lo: 0x1343 },
+        Multiplier{ hi: 0xb424, lo: 0xdc36 },
+        Multiplier{ hi: 0x901d, lo: 0x7cf8 },
+        Multiplier{ hi: 0xe695, lo: 0x94bf },
+        Multiplier{ hi: 0xb877, lo: 0xaa33 },
+        Multiplier{ hi: 0x9392, lo: 0xee8f },
+        Multiplier{ hi: 0xec1e, lo: 0x4a7e },
+        Multiplier{ hi: 0xbce5, lo: 0x0865 },
+        Multiplier{ hi: 0x971d, lo: 0xa051 },
+        Multiplier{ hi: 0xf1c9, lo: 0x0081 },
+        Multiplier{ hi: 0xc16d, lo: 0x9a01 },
+        Multiplier{ hi: 0x9abe, lo: 0x14ce },
+        Multiplier{ hi: 0xf796, lo: 0x87af },
+        Multiplier{ hi: 0xc612, lo: 0x0626 },
+        Multiplier{ hi: 0x9e74, lo: 0xd1b8 },
+        Multiplier{ hi: 0xfd87, lo: 0xb5f3 },
+        Multiplier{ hi: 0xcad2, lo: 0xf7f6 },
+        Multiplier{ hi: 0xa242, lo: 0x5ff8 },
+        Multiplier{ hi: 0x81ce, lo: 0xb32d },
+        Multiplier{ hi: 0xcfb1, lo: 0x1eae },
+        Multiplier{ hi: 0xa627, lo: 0x4bbe },
+        Multiplier{ hi: 0x84ec, lo: 0x3c98 },
+        Multiplier{ hi: 0xd4ad, lo: 0x2dc0 },
+        Multiplier{ hi: 0xaa24, lo: 0x249a },
+    ]);
+
+    pub const MULT_INVERSES: MultInverses<Mant, 7> = MultInverses::new([
+        MultInverse{ multiplier: 0x0001, bound: 0xffff },
+        MultInverse{ multiplier: 0xcccd, bound: 0x3333 },
+        MultInverse{ multiplier: 0x5c29, bound: 0x0a3d },
+        MultInverse{ multiplier: 0x78d5, bound: 0x020c },
+        MultInverse{ multiplier: 0x7e91, bound: 0x0068 },
+        MultInverse{ multiplier: 0xe61d, bound: 0x0014 },
+        MultInverse{ multiplier: 0x6139, bound: 0x0004 },
+    ]);
+}
+
+/// Exhaustive tests, which are only feasible for 16-bit floats.
+#[cfg(test)]
+mod exhaustive {
+    use super::*;
+    use crate::Buffer;
+    use crate::teju::float::Sealed;
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format(bf16::PI), "3.14");
+        assert_eq!(buf.format(bf16::E), "2.72");
+        assert_eq!(buf.format(bf16::ONE), "1.0");
+        assert_eq!(buf.format(bf16::from_bits(1)), "1e-40");
+        assert_eq!(buf.format(bf16::MIN_POSITIVE), "1.18e-38");
+        assert_eq!(buf.format(bf16::MAX), "3.39e38");
+        assert_eq!(buf.format(bf16::MIN), "-3.39e38");
+        assert_eq!(buf.format(bf16::NEG_ZERO), "-0.0");
+        assert_eq!(buf.format(bf16::NAN), "NaN");
+        assert_eq!(Buffer::new().format_dec(bf16::from_bits(1)), "0.0000000000000000000000000000000000000001");
+        assert_eq!(Buffer::new().format_dec(bf16::MAX), "339000000000000000000000000000000000000.0");
+    }
+
+    /// Every finite `bf16` is printed with digits that parse back to it, in every notation, also
+    /// through `f32`.
+    #[test]
+    fn roundtrip() {
+        for bits in 0 ..= u16::MAX {
+            let num = bf16::from_bits(bits);
+            if !num.is_finite() {
+                continue
+            }
+            let strs = [
+                Buffer::new().format(num).to_owned(),
+                Buffer::new().format_exp(num).to_owned(),
+                Buffer::new().format_dec(num).to_owned(),
+            ];
+            for str in strs {
+                assert_eq!(str.parse::<bf16>().unwrap().to_bits(), bits, "{str}");
+                assert_eq!(bf16::from_f32(str.parse::<f32>().unwrap()).to_bits(), bits, "{str}");
+            }
+        }
+    }
+
+    /// No representation with fewer significant digits parses back to the same `bf16`.
+    #[test]
+    fn shortest() {
+        for bits in 1 .. bf16::INFINITY.to_bits() {
+            let num = bf16::from_bits(bits);
+            let decimal = unsafe { num.to_decimal_finite() };
+            let len = crate::teju::fmt::len_u64(decimal.mant);
+            for digits in 1 .. len {
+                let pow10 = 10u64.pow((len - digits) as u32);
+                let exp = decimal.exp + (len - digits) as i32;
+                for mant in [decimal.mant / pow10, decimal.mant / pow10 + 1] {
+                    let parsed = format!("{mant}e{exp}").parse::<bf16>().unwrap();
+                    assert_ne!(parsed.to_bits(), bits, "{mant}e{exp} for {}", Buffer::new().format(num));
+                }
+            }
+        }
+    }
+}
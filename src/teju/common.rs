@@ -4,8 +4,23 @@
 /// to accomodate the exponent of any floating point format.
 pub type Exp = i32;
 
+/// A decimal representation of a finite float of any type, i.e. `±mant * 10^exp`.
+///
+/// Formatting routines which are shared between all float types work on this type, rather than
+/// on the per-type `Result`.
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub struct SignedDecimal {
+    /// `true` if the number is positive.
+    pub sign: bool,
+    pub mant: u64,
+    pub exp: Exp,
+}
+
 //
 
+/// Range of exponents for which [exp_log10_pow2] and [exp_log10_pow2_residual] are exact.
 pub const EXP_LOG10_POW2_BOUNDS: core::ops::RangeInclusive<i32> = -112815 ..= 112815;
 
 /// Returns the largest exponent `f` such that `10^f ≤ 2^e`, i.e. the integer part of
@@ -20,8 +35,9 @@ pub const fn exp_log10_pow2(exp: i32) -> i32 {
     (x >> 32) as i32
 }
 
-/// Returns the largest exponent `f` such that `10^f ≤ 2^e`, i.e. the integer part of
-/// `log_10(2^e)`.
+/// Returns `e - e₀`, where `e₀` is the smallest exponent such that
+/// `exp_log10_pow2(e₀) == exp_log10_pow2(e)`, i.e. how many exponents below `e` have the same
+/// [exp_log10_pow2].
 ///
 /// Uses an euclidean approximation that is only valid in the range [EXP_LOG10_POW2_BOUNDS]. If
 /// `exp` is not in that range, the result is unspecified.
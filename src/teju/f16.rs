@@ -0,0 +1,140 @@
+use half::f16;
+
+super::mk_impl::mk_impl! {
+    float = f16,
+    mant = u16,
+    mant_signed = i16,
+    mant_double = u32,
+    len_mantissa = crate::teju::fmt::len_u64,
+    print_mantissa = crate::teju::fmt::print_u64_mantissa,
+    print_mantissa_known_len = crate::teju::fmt::print_u64_mantissa_known_len,
+    print_mantissa_with_point = crate::teju::fmt::print_u64_mantissa_with_point,
+    ryu_general_bounds = (-6, 13),
+    limits = crate::teju::format::F16,
+    tests = {
+        pi = {
+            dec = "3.14",
+            exp = "3.14e0",
+            decimal = Decimal{ exp: -2, mant: 3_14 },
+        },
+        e = {
+            dec = "2.719",
+            exp = "2.719e0",
+            decimal = Decimal{ exp: -3, mant: 2_719 },
+        },
+        ln2 = {
+            dec = "0.6934",
+            exp = "6.934e-1",
+            decimal = Decimal{ exp: -4, mant: 0_6934 },
+        },
+        min_subnormal = {
+            general = "6e-8",
+            dec = "0.00000006",
+            exp = "6e-8",
+            decimal = Decimal{ exp: -8, mant: 6 },
+        },
+        min_normal = {
+            general = "0.00006104",
+            dec = "0.00006104",
+            exp = "6.104e-5",
+            decimal = Decimal{ exp: -5-3, mant: 6_104 },
+        },
+        max = {
+            general = "65500.0",
+            dec = "65500.0",
+            exp = "6.55e4",
+            decimal = Decimal{ exp: 4-2, mant: 6_55 },
+        },
+    }
+}
+
+pub mod lut {
+    use crate::teju::common::{Multiplier, Multipliers, MultInverse, MultInverses};
+    use super::Mant;
+
+    // TODO: generate with crabtime
+
+    pub const MULTIPLIERS: Multipliers<Mant, 10, {super::Binary::MIN_EXP}> = Multipliers::new([
+        Multiplier{ hi: 0xbebc, lo: 0x2001 },
+        Multiplier{ hi: 0x9896, lo: 0x8001 },
+        Multiplier{ hi: 0xf424, lo: 0x0001 },
+        Multiplier{ hi: 0xc350, lo: 0x0001 },
+        Multiplier{ hi: 0x9c40, lo: 0x0001 },
+        Multiplier{ hi: 0xfa00, lo: 0x0001 },
+        Multiplier{ hi: 0xc800, lo: 0x0001 },
+        Multiplier{ hi: 0xa000, lo: 0x0001 },
+        Multiplier{ hi: 0x8000, lo: 0x0001 },
+        Multiplier{ hi: 0xcccc, lo: 0xcccd },
+    ]);
+
+    pub const MULT_INVERSES: MultInverses<Mant, 7> = MultInverses::new([
+        MultInverse{ multiplier: 0x0001, bound: 0xffff },
+        MultInverse{ multiplier: 0xcccd, bound: 0x3333 },
+        MultInverse{ multiplier: 0x5c29, bound: 0x0a3d },
+        MultInverse{ multiplier: 0x78d5, bound: 0x020c },
+        MultInverse{ multiplier: 0x7e91, bound: 0x0068 },
+        MultInverse{ multiplier: 0xe61d, bound: 0x0014 },
+        MultInverse{ multiplier: 0x6139, bound: 0x0004 },
+    ]);
+}
+
+/// Exhaustive tests, which are only feasible for 16-bit floats.
+#[cfg(test)]
+mod exhaustive {
+    use super::*;
+    use crate::Buffer;
+    use crate::teju::float::Sealed;
+
+    #[test]
+    fn extremes() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format(f16::PI), "3.14");
+        assert_eq!(buf.format(f16::E), "2.719");
+        assert_eq!(buf.format(f16::ONE), "1.0");
+        assert_eq!(buf.format(f16::from_bits(1)), "6e-8");
+        assert_eq!(buf.format(f16::MIN_POSITIVE), "0.00006104");
+        assert_eq!(buf.format(f16::MAX), "65500.0");
+        assert_eq!(buf.format(f16::MIN), "-65500.0");
+        assert_eq!(buf.format(f16::NEG_ZERO), "-0.0");
+        assert_eq!(buf.format(f16::NAN), "NaN");
+        assert_eq!(Buffer::new().format_dec(f16::from_bits(1)), "0.00000006");
+        assert_eq!(Buffer::new().format_exp(f16::MAX), "6.55e4");
+    }
+
+    /// Every finite `f16` is printed with digits that parse back to it, in every notation.
+    #[test]
+    fn roundtrip() {
+        for bits in 0 ..= u16::MAX {
+            let num = f16::from_bits(bits);
+            if !num.is_finite() {
+                continue
+            }
+            let strs = [
+                Buffer::new().format(num).to_owned(),
+                Buffer::new().format_exp(num).to_owned(),
+                Buffer::new().format_dec(num).to_owned(),
+            ];
+            for str in strs {
+                assert_eq!(str.parse::<f16>().unwrap().to_bits(), bits, "{str}");
+            }
+        }
+    }
+
+    /// No representation with fewer significant digits parses back to the same `f16`.
+    #[test]
+    fn shortest() {
+        for bits in 1 .. f16::INFINITY.to_bits() {
+            let num = f16::from_bits(bits);
+            let decimal = unsafe { num.to_decimal_finite() };
+            let len = crate::teju::fmt::len_u64(decimal.mant);
+            for digits in 1 .. len {
+                let pow10 = 10u64.pow((len - digits) as u32);
+                let exp = decimal.exp + (len - digits) as i32;
+                for mant in [decimal.mant / pow10, decimal.mant / pow10 + 1] {
+                    let parsed = format!("{mant}e{exp}").parse::<f16>().unwrap();
+                    assert_ne!(parsed.to_bits(), bits, "{mant}e{exp} for {}", Buffer::new().format(num));
+                }
+            }
+        }
+    }
+}
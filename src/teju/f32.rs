@@ -6,6 +6,9 @@ super::mk_impl::mk_impl! {
     len_mantissa = crate::teju::fmt::len_u64,
     print_mantissa = crate::teju::fmt::print_u64_mantissa,
     print_mantissa_known_len = crate::teju::fmt::print_u64_mantissa_known_len,
+    print_mantissa_with_point = crate::teju::fmt::print_u64_mantissa_with_point,
+    ryu_general_bounds = (-6, 13),
+    limits = crate::teju::format::F32,
     tests = {
         pi = {
             dec = "3.1415927",
@@ -23,16 +26,19 @@ super::mk_impl::mk_impl! {
             decimal = Decimal{ exp: -7, mant: 0_6931472 },
         },
         min_subnormal = {
+            general = "1e-45",
             dec = "0.000000000000000000000000000000000000000000001",
             exp = "1e-45",
             decimal = Decimal{ exp: -45, mant: 1 },
         },
         min_normal = {
+            general = "1.1754944e-38",
             dec = "0.000000000000000000000000000000000000011754944",
             exp = "1.1754944e-38",
             decimal = Decimal{ exp: -38-7, mant: 1_1754944 },
         },
         max = {
+            general = "3.4028235e38",
             dec = "340282350000000000000000000000000000000.0",
             exp = "3.4028235e38",
             decimal = Decimal{ exp: 38-7, mant: 3_4028235 },
@@ -6,6 +6,9 @@ super::mk_impl::mk_impl! {
     len_mantissa = crate::teju::fmt::len_u64,
     print_mantissa = crate::teju::fmt::print_u64_mantissa,
     print_mantissa_known_len = crate::teju::fmt::print_u64_mantissa_known_len,
+    print_mantissa_with_point = crate::teju::fmt::print_u64_mantissa_with_point,
+    ryu_general_bounds = (-5, 16),
+    limits = crate::teju::format::F64,
     tests = {
         pi = {
             dec = "3.141592653589793",
@@ -23,16 +26,19 @@ super::mk_impl::mk_impl! {
             decimal = Decimal{ exp: -16, mant: 0_6931471805599453 },
         },
         min_subnormal = {
+            general = "5e-324",
             dec = "0.000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005",
             exp = "5e-324",
             decimal = Decimal{ exp: -324, mant: 5 },
         },
         min_normal = {
+            general = "2.2250738585072014e-308",
             dec = "0.000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000022250738585072014",
             exp = "2.2250738585072014e-308",
             decimal = Decimal{ exp: -308-16, mant: 22250738585072014 },
         },
         max = {
+            general = "1.7976931348623157e308",
             dec = "179769313486231570000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000.0",
             exp = "1.7976931348623157e308",
             decimal = Decimal{ exp: 308-16, mant: 17976931348623157 },
@@ -0,0 +1,326 @@
+//! Fixed-precision decimal notation, shared between all float types.
+//!
+//! These routines work on the shortest decimal representation of a float (as given by
+//! [super::float::Sealed::to_decimal_finite]), so rounding is performed on the shortest digits
+//! that round-trip, not on the exact binary value, except that [SignedDecimal::round_exact]
+//! decides ties on the exact value.
+
+use core::cmp::Ordering;
+
+use super::common::SignedDecimal;
+use super::fmt;
+
+impl SignedDecimal {
+    /// Round to `precision` digits after the decimal point, with ties rounding to even. The result
+    /// has `exp >= -precision`. Invariant: `precision <= format::MAX_PRECISION`.
+    #[inline]
+    pub const fn round(self, precision: usize) -> Self {
+        self.round_exact(precision, false, None)
+    }
+
+    /// As [Self::round], but with ties rounding away from zero if `ties_away`, and, if `exact` is
+    /// the exact value `(mant, exp)` of the float `self` represents, i.e. `mant * 2^exp` as given
+    /// by [super::float::Sealed::to_binary_finite_nonzero], with ties decided on it: dropped digits
+    /// `5000...` only round as a tie if `self` is exactly that value, and otherwise round towards
+    /// it. `exact` must be `None` for zero.
+    ///
+    /// If any digits are dropped, this is the exact value correctly rounded to `precision` digits,
+    /// as by IEEE 754's conversions to decimal with roundTiesToEven and roundTiesToAway: `self` is
+    /// the closest of the shortest decimals in the rounding interval, so no other halfway point
+    /// lies between `self` and the exact value.
+    #[inline]
+    pub const fn round_exact(self, precision: usize, ties_away: bool, exact: Option<(u64, i32)>) -> Self {
+        let min_exp = -(precision as i32);
+        if self.exp >= min_exp {
+            return self
+        }
+
+        // Number of digits to drop.
+        let k = (min_exp - self.exp) as usize;
+        let mant = if k > fmt::len_u64(self.mant) {
+            // Less than `0.1 * 10^-precision`, rounds to zero.
+            0
+        } else {
+            let pow10 = 10u64.pow(k as u32);
+            let q = self.mant / pow10;
+            let r = self.mant % pow10;
+            let half = pow10 / 2;
+            let round_up = if r != half {
+                r > half
+            } else {
+                match self.cmp_exact(exact) {
+                    Ordering::Less => false,
+                    Ordering::Greater => true,
+                    Ordering::Equal => ties_away || q % 2 == 1,
+                }
+            };
+            q + round_up as u64
+        };
+        SignedDecimal { sign: self.sign, mant, exp: min_exp }
+    }
+
+    /// How the magnitude of the exact value `exact` (see [Self::round_exact]) compares with that of
+    /// `self`, or [Ordering::Equal] if it is `None`. Invariant: `-self.exp <= format::MAX_PRECISION
+    /// + 20`, and `self` is within a factor of 2 of `exact`.
+    const fn cmp_exact(self, exact: Option<(u64, i32)>) -> Ordering {
+        let Some((mant, exp)) = exact else {
+            return Ordering::Equal
+        };
+        // `mant * 2^exp` vs `self.mant * 10^self.exp`, i.e., multiplying both by `10^q`,
+        // `mant * 5^q * 2^(exp + q)` vs `self.mant`, with the power of 2 moved to the side where
+        // it is positive.
+        let q = -self.exp;
+        let mut lhs = Big::new(mant);
+        let mut rhs = Big::new(self.mant);
+        if q >= 0 {
+            lhs.mul_pow(5, q as u32);
+        } else {
+            rhs.mul_pow(5, -q as u32);
+        }
+        if exp + q >= 0 {
+            lhs.mul_pow(2, (exp + q) as u32);
+        } else {
+            rhs.mul_pow(2, -(exp + q) as u32);
+        }
+        lhs.cmp(&rhs)
+    }
+
+    /// Round a nonzero decimal to at most `digits` significant digits, with ties rounding to even,
+    /// and remove trailing zeros from the mantissa, e.g. `1250e0` to `12e2` with 2 digits.
+    /// Invariant: `digits >= 1`.
+    #[inline]
+    pub const fn round_sig(self, digits: usize) -> Self {
+        let SignedDecimal { sign, mut mant, mut exp } = self;
+        let len = fmt::len_u64(mant);
+        if len > digits {
+            let drop = (len - digits) as u32;
+            let pow10 = 10u64.pow(drop);
+            let (q, r) = (mant / pow10, mant % pow10);
+            let half = pow10 / 2;
+            mant = q + (r > half || (r == half && q % 2 == 1)) as u64;
+            exp += drop as i32;
+        }
+        SignedDecimal { sign, mant, exp }.trim_trailing_zeros()
+    }
+
+    /// Truncate to `precision` digits after the decimal point, i.e. round towards zero. The result
+    /// has `exp >= -precision`. Invariant: `precision <= format::MAX_PRECISION`.
+    #[inline]
+    pub const fn truncate(self, precision: usize) -> Self {
+        let min_exp = -(precision as i32);
+        if self.exp >= min_exp {
+            return self
+        }
+
+        // Number of digits to drop.
+        let k = (min_exp - self.exp) as usize;
+        let mant = if k >= fmt::len_u64(self.mant) { 0 } else { self.mant / 10u64.pow(k as u32) };
+        SignedDecimal { sign: self.sign, mant, exp: min_exp }
+    }
+
+    /// Remove trailing zeros from the mantissa, e.g. `15e1` for `1500e-1`. Zero becomes `0e0`.
+    #[inline]
+    pub const fn trim_trailing_zeros(self) -> Self {
+        let SignedDecimal { sign, mut mant, mut exp } = self;
+        if mant == 0 {
+            return SignedDecimal { sign, mant, exp: 0 }
+        }
+        while mant % 10 == 0 {
+            mant /= 10;
+            exp += 1;
+        }
+        SignedDecimal { sign, mant, exp }
+    }
+
+    /// Print in decimal notation with exactly `precision` digits after the decimal point, and no
+    /// decimal point if `precision` is `0`. Returns the number of bytes written.
+    ///
+    /// Invariant: `self.exp >= -precision`, i.e. `self` has been [rounded](Self::round) to
+    /// `precision`.
+    #[inline]
+    pub unsafe fn format_fixed(self, mut buf: *mut u8, precision: usize) -> usize {
+        debug_assert!(self.exp >= -(precision as i32));
+        let buf_orig = buf;
+        unsafe {
+            buf.write(b'-');
+            buf = buf.add(!self.sign as usize);
+
+            let mant_len = fmt::len_u64(self.mant);
+            let decimal_exp = mant_len as i32 + self.exp;
+            let len = if self.exp >= 0 {
+                // `1234e2 -> 123400.00`
+                fmt::print_u64_mantissa_known_len(self.mant, buf, mant_len);
+                let int_len = decimal_exp as usize;
+                core::ptr::write_bytes(buf.add(mant_len), b'0', self.exp as usize);
+                if precision == 0 {
+                    int_len
+                } else {
+                    *buf.add(int_len) = b'.';
+                    core::ptr::write_bytes(buf.add(int_len + 1), b'0', precision);
+                    int_len + 1 + precision
+                }
+            } else if decimal_exp > 0 {
+                // `1234e-1 -> 123.400`
+                fmt::print_u64_mantissa_known_len(self.mant, buf, mant_len);
+                let int_len = decimal_exp as usize;
+                let frac_len = -self.exp as usize;
+                core::ptr::copy(buf.add(int_len), buf.add(int_len + 1), frac_len);
+                *buf.add(int_len) = b'.';
+                core::ptr::write_bytes(buf.add(int_len + 1 + frac_len), b'0', precision - frac_len);
+                int_len + 1 + precision
+            } else {
+                // `1234e-6 -> 0.00123400`
+                let zeros = -decimal_exp as usize;
+                *buf = b'0';
+                *buf.add(1) = b'.';
+                core::ptr::write_bytes(buf.add(2), b'0', zeros);
+                fmt::print_u64_mantissa_known_len(self.mant, buf.add(2 + zeros), mant_len);
+                let frac_len = -self.exp as usize;
+                core::ptr::write_bytes(buf.add(2 + frac_len), b'0', precision - frac_len);
+                2 + precision
+            };
+            buf.add(len).offset_from(buf_orig) as usize
+        }
+    }
+}
+
+/// An unsigned integer of up to 896 bits, as base-`2^32` limbs from least to most significant.
+/// This fits both sides of [SignedDecimal::cmp_exact], the larger of which is at most about
+/// `2^64 * 5^(format::MAX_PRECISION + 20)`.
+struct Big([u32; 28]);
+
+impl Big {
+    const fn new(x: u64) -> Self {
+        let mut limbs = [0; 28];
+        limbs[0] = x as u32;
+        limbs[1] = (x >> 32) as u32;
+        Big(limbs)
+    }
+
+    /// Multiply by `m`. The result must fit.
+    const fn mul_small(&mut self, m: u32) {
+        let mut carry = 0;
+        let mut i = 0;
+        while i < self.0.len() {
+            let x = self.0[i] as u64 * m as u64 + carry;
+            self.0[i] = x as u32;
+            carry = x >> 32;
+            i += 1;
+        }
+        debug_assert!(carry == 0);
+    }
+
+    /// Multiply by `base^n`, for `base` 2 or 5, in chunks of the largest power of `base` which
+    /// fits in a `u32`.
+    const fn mul_pow(&mut self, base: u32, mut n: u32) {
+        let chunk = if base == 2 { 31 } else { 13 };
+        while n >= chunk {
+            self.mul_small(base.pow(chunk));
+            n -= chunk;
+        }
+        self.mul_small(base.pow(n));
+    }
+
+    const fn cmp(&self, other: &Self) -> Ordering {
+        let mut i = self.0.len();
+        while i > 0 {
+            i -= 1;
+            if self.0[i] != other.0[i] {
+                return if self.0[i] < other.0[i] { Ordering::Less } else { Ordering::Greater }
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teju::float::Sealed;
+
+    fn dec(mant: u64, exp: i32) -> SignedDecimal {
+        SignedDecimal { sign: true, mant, exp }
+    }
+
+    #[test]
+    fn round() {
+        assert_eq!(dec(1234, -3).round(2), dec(123, -2));
+        assert_eq!(dec(1235, -3).round(2), dec(124, -2));
+        assert_eq!(dec(1245, -3).round(2), dec(124, -2));
+        assert_eq!(dec(12451, -4).round(2), dec(125, -2));
+        assert_eq!(dec(9995, -3).round(2), dec(1000, -2));
+        assert_eq!(dec(5, -1).round(0), dec(0, 0));
+        assert_eq!(dec(15, -1).round(0), dec(2, 0));
+        assert_eq!(dec(5, -3).round(1), dec(0, -1));
+        assert_eq!(dec(5, -300).round(2), dec(0, -2));
+        assert_eq!(dec(99999999999999999, -17).round(0), dec(1, 0));
+        assert_eq!(dec(1234, 5).round(2), dec(1234, 5));
+    }
+
+    #[test]
+    fn round_half_away() {
+        let round_half_away = |dec: SignedDecimal, precision| dec.round_exact(precision, true, None);
+        assert_eq!(round_half_away(dec(1234, -3), 2), dec(123, -2));
+        assert_eq!(round_half_away(dec(1235, -3), 2), dec(124, -2));
+        assert_eq!(round_half_away(dec(1245, -3), 2), dec(125, -2));
+        assert_eq!(round_half_away(dec(12449, -4), 2), dec(124, -2));
+        assert_eq!(round_half_away(dec(9995, -3), 2), dec(1000, -2));
+        assert_eq!(round_half_away(dec(5, -1), 0), dec(1, 0));
+        assert_eq!(round_half_away(dec(25, -1), 0), dec(3, 0));
+        assert_eq!(round_half_away(dec(5, -3), 1), dec(0, -1));
+        assert_eq!(round_half_away(dec(1234, 5), 2), dec(1234, 5));
+    }
+
+    #[test]
+    fn round_exact() {
+        let binary = |num: f64| Some(unsafe { num.to_binary_finite_nonzero() });
+        // Exact ties.
+        assert_eq!(dec(125, -3).round_exact(2, false, binary(0.125)), dec(12, -2));
+        assert_eq!(dec(125, -3).round_exact(2, true, binary(0.125)), dec(13, -2));
+        assert_eq!(dec(25, -1).round_exact(0, false, binary(2.5)), dec(2, 0));
+        // 2.675 is 2.67499999..., 2.665 is 2.66500000000000003552...
+        assert_eq!(dec(2675, -3).round_exact(2, false, binary(2.675)), dec(267, -2));
+        assert_eq!(dec(2675, -3).round_exact(2, true, binary(2.675)), dec(267, -2));
+        assert_eq!(dec(2665, -3).round_exact(2, false, binary(2.665)), dec(267, -2));
+        assert_eq!(dec(1005, -3).round_exact(2, true, binary(1.005)), dec(100, -2));
+        // Not ties.
+        assert_eq!(dec(2676, -3).round_exact(2, false, binary(2.676)), dec(268, -2));
+        assert_eq!(dec(2674, -3).round_exact(2, true, binary(2.674)), dec(267, -2));
+        // 5e-324 is 4.94065...e-324.
+        assert_eq!(dec(5, -324).round_exact(323, true, binary(5e-324)), dec(0, -323));
+        assert_eq!(dec(5, -1).round_exact(0, true, binary(0.5)), dec(1, 0));
+        // `f32`s.
+        let binary = |num: f32| Some(unsafe { num.to_binary_finite_nonzero() });
+        assert_eq!(dec(15, -2).round_exact(1, false, binary(0.15)), dec(2, -1));
+        assert_eq!(dec(35, -2).round_exact(1, false, binary(0.35)), dec(3, -1));
+    }
+
+    #[test]
+    fn round_sig() {
+        assert_eq!(dec(1234, -3).round_sig(2), dec(12, -1));
+        assert_eq!(dec(1250, 0).round_sig(2), dec(12, 2));
+        assert_eq!(dec(1350, 0).round_sig(2), dec(14, 2));
+        assert_eq!(dec(995, -3).round_sig(2), dec(1, 0));
+        assert_eq!(dec(1200, 0).round_sig(4), dec(12, 2));
+        assert_eq!(dec(99999999999999999, -17).round_sig(16), dec(1, 0));
+        assert_eq!(dec(5, -324).round_sig(1), dec(5, -324));
+    }
+
+    #[test]
+    fn truncate() {
+        assert_eq!(dec(1239, -3).truncate(2), dec(123, -2));
+        assert_eq!(dec(9999, -3).truncate(2), dec(999, -2));
+        assert_eq!(dec(9, -1).truncate(0), dec(0, 0));
+        assert_eq!(dec(99999999999999999, -17).truncate(0), dec(0, 0));
+        assert_eq!(dec(5, -300).truncate(2), dec(0, -2));
+        assert_eq!(dec(1234, 5).truncate(2), dec(1234, 5));
+    }
+
+    #[test]
+    fn trim_trailing_zeros() {
+        assert_eq!(dec(1500, -3).trim_trailing_zeros(), dec(15, -1));
+        assert_eq!(dec(1500, 0).trim_trailing_zeros(), dec(15, 2));
+        assert_eq!(dec(1000, -2).trim_trailing_zeros(), dec(1, 1));
+        assert_eq!(dec(0, -4).trim_trailing_zeros(), dec(0, 0));
+    }
+}
@@ -4,6 +4,16 @@
 pub trait Float: Sealed {}
 impl Float for f64 {}
 impl Float for f32 {}
+#[cfg(feature = "half")]
+impl Float for half::f16 {}
+#[cfg(feature = "half")]
+impl Float for half::bf16 {}
+
+/// The bits of `num` with the sign bit cleared, which compare as the magnitudes of non-NaN floats.
+pub(crate) fn abs_bits<F: Float>(num: F) -> u64 {
+    let sign_bit = 1 << (8 * core::mem::size_of::<F>() - 1);
+    num.to_bits_u64() & !sign_bit
+}
 
 #[derive(Debug)]
 pub enum FloatType {
@@ -22,12 +32,54 @@ pub enum FiniteFloatType {
 
 pub trait Sealed
 where
-    Self: core::panic::RefUnwindSafe + Send + Sync + Unpin + core::panic::UnwindSafe 
+    Self: Copy + core::panic::RefUnwindSafe + Send + Sync + Unpin + core::panic::UnwindSafe 
 {
+    /// Name of the primitive type, e.g. `"f64"`, printed as a literal suffix. Wrappers use that of
+    /// the type they wrap, which also keeps it short enough for the buffers.
+    const NAME: &'static str;
+    /// Bounds on the shortest representations of values of this type.
+    const LIMITS: super::format::Limits;
+    /// The bit representation of `2^(MANTISSA_DIGITS + 1)`, above which not every integer is
+    /// representable, zero-extended to 64 bits.
+    const EXACT_INT_LIMIT_BITS: u64;
+    /// The quiet bit of NaNs, i.e. the most significant stored bit of the mantissa.
+    const QUIET_NAN_BIT: u64;
+
+    /// Storage of buffers whose size depends on the float type, sized from [Self::LIMITS].
+    type BufferExp: super::format::Bytes;
+    type BufferDec: super::format::Bytes;
+    type BufferLocale: super::format::Bytes;
+    type BufferFixed: super::format::Bytes;
+
     fn classify(&self) -> FloatType;
+    fn is_sign_negative(&self) -> bool;
+    /// The bit representation, zero-extended to 64 bits.
+    fn to_bits_u64(&self) -> u64;
     fn classify_finite(&self) -> FiniteFloatType;
+    /// As [Self::classify], but only inspecting the bit representation with integer operations.
+    fn classify_bits(&self) -> FloatType;
+    /// As [Self::classify_finite], but only inspecting the bit representation with integer
+    /// operations.
+    fn classify_finite_bits(&self) -> FiniteFloatType;
+
+    unsafe fn to_decimal_finite(self) -> super::common::SignedDecimal;
+    /// Formats `decimal` as [Self::format_general_finite_nonzero] would format the number it
+    /// represents. Invariant: `decimal` is nonzero and its mantissa has at most as many digits as
+    /// those produced by [Self::to_decimal_finite].
+    unsafe fn format_general_decimal(decimal: super::common::SignedDecimal, buf: *mut u8) -> usize;
+    /// Formats `decimal` as [Self::format_exp_finite_nonzero] would format the number it
+    /// represents, with the same invariant as [Self::format_general_decimal].
+    unsafe fn format_exp_decimal(decimal: super::common::SignedDecimal, buf: *mut u8) -> usize;
 
     unsafe fn format_general_finite_nonzero(self, buf: *mut u8) -> usize;
     unsafe fn format_exp_finite_nonzero(self, buf: *mut u8) -> usize;
     unsafe fn format_dec_finite_nonzero(self, buf: *mut u8) -> usize;
+
+    unsafe fn format_ryu_finite_nonzero(self, buf: *mut u8) -> usize;
+    unsafe fn format_general_bounded_finite_nonzero(self, buf: *mut u8, low: i32, high: i32) -> usize;
+    unsafe fn format_exp_min_sig_finite(self, buf: *mut u8, min_sig: usize) -> usize;
+    /// The magnitude as `mant * 2^exp`, exactly, with `mant` odd.
+    unsafe fn to_binary_finite_nonzero(self) -> (u64, i32);
+    /// Formats as `mant * 2^exp`, e.g. `3p-1`, with `mant` odd.
+    unsafe fn format_bin_exp_finite_nonzero(self, buf: *mut u8) -> usize;
 }
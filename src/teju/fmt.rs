@@ -64,8 +64,25 @@ pub const fn len_u64(x: u64) -> usize {
     }
 }
 
+/// Bytes written by [print_u64_mantissa] and [print_u64_mantissa_known_len], regardless of the
+/// mantissa's length: the digits are built right-aligned in this many bytes, then copied whole.
+pub const PRINT_U64_LEN: usize = 20;
+
+/// Bytes written by [print_u64_mantissa_with_point] for each side of the point, regardless of the
+/// mantissa's length.
+const PRINT_U64_HALF_LEN: usize = 16;
+
+/// Bytes written by [print_u64_mantissa_with_point] with `int_len` digits before the point,
+/// regardless of the mantissa's length.
+pub const fn print_u64_with_point_len(int_len: usize) -> usize {
+    let int = if int_len < PRINT_U64_HALF_LEN { PRINT_U64_HALF_LEN } else { int_len };
+    let frac = int_len + 1 + PRINT_U64_HALF_LEN;
+    if int > frac { int } else { frac }
+}
+
 /// Print an `u64`, returning the number of bytes written. Invariant: `x` has at most 17 digits.
-/// May clobber / write junk to `buf` after the bytes written (up to 20 bytes in total).
+/// May clobber / write junk to `buf` after the bytes written (up to [PRINT_U64_LEN] bytes in
+/// total).
 ///
 /// Performance should be better than `len_u64` + `print_u64_mantissa_known_len` if `len_u64` is
 /// not well predicted, and worse if not (but microbenchmarks don't show this!).
@@ -79,7 +96,7 @@ pub const unsafe fn print_u64_mantissa(x: u64, buf: *mut u8) -> usize {
             return 1
         }
 
-        let mut digits = [MaybeUninit::<u8>::uninit(); 20];
+        let mut digits = [MaybeUninit::<u8>::uninit(); PRINT_U64_LEN];
         let digits_ptr = digits.as_mut_ptr() as *mut u8;
         let top12 = x / 100000000u64;
         let top4 = x / 10000000000000000u64;
@@ -118,28 +135,16 @@ pub const unsafe fn print_u64_mantissa(x: u64, buf: *mut u8) -> usize {
         let offset = (neg_log2 * 1233) >> 12; // 1233 / 2**12 ≈ log10(2)
         let offset = offset + (*digits_ptr.add(offset) == b'0') as usize;
 
-        core::ptr::copy_nonoverlapping(digits_ptr.add(offset), buf, 20);
-        20 - offset
+        core::ptr::copy_nonoverlapping(digits_ptr.add(offset), buf, PRINT_U64_LEN);
+        PRINT_U64_LEN - offset
     }
 }
 
-/// Print an `u64` with `len` digits, returning the number of bytes written. Invariant: `x` has at
-/// most 17 digits. May clobber / write junk to `buf` after the bytes written (up to 20 bytes in
-/// total).
+/// Write the 17 digits of `x` (with leading zeros) to `digits[3..20]`. Invariant: `x` has at most
+/// 17 digits.
 #[inline]
-#[allow(unused)]
-pub const unsafe fn print_u64_mantissa_known_len(x: u64, buf: *mut u8, len: usize) -> usize {
-    debug_assert!(x < 10u64.pow(17));
-    debug_assert!(len <= 17);
+const unsafe fn write_digits_u64(x: u64, digits_ptr: *mut u8) {
     unsafe {
-        if x == 0 {
-            *buf = b'0';
-            return 1
-        }
-
-        // TODO build directly in buf? But then we'd have to branch
-        let mut digits = [MaybeUninit::<u8>::uninit(); 40];
-        let digits_ptr = digits.as_mut_ptr() as *mut u8;
         let top12 = x / 100000000u64;
         let top4 = x / 10000000000000000u64;
 
@@ -172,18 +177,75 @@ pub const unsafe fn print_u64_mantissa_known_len(x: u64, buf: *mut u8, len: usiz
         write_lut_u64(digits_ptr,  2, uvalue_8, uvalue_9);
         write_lut_u64(digits_ptr,  0, uvalue_9, 0);*/
         *digits_ptr.add(3) = b'0' + uvalue_8 as u8;
+    }
+}
+
+/// Print an `u64` with `len` digits, returning the number of bytes written. Invariant: `x` has at
+/// most 17 digits. May clobber / write junk to `buf` after the bytes written (up to
+/// [PRINT_U64_LEN] bytes in total).
+#[inline]
+#[allow(unused)]
+pub const unsafe fn print_u64_mantissa_known_len(x: u64, buf: *mut u8, len: usize) -> usize {
+    debug_assert!(x < 10u64.pow(17));
+    debug_assert!(len <= 17);
+    unsafe {
+        if x == 0 {
+            *buf = b'0';
+            return 1
+        }
+
+        // TODO build directly in buf? But then we'd have to branch
+        let mut digits = [MaybeUninit::<u8>::uninit(); 40];
+        let digits_ptr = digits.as_mut_ptr() as *mut u8;
+        write_digits_u64(x, digits_ptr);
 
-        let offset = 20 - len;
+        let offset = PRINT_U64_LEN - len;
 
-        core::ptr::copy_nonoverlapping(digits_ptr.add(offset), buf, 20);
+        core::ptr::copy_nonoverlapping(digits_ptr.add(offset), buf, PRINT_U64_LEN);
         len
     }
 }
 
+/// Print an `u64` with `len` digits, with a decimal point after the first `int_len` digits,
+/// returning the number of bytes written (`len + 1`). Invariant: `x` has at most 17 digits,
+/// `0 < int_len < len`, and `len - int_len <= 16`. May clobber / write junk to `buf` after the
+/// bytes written (up to [print_u64_with_point_len] bytes in total, so at most 33).
+///
+/// Equivalent to [print_u64_mantissa_known_len] followed by shifting the digits after the point,
+/// but with fixed-size copies only.
+#[inline]
+pub const unsafe fn print_u64_mantissa_with_point(x: u64, buf: *mut u8, len: usize, int_len: usize) -> usize {
+    debug_assert!(x < 10u64.pow(17));
+    debug_assert!(len <= 17);
+    debug_assert!(0 < int_len && int_len < len && len - int_len <= 16);
+    unsafe {
+        let mut digits = [MaybeUninit::<u8>::uninit(); 40];
+        let digits_ptr = digits.as_mut_ptr() as *mut u8;
+        write_digits_u64(x, digits_ptr);
+
+        let offset = PRINT_U64_LEN - len;
+
+        // Integer part (at most 16 digits), point, then fractional part (at most 16 digits).
+        core::ptr::copy_nonoverlapping(digits_ptr.add(offset), buf, PRINT_U64_HALF_LEN);
+        *buf.add(int_len) = b'.';
+        core::ptr::copy_nonoverlapping(digits_ptr.add(offset + int_len), buf.add(int_len + 1), PRINT_U64_HALF_LEN);
+        len + 1
+    }
+}
+
+/// Print an exponent `x` into `buf`, with a `-` if negative, and return the number of bytes
+/// written. Zero is always printed as `0`, never `-0`.
 #[inline]
 pub const unsafe fn print_i32_exp(x: i32, buf: *mut u8) -> usize {
-    // Invariant: never more than 4 digits
-    debug_assert!(-999 <= x && x <= 999);
+    unsafe { print_exp::<3>(x, buf) }
+}
+
+/// As [print_i32_exp], for exponents of up to `MAX_DIGITS` digits, which must be at most 5 (enough
+/// for e.g. `f128`, whose exponents are within ±4966). Invariant: `|x| < 10^MAX_DIGITS`.
+#[inline]
+pub const unsafe fn print_exp<const MAX_DIGITS: u32>(x: i32, buf: *mut u8) -> usize {
+    debug_assert!(MAX_DIGITS <= 5);
+    debug_assert!(x.unsigned_abs() < 10u32.pow(MAX_DIGITS));
 
     unsafe {
         let sign = x >= 0;
@@ -192,7 +254,23 @@ pub const unsafe fn print_i32_exp(x: i32, buf: *mut u8) -> usize {
         *buf = b'-';
         let buf = buf.add(!sign as usize);
 
-        if x_abs >= 100 {
+        if MAX_DIGITS > 3 && x_abs >= 1000 {
+            // Print the leading 2 or 3 digits, then the last 2.
+            let hi = x_abs / 100;
+            let lo = DIGITS_LUT.as_ptr().add(x_abs as usize % 100 * 2);
+            let hi_len = if hi >= 100 {
+                *buf = b'0' + (hi / 100) as u8;
+                let d = DIGITS_LUT.as_ptr().add(hi as usize % 100 * 2);
+                core::ptr::copy_nonoverlapping(d, buf.offset(1), 2);
+                3
+            } else {
+                let d = DIGITS_LUT.as_ptr().add(hi as usize * 2);
+                core::ptr::copy_nonoverlapping(d, buf, 2);
+                2
+            };
+            core::ptr::copy_nonoverlapping(lo, buf.add(hi_len), 2);
+            !sign as usize + hi_len + 2
+        } else if x_abs >= 100 {
             *buf = b'0' + (x_abs / 100) as u8;
             let d = DIGITS_LUT.as_ptr().add(x_abs as usize % 100 * 2);
             core::ptr::copy_nonoverlapping(d, buf.offset(1), 2);
@@ -208,11 +286,149 @@ pub const unsafe fn print_i32_exp(x: i32, buf: *mut u8) -> usize {
     }
 }
 
+/// Number of digits of `x`.
+pub const fn len_u128(x: u128) -> usize {
+    if x == 0 {
+        1
+    } else {
+        x.ilog10() as usize + 1
+    }
+}
+
+/// Print the last `len` digits of `x` into `buf[..len]`, padded with leading zeros. Invariant:
+/// `len` is even.
+#[inline]
+const unsafe fn print_u64_digit_pairs(mut x: u64, buf: *mut u8, mut len: usize) {
+    debug_assert!(len.is_multiple_of(2));
+    unsafe {
+        while len > 0 {
+            let hi = x / 100;
+            len -= 2;
+            write_lut_u64(buf, len, x, hi);
+            x = hi;
+        }
+    }
+}
+
+/// Print an `u128`, returning the number of bytes written (at most 39). Unlike the mantissa
+/// printers, does not write past the bytes written.
+#[inline]
+pub const unsafe fn print_u128(x: u128, buf: *mut u8) -> usize {
+    const CHUNK: u128 = 10u128.pow(16);
+    let len = len_u128(x);
+    unsafe {
+        // Peel off chunks of 16 digits, which fit in an `u64`, so that only a few divisions are on
+        // `u128`s.
+        let mut x = x;
+        let mut end = len;
+        while x >= CHUNK {
+            end -= 16;
+            print_u64_digit_pairs((x % CHUNK) as u64, buf.add(end), 16);
+            x /= CHUNK;
+        }
+
+        // Remaining 1 to 16 digits; if odd, the first is written on its own.
+        let x = x as u64;
+        if end % 2 == 1 {
+            *buf = b'0' + (x / 10u64.pow(end as u32 - 1)) as u8;
+            print_u64_digit_pairs(x, buf.add(1), end - 1);
+        } else {
+            print_u64_digit_pairs(x, buf, end);
+        }
+        len
+    }
+}
+
+/// Copy `str` to `buf`, returning the number of bytes written.
+#[inline]
+pub const unsafe fn write_str(str: &str, buf: *mut u8) -> usize {
+    unsafe { core::ptr::copy_nonoverlapping(str.as_ptr(), buf, str.len()) };
+    str.len()
+}
+
+/// Whether a group separator goes before the last `remaining` digits of an integer part, for
+/// groups of the sizes in `grouping`, counting from the point, with the last size repeating.
+/// Invariant: every size in `grouping` is nonzero.
+#[inline]
+const fn is_group_boundary(grouping: &[u8], remaining: usize) -> bool {
+    let [init @ .., last] = grouping else {
+        return false
+    };
+    if remaining == 0 {
+        return false
+    }
+    let mut acc = 0;
+    let mut i = 0;
+    while i < init.len() {
+        acc += init[i] as usize;
+        if remaining <= acc {
+            return remaining == acc
+        }
+        i += 1;
+    }
+    (remaining - acc).is_multiple_of(*last as usize)
+}
+
+/// Copy the decimal string in `src[..len]` (an optional `-`, the integer digits, a `.`, and the
+/// fractional digits) to `dst`, replacing the `.` by `decimal_sep` and inserting a `group_sep`
+/// between groups of digits of the integer part, whose sizes are given by `grouping`, counting
+/// from the point, with the last size repeating. If `grouping` is empty, no group separators are
+/// inserted. Returns the number of bytes written. Invariant: every size in `grouping` is nonzero.
+///
+/// `dst` may overlap `src` as long as it starts before it by at least the number of separators
+/// inserted.
+pub unsafe fn localise(
+    src: *const u8,
+    len: usize,
+    dst: *mut u8,
+    decimal_sep: u8,
+    group_sep: u8,
+    grouping: &[u8],
+) -> usize {
+    unsafe {
+        let mut i = 0;
+        let mut j = 0;
+        if *src == b'-' {
+            *dst = b'-';
+            i += 1;
+            j += 1;
+        }
+
+        let mut point = i;
+        while *src.add(point) != b'.' {
+            point += 1;
+        }
+
+        // Integer part: a separator goes after each digit which is followed by a group boundary.
+        while i < point {
+            *dst.add(j) = *src.add(i);
+            i += 1;
+            j += 1;
+            if is_group_boundary(grouping, point - i) {
+                *dst.add(j) = group_sep;
+                j += 1;
+            }
+        }
+
+        // Decimal point and fractional part
+        *dst.add(j) = decimal_sep;
+        i += 1;
+        j += 1;
+        while i < len {
+            *dst.add(j) = *src.add(i);
+            i += 1;
+            j += 1;
+        }
+        j
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::zero_prefixed_literal)]
     fn test_u64_mantissa() {
         let mut buf = [0u8; 80];
 
@@ -239,6 +455,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::zero_prefixed_literal)]
     fn test_u64_mantissa_known_len() {
         let mut buf = [0u8; 80];
 
@@ -264,6 +481,23 @@ mod tests {
         assert_eq!(&buf[..n], b"99999999999999999");
     }
 
+    #[test]
+    fn test_u64_mantissa_with_point() {
+        let mut buf = [0u8; 80];
+
+        let n = unsafe { print_u64_mantissa_with_point(1234, buf.as_mut_ptr(), 4, 3) };
+        assert_eq!(&buf[..n], b"123.4");
+
+        let n = unsafe { print_u64_mantissa_with_point(10, buf.as_mut_ptr(), 2, 1) };
+        assert_eq!(&buf[..n], b"1.0");
+
+        let n = unsafe { print_u64_mantissa_with_point(99_999_999_999_999_999, buf.as_mut_ptr(), 17, 1) };
+        assert_eq!(&buf[..n], b"9.9999999999999999");
+
+        let n = unsafe { print_u64_mantissa_with_point(99_999_999_999_999_999, buf.as_mut_ptr(), 17, 16) };
+        assert_eq!(&buf[..n], b"9999999999999999.9");
+    }
+
     #[test]
     fn test_i32_exp() {
         let mut buf = [0u8; 80];
@@ -275,6 +509,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_exp() {
+        let mut buf = [0u8; 80];
+
+        // Every exponent of `f128`, including subnormals.
+        for x in -4966 ..= 4966 {
+            let len = unsafe { print_exp::<4>(x, buf.as_mut_ptr()) };
+            assert_eq!(&buf[..len], format!("{x}").as_bytes())
+        }
+        for x in -99999 ..= 99999 {
+            let len = unsafe { print_exp::<5>(x, buf.as_mut_ptr()) };
+            assert_eq!(&buf[..len], format!("{x}").as_bytes())
+        }
+    }
+
+    #[test]
+    fn test_localise() {
+        let mut buf = [0u8; 80];
+        let mut check = |src: &str, decimal_sep, group_sep, grouping: &[u8], expected: &str| {
+            buf[20..20 + src.len()].copy_from_slice(src.as_bytes());
+            let src_ptr = unsafe { buf.as_ptr().add(20) };
+            let len = unsafe {
+                localise(src_ptr, src.len(), buf.as_mut_ptr(), decimal_sep, group_sep, grouping)
+            };
+            assert_eq!(&buf[..len], expected.as_bytes());
+        };
+
+        check("1234567.89", b',', b'.', &[3], "1.234.567,89");
+        check("-1234567.89", b'.', b',', &[3], "-1,234,567.89");
+        check("123456.0", b'.', b',', &[3], "123,456.0");
+        check("12345.0", b'.', b' ', &[3], "12 345.0");
+        check("123.0", b'.', b',', &[3], "123.0");
+        check("0.001234", b',', b'.', &[3], "0,001234");
+        check("-1234.5", b'.', b'\'', &[2], "-12'34.5");
+        check("1234567.0", b'.', b',', &[], "1234567.0");
+        check("1234.0", b'.', b'_', &[1], "1_2_3_4.0");
+        check("1234567.0", b'.', b',', &[3, 2], "12,34,567.0");
+        check("-123456789.5", b'.', b',', &[3, 2], "-12,34,56,789.5");
+        check("1234.0", b'.', b',', &[3, 2], "1,234.0");
+        check("123.0", b'.', b',', &[3, 2], "123.0");
+        check("123456789.0", b'.', b',', &[1, 2, 3], "123,456,78,9.0");
+    }
+
+    #[test]
+    fn test_u128() {
+        let mut buf = [0u8; 80];
+        let mut check = |x: u128| {
+            let len = unsafe { print_u128(x, buf.as_mut_ptr()) };
+            assert_eq!(&buf[..len], format!("{x}").as_bytes());
+        };
+
+        for x in [0, 1, 9, 10, 99, 100, 61295, u64::MAX as u128, u64::MAX as u128 + 1, u128::MAX] {
+            check(x);
+        }
+        for i in 0 ..= 38 {
+            check(10u128.pow(i) - 1);
+            check(10u128.pow(i));
+        }
+    }
+
     use proptest::prelude::*;
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(200_000))]
@@ -295,5 +589,23 @@ mod tests {
             assert_eq!(len, std.len());
             assert_eq!(&buf[..len], std.as_bytes())
         }
+
+        #[test]
+        fn proptest_u64_mantissa_with_point(x in 10u64 .. 10u64.pow(17), int_len in 1usize .. 17) {
+            let mut buf = [0u8; 80];
+            let std = format!("{x}");
+            let int_len = int_len.min(std.len() - 1);
+            let len = unsafe { print_u64_mantissa_with_point(x, buf.as_mut_ptr(), std.len(), int_len) };
+            let expected = format!("{}.{}", &std[..int_len], &std[int_len..]);
+            assert_eq!(&buf[..len], expected.as_bytes())
+        }
+
+        #[test]
+        fn proptest_u128(x in any::<u128>(), shift in 0u32 .. 128) {
+            let x = x >> shift;
+            let mut buf = [0u8; 80];
+            let len = unsafe { print_u128(x, buf.as_mut_ptr()) };
+            assert_eq!(&buf[..len], format!("{x}").as_bytes())
+        }
     }
 }
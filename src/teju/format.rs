@@ -1,3 +1,7 @@
+use core::mem::MaybeUninit;
+use super::float::Float;
+use super::fmt;
+
 /// A format for serialising floats.
 ///
 /// This trait is "sealed", meaning it cannot be implemented for any other types.
@@ -5,65 +9,543 @@ pub trait Format: Sealed {}
 impl Format for General {}
 impl Format for Scientific {}
 impl Format for Decimal {}
+impl Format for Locale {}
+impl Format for Bounded {}
+impl Format for Fixed {}
+impl Format for Dynamic {}
+impl Format for Fraction {}
+impl Format for Interval {}
+impl Format for Both {}
+impl Format for Grouped {}
+#[cfg(feature = "num-complex")]
+impl Format for Complex {}
 
+/// Selects [Buffer::format](crate::Buffer::format) and related methods.
 pub struct General;
+/// Selects [Buffer::format_exp](crate::Buffer::format_exp) and related methods.
 pub struct Scientific;
+/// Selects [Buffer::format_dec](crate::Buffer::format_dec) and related methods.
 pub struct Decimal;
+/// Selects [Buffer::format_locale](crate::Buffer::format_locale).
+pub struct Locale;
+/// Selects [Buffer::format_with_thresholds](crate::Buffer::format_with_thresholds).
+pub struct Bounded;
+/// Selects [Buffer::format_round](crate::Buffer::format_round) and related methods.
+pub struct Fixed;
+/// Selects [Buffer::format_as](crate::Buffer::format_as).
+pub struct Dynamic;
+/// Selects [Buffer::format_fraction](crate::Buffer::format_fraction).
+pub struct Fraction;
+/// Selects [Buffer::format_interval](crate::Buffer::format_interval) and
+/// [Buffer::format_pm](crate::Buffer::format_pm).
+pub struct Interval;
+/// Selects [Buffer::format_both](crate::Buffer::format_both).
+pub struct Both;
+/// Selects [Buffer::format_exp_grouped](crate::Buffer::format_exp_grouped).
+pub struct Grouped;
+/// Selects `Buffer::format_complex`.
+#[cfg(feature = "num-complex")]
+pub struct Complex;
+
+pub trait Sealed {
+    /// Storage of a [Buffer](crate::Buffer) of this format, for floats of type `F`.
+    type Buffer<F: Float>: Bytes;
+    /// Name of the marker type, for [Debug](core::fmt::Debug) impls.
+    const NAME: &'static str;
+
+    fn new_buffer<F: Float>() -> Self::Buffer<F> {
+        Bytes::uninit()
+    }
+
+    fn buffer_as_ptr<F: Float>(buf: &mut Self::Buffer<F>) -> *mut u8 {
+        buf.as_byte_ptr()
+    }
+}
 
-pub trait Sealed
+/// Uninitialised bytes backing a [Buffer](crate::Buffer).
+pub trait Bytes
 where
-    Self::Buffer: core::panic::RefUnwindSafe + Send + Sync + Unpin + core::panic::UnwindSafe 
+    Self: Copy + core::panic::RefUnwindSafe + Send + Sync + Unpin + core::panic::UnwindSafe
 {
-    type Buffer;
-    fn new_buffer() -> Self::Buffer;
-    fn buffer_as_ptr(buf: &mut Self::Buffer) -> *mut u8;
+    /// Number of bytes.
+    const LEN: usize;
+    fn uninit() -> Self;
+    fn as_byte_ptr(&mut self) -> *mut u8;
+}
+
+impl<const N: usize> Bytes for [MaybeUninit<u8>; N] {
+    const LEN: usize = N;
+
+    fn uninit() -> Self {
+        [MaybeUninit::uninit(); N]
+    }
+
+    fn as_byte_ptr(&mut self) -> *mut u8 {
+        self.as_mut_ptr() as *mut u8
+    }
+}
+
+/// Bounds on the shortest decimal representations of the values of a float type, from which the
+/// sizes of its buffers are computed.
+#[derive(Clone, Copy)]
+pub struct Limits {
+    /// Significant digits in the shortest representation.
+    pub mant: usize,
+    /// Digits in the exponent, plus its sign.
+    pub exp: usize,
+    /// Digits in the integer part of the largest value.
+    pub max_int_digits: usize,
+    /// Zeros after the decimal point of the smallest value, before its first significant digit.
+    pub max_frac_zeros: usize,
+    /// Digits after the decimal point of the value less than one whose decimal representation is
+    /// the longest.
+    pub max_frac_digits: usize,
+    /// Digits in the integer part of `2^(MANTISSA_DIGITS + 1)`, above which not every integer is
+    /// representable.
+    pub exact_int_digits: usize,
+    /// Digits in the largest numerator printed by `format_fraction`, i.e. the largest odd mantissa.
+    pub frac_num_digits: usize,
+    /// Digits in the largest denominator printed by `format_fraction`, i.e. that of the smallest
+    /// value, or `2^63` if it doesn't fit in a `u64`.
+    pub frac_den_digits: usize,
 }
 
-/// Size of buffer necessary for serialising any `f64` in scientific notation.
-const LEN_EXP: usize = {
-    12 + 20
+/// `-1.7976931348623157e308`, `5e-324`, `2.2250738585072014e-308`, `2^54 = 18014398509481984`,
+/// `9007199254740991/9223372036854775808`.
+pub(crate) const F64: Limits = Limits {
+    mant: 17, exp: 1 + 3, max_int_digits: 309, max_frac_zeros: 323, max_frac_digits: 324,
+    exact_int_digits: 17, frac_num_digits: 16, frac_den_digits: 19,
+};
+/// `-3.4028235e38`, `1e-45`, `1.1754942e-38`, `2^25 = 33554432`, `16777215/9223372036854775808`.
+pub(crate) const F32: Limits = Limits {
+    mant: 9, exp: 1 + 2, max_int_digits: 39, max_frac_zeros: 44, max_frac_digits: 45,
+    exact_int_digits: 8, frac_num_digits: 8, frac_den_digits: 19,
+};
+/// `-65500.0`, `6e-8`, `6.104e-5`, `2^12 = 4096`, `2047/16777216`.
+///
+/// Although no `f16` takes more than a dozen bytes in general, scientific or decimal notation, its
+/// buffers for them are 32 bytes, as for `f32`, even though they are sized from these limits by
+/// [len_exp] and [len_dec] and checked by `prove`. The mantissa printers, shared by every float
+/// type, always write [fmt::PRINT_U64_LEN] bytes, which may start after a sign and `0.0000`; and
+/// even with narrower printers, the same buffers back `format_fixed_field` and
+/// `format_exp_min_sig`, whose output has up to 17 significant digits for any float type, so
+/// takes up to 27 bytes.
+pub(crate) const F16: Limits = Limits {
+    mant: 5, exp: 1 + 1, max_int_digits: 5, max_frac_zeros: 7, max_frac_digits: 8,
+    exact_int_digits: 4, frac_num_digits: 4, frac_den_digits: 8,
+};
+/// `-3.39e38`, `1e-40`, `5.5e-40`, `2^9 = 512`, `255/9223372036854775808`.
+///
+/// As for [F16], its general and scientific buffers are 32 bytes because of the mantissa printers.
+pub(crate) const BF16: Limits = Limits {
+    mant: 4, exp: 1 + 2, max_int_digits: 39, max_frac_zeros: 39, max_frac_digits: 41,
+    exact_int_digits: 3, frac_num_digits: 3, frac_den_digits: 19,
 };
 
-/// Size of buffer necessary for serialising any `f64` in decimal notation.
-const LEN_DEC: usize = {
-    let max_exp = 324usize;
+const fn max(a: usize, b: usize) -> usize { if a > b { a } else { b } }
+
+const fn min(a: usize, b: usize) -> usize { if a < b { a } else { b } }
+
+/// Length of the longest output of `format_exp` for a float with limits `l`, e.g.
+/// `-1.2345678901234567e-300`.
+pub(crate) const fn exp_len(l: Limits) -> usize {
+    1 + l.mant + 1 + 1 + l.exp
+}
+
+/// `format_general` writes a number in decimal notation if `GENERAL_LOW < decimal_exp <=
+/// GENERAL_HIGH`, where `decimal_exp` is the number of digits before the decimal point, and in
+/// scientific notation otherwise.
+pub(crate) const GENERAL_LOW: i32 = -5;
+pub(crate) const GENERAL_HIGH: i32 = 16;
+
+/// Whether `format_general` writes a number with `decimal_exp` digits before the decimal point in
+/// decimal notation, rather than in scientific notation.
+pub(crate) const fn is_general_dec(decimal_exp: i32) -> bool {
+    GENERAL_LOW < decimal_exp && decimal_exp <= GENERAL_HIGH
+}
+
+/// Lengths of the nonzero `decimal` in decimal notation without a trailing `.0`, which assumes it
+/// has no trailing zeros, and in scientific notation with every digit of its mantissa, e.g.
+/// `(11, 7)` for `-1.5e-7`.
+pub(crate) const fn notation_lens(decimal: super::common::SignedDecimal) -> (usize, usize) {
+    let sign_len = !decimal.sign as usize;
+    let mant_len = fmt::len_u64(decimal.mant);
+    let decimal_exp = mant_len as i32 + decimal.exp;
+
+    // `1234e2 -> 123400`, `1234e-1 -> 123.4`, `1234e-6 -> 0.001234`
+    let dec_len = sign_len + if decimal.exp >= 0 {
+        decimal_exp as usize
+    } else if decimal_exp > 0 {
+        mant_len + 1
+    } else {
+        2 + (-decimal.exp) as usize
+    };
+    // `1234e-6 -> 1.234e-3`
+    let sci_exp = decimal_exp - 1;
+    let sci_len = sign_len
+        + mant_len + (mant_len > 1) as usize
+        + 1 + (sci_exp < 0) as usize + fmt::len_u64(sci_exp.unsigned_abs() as u64);
+    (dec_len, sci_len)
+}
+
+/// Length of the longest output of `format_general` for a float with limits `l`: as long as
+/// `-1.234e-300`, `-0.00001234`, or `-1234000000000000.0`.
+pub(crate) const fn general_len(l: Limits) -> usize {
+    let small = 1 + 2 + (-GENERAL_LOW - 1) as usize + l.mant;
+    let int = 1 + min(l.max_int_digits, GENERAL_HIGH as usize) + 2;
+    max(exp_len(l), max(small, int))
+}
+
+/// Length of the longest output of `format_dec` for a float with limits `l`: the integer part and
+/// `.0`, or `0.` and the fractional digits.
+pub(crate) const fn dec_len(l: Limits) -> usize {
+    let int = 1 + l.max_int_digits + 2;
+    let frac = 1 + 2 + l.max_frac_digits;
+    max(int, frac)
+}
+
+/// Size of buffer necessary for serialising any float with limits `l` in scientific notation, or
+/// in general notation. This is dominated by the bytes touched by the mantissa printers after a
+/// sign and up to `0.0000`, or by the output of `format_general` with a suffix such as `_f64`.
+pub(crate) const fn len_exp(l: Limits) -> usize {
+    let small = 1 + 2 + 4 + fmt::PRINT_U64_LEN;
+    let suffixed = general_len(l) + 4;
+    max(small, suffixed).next_multiple_of(8)
+}
+
+/// Size of buffer necessary for serialising any float of any type in scientific or general
+/// notation, i.e. [len_exp] of `f64`, for storage shared between float types.
+pub(crate) const LEN_EXP: usize = len_exp(F64);
+
+/// Size of buffer necessary for serialising any float with limits `l` in decimal notation. This is
+/// never less than [len_exp], so that the same buffer fits any notation (see `format_as`), which is
+/// what sizes it for types with few digits, such as `f16`.
+pub(crate) const fn len_dec(l: Limits) -> usize {
+    let max_exp = l.max_frac_zeros + 1;
     let decimal_point = 2;
-    let mantissa = 20;
-    (max_exp + decimal_point + mantissa).next_multiple_of(8)
+    let len = (max_exp + decimal_point + fmt::PRINT_U64_LEN).next_multiple_of(8);
+    max(len, len_exp(l))
+}
+
+/// Size of buffer necessary for serialising any `f64` in decimal notation.
+#[cfg(feature = "arrayvec")]
+pub(crate) const LEN_DEC: usize = len_dec(F64);
+
+/// Size of buffer necessary for serialising any float with limits `l` in decimal notation with
+/// digit group separators. The decimal string is first written to the last [len_dec] bytes and
+/// then moved to the front, inserting separators; there are at most `max_int_digits - 1` of them
+/// (groups of 1 digit).
+pub(crate) const fn len_locale(l: Limits) -> usize {
+    let max_separators = l.max_int_digits - 1;
+    (len_dec(l) + max_separators).next_multiple_of(8)
+}
+
+/// Maximum number of digits after the decimal point in fixed-precision notation. The shortest
+/// representation of any `f64` has no significant digits past the 324th decimal place, so more
+/// digits would all be zeros.
+pub(crate) const MAX_PRECISION: usize = 324;
+
+/// Maximum number of significant digits [Buffer::format_exp_min_sig](crate::Buffer::format_exp_min_sig)
+/// pads the mantissa to; no float needs more than 17 to be represented exactly.
+pub(crate) const MAX_MIN_SIG: usize = 17;
+
+/// Maximum number of places the decimal point may be moved by
+/// [Buffer::format_scaled](crate::Buffer::format_scaled), in either direction.
+pub(crate) const MAX_SCALE: usize = 256;
+
+/// Size of buffer necessary for serialising any float with limits `l` in fixed-precision decimal
+/// notation, with up to [MAX_PRECISION] digits after the decimal point.
+pub(crate) const fn len_fixed(l: Limits) -> usize {
+    let decimal_point = 1;
+    (1 + l.max_int_digits + decimal_point + MAX_PRECISION).next_multiple_of(8)
+}
+
+/// Number of thousands separators in the integer part of any float with limits `l`, as printed by
+/// [Buffer::format_money](crate::Buffer::format_money).
+pub(crate) const fn money_separators(l: Limits) -> usize {
+    (l.max_int_digits - 1) / 3
+}
+
+/// Size of buffer necessary for serialising any pair of floats as a complex number, in general
+/// notation.
+pub(crate) const LEN_COMPLEX: usize = 2 * LEN_EXP;
+
+/// Size of buffer necessary for serialising any pair of floats as an interval, in general
+/// notation.
+pub(crate) const LEN_INTERVAL: usize = 2 * LEN_EXP;
+
+/// Size of buffer necessary for serialising any float in both general and scientific notation.
+pub(crate) const LEN_BOTH: usize = 2 * LEN_EXP;
+
+/// Size of buffer necessary for serialising any float as an exact fraction, such as
+/// `-9007199254740991/9223372036854775808`, or else in general notation.
+pub(crate) const LEN_FRACTION: usize = {
+    let l = F64;
+    max(1 + l.frac_num_digits + 1 + l.frac_den_digits, LEN_EXP).next_multiple_of(8)
 };
 
-impl Sealed for General {
-    type Buffer = [core::mem::MaybeUninit<u8>; LEN_EXP];
+/// Size of buffer necessary for serialising any float in scientific notation with the digits of
+/// its mantissa in groups, i.e. with up to one separator per fractional digit but the first.
+pub(crate) const LEN_GROUPED: usize = {
+    let l = F64;
+    (1 + l.mant + 1 + (l.mant - 2) + 1 + l.exp).next_multiple_of(8)
+};
 
-    fn new_buffer() -> Self::Buffer {
-        [core::mem::MaybeUninit::uninit(); LEN_EXP]
-    }
+/// Proof that the buffers above are large enough for every float with limits `l`, in every branch
+/// of every formatting routine. The bounds count every byte *touched*, which includes scratch bytes
+/// written past the end of the output: the mantissa printers always write 20 bytes, and some
+/// routines pad with zeros in fixed-size chunks. The printers' footprints are taken from
+/// [fmt::PRINT_U64_LEN] and [fmt::print_u64_with_point_len], so they can't drift apart.
+///
+/// Since every routine is covered here, the `debug_assert!(n <= Self::MAX_LEN)` after each one in
+/// the [Buffer](crate::Buffer) methods can't fire; they are kept as a cheap cross-check of this
+/// proof, which the `extremes` and `extreme_magnitudes` tests exercise in debug builds.
+const fn prove(l: Limits) {
+    /// Sign byte.
+    const SIGN: usize = 1;
+    const PRINT: usize = fmt::PRINT_U64_LEN;
+
+    let (len_exp, len_dec, len_locale, len_fixed) = (len_exp(l), len_dec(l), len_locale(l), len_fixed(l));
+    assert!(len_exp <= LEN_EXP);
+
+    // `format_exp`: mantissa printed after the sign and first digit slot, then point, `e`, and
+    // exponent.
+    let exp = max(SIGN + 1 + PRINT, exp_len(l));
+    assert!(exp <= len_exp);
 
-    fn buffer_as_ptr(buf: &mut Self::Buffer) -> *mut u8 {
-        buf.as_mut_ptr() as *mut u8
+    // `format_exp_min_sig`: as `format_exp`, with the mantissa padded to `MAX_MIN_SIG` digits.
+    let exp_min_sig = SIGN + max(1 + PRINT, MAX_MIN_SIG + 1 + 1 + l.exp);
+    assert!(exp_min_sig <= len_exp);
+
+    // `format_general_bounded`, per branch, with up to `int_digits` digits before the point or
+    // `frac_zeros` zeros after it in decimal notation (zeros are padded in chunks of 8 bytes):
+    const fn general_bounded(l: Limits, int_digits: usize, frac_zeros: usize) -> usize {
+        // `1234e7 -> 12340000000.0`
+        let int = SIGN + max(PRINT, int_digits + 2 + 7);
+        // `1234e-1 -> 123.4`: mantissa printed in place, then shifted by one.
+        let frac = SIGN + max(PRINT, l.mant + 1);
+        // `1234e-6 -> 0.001234`: `0.` and zeros, then the mantissa.
+        let small = SIGN + 2 + frac_zeros + max(7, PRINT);
+        // `1234e30 -> 1.234e33`: mantissa printed with the point in place, then `e` and exponent.
+        let large = max(SIGN + fmt::print_u64_with_point_len(1), exp_len(l));
+        max(max(int, frac), max(small, large))
     }
+
+    // `format_general`: `format_general_bounded` with `-5 < decimal_exp <= 16`.
+    let general = general_bounded(l, 16, 4);
+    assert!(general <= len_exp);
+
+    // `format_ryu_compatible`: `format_general_bounded` with `-6 < decimal_exp <= 16` at most.
+    let ryu = general_bounded(l, 16, 5);
+    assert!(ryu <= len_exp);
+
+    let general_len = general_len(l);
+
+    // `format_rust_literal`: the output of `format_general`, then `_f64`.
+    let rust_literal = general_len + 4;
+    assert!(rust_literal <= len_exp);
+
+    // `format_duration_secs`: as `format_rust_literal`, but with a suffix of at most 3 bytes
+    // (`µs`), and the exponent may be up to 9 lower (which doesn't change its length).
+    let duration = general_len + 3;
+    assert!(duration <= len_exp);
+
+    // `format_clamped`: `format_general`, or a sentinel such as `<-1.5e-300`, i.e. the output of
+    // `format_exp` moved one byte to the right.
+    assert!(max(exp, 1 + exp_len(l)) <= len_exp);
+
+    // `format_json_quoted`: `format_general` between quotes.
+    let json_quoted = 1 + max(general, general_len + 1);
+    assert!(json_quoted <= len_exp);
+
+    // `format_fixed_field`: sign, up to 17 digits printed after the first digit slot, point, `e`,
+    // sign, and up to 5 exponent digits.
+    let fixed_field = SIGN + 1 + max(PRINT, 17 + 1 + 1 + 1 + 5);
+    assert!(fixed_field <= len_exp);
+
+    // `format_exp_grouped`: `format_exp`, then up to one separator per fractional digit but the
+    // first.
+    let exp_grouped = max(exp, SIGN + l.mant + 1 + (l.mant - 2) + 1 + l.exp);
+    assert!(exp_grouped <= LEN_GROUPED);
+
+    // `format_fraction`: numerator, `/`, and denominator, printed exactly, or else `format`.
+    let fraction = SIGN + l.frac_num_digits + 1 + l.frac_den_digits;
+    assert!(fraction <= LEN_FRACTION);
+    assert!(len_exp <= LEN_FRACTION);
+
+    // `format_bin_exp`: mantissa of at most 16 digits (`2^53`), `p`, and an exponent of at most 4
+    // digits (`-1074`) plus its sign.
+    let bin_exp = SIGN + max(PRINT, 16 + 1 + 5);
+    assert!(bin_exp <= len_exp);
+
+    // `format_complex`: `format_general` twice, with a `+` in between, then `i`.
+    let complex = general_len + 1 + max(general, general_len + 1);
+    assert!(complex <= LEN_COMPLEX);
+
+    // `format_interval`: `[`, `format_general`, `, `, `format_general`, then `]`.
+    let interval = 1 + general_len + 2 + max(general, general_len + 1);
+    assert!(interval <= LEN_INTERVAL);
+
+    // `format_pm`: `format_general`, `±` (2 bytes), then `format_general`.
+    let pm = general_len + 2 + general;
+    assert!(pm <= LEN_INTERVAL);
+
+    // `format_both`: `format_general`, then `format_exp`.
+    let both = general_len + max(general, exp);
+    assert!(both <= LEN_BOTH);
+
+    // `format_go`, per branch:
+    // `1234e30 -> 1.234e+33`: mantissa printed with the point in place, then `e`, sign, exponent.
+    let go_large = SIGN + max(fmt::print_u64_with_point_len(1), l.mant + 1 + 1 + 1 + l.exp);
+    // `1234e2 -> 123400`: at most 6 digits.
+    let go_int = SIGN + PRINT;
+    // `1234e-1 -> 123.4`: mantissa printed in place, then shifted by one.
+    let go_frac = SIGN + max(PRINT, l.mant + 1);
+    // `1234e-6 -> 0.001234`: `0.`, up to 4 zeros, then mantissa.
+    let go_small = SIGN + 2 + 4 + PRINT;
+    assert!(go_large <= len_exp);
+    assert!(go_int <= len_exp);
+    assert!(go_frac <= len_exp);
+    assert!(go_small <= len_exp);
+
+    // `format_swift`: as `format_go`, but integers are at most `2^(MANTISSA_DIGITS + 1)`, and have
+    // a trailing `.0`; and the fractional part up to 3 zeros.
+    let swift_int = SIGN + max(PRINT, l.exact_int_digits + 2);
+    let swift_small = SIGN + 2 + 3 + PRINT;
+    assert!(swift_int <= len_exp);
+    assert!(swift_small <= len_exp);
+
+    // `format_dec`, per branch (zeros are padded in chunks of 8 bytes):
+    // `1234e7 -> 12340000000.0`
+    let dec_int = SIGN + max(PRINT, (l.max_int_digits + 2).next_multiple_of(8) + 7);
+    // `1234e-1 -> 123.4`
+    let dec_frac = SIGN + max(PRINT, l.mant + 1);
+    // `1234e-6 -> 0.001234`
+    let dec_small = SIGN + max((2 + l.max_frac_zeros).next_multiple_of(8), 2 + l.max_frac_zeros + PRINT);
+    assert!(dec_int <= len_dec);
+    assert!(dec_frac <= len_dec);
+    assert!(dec_small <= len_dec);
+
+    // `format_locale`: `format_dec` at offset `len_locale - len_dec`, then one separator per digit
+    // in the integer part (but the last) moved to the front.
+    let locale = SIGN + l.max_int_digits + (l.max_int_digits - 1) + 2;
+    assert!(locale <= len_locale);
+    assert!(l.max_int_digits - 1 <= len_locale - len_dec);
+
+    // `format_with_thresholds`: `format_general_bounded` with any thresholds, so the integer part
+    // may have up to `max_int_digits` and the fractional part `max_frac_zeros` zeros.
+    let bounded = general_bounded(l, l.max_int_digits, l.max_frac_zeros);
+    assert!(bounded <= len_dec);
+
+    // `format_as`: any of `format`, `format_exp`, and `format_dec`.
+    assert!(len_exp <= len_dec);
+
+    // `format_round` and `format_min_frac`: the integer part is padded with zeros, then the
+    // fractional part with up to `MAX_PRECISION` zeros. Mantissas are only printed before the last
+    // `MAX_PRECISION` digits.
+    let fixed_int = SIGN + max(PRINT, l.max_int_digits + 1 + MAX_PRECISION);
+    let fixed_small = SIGN + max(2 + l.max_frac_zeros + PRINT, 2 + MAX_PRECISION);
+    assert!(fixed_int <= len_fixed);
+    assert!(fixed_small <= len_fixed);
+
+    // `format_accounting`: as `format_round`, with a closing parenthesis after the number.
+    let accounting_int = fixed_int + 1;
+    let accounting_small = fixed_small + 1;
+    assert!(accounting_int <= len_fixed);
+    assert!(accounting_small <= len_fixed);
+
+    // `format_money`: as `format_round` with 2 digits, at offset `money_separators`, then moved to
+    // the front inserting that many separators at most.
+    let money = money_separators(l) + SIGN + max(PRINT, l.max_int_digits + 1 + 2);
+    assert!(money <= len_fixed);
+
+    // `format_scaled`: as `format_dec`, with the decimal point moved by up to `MAX_SCALE` places,
+    // so with up to `MAX_SCALE` more integer digits or fractional zeros.
+    let scaled_int = SIGN + max(PRINT, l.max_int_digits + MAX_SCALE + 2);
+    let scaled_frac = SIGN + PRINT + 1;
+    let scaled_small = SIGN + 2 + l.max_frac_zeros + MAX_SCALE + PRINT;
+    assert!(scaled_int <= len_fixed);
+    assert!(scaled_frac <= len_fixed);
+    assert!(scaled_small <= len_fixed);
+
+    // `format_compact`: either `format_exp`, or as `format_round` with `precision` exactly the
+    // number of fractional digits (so none are padded, and there are at most `max_frac_digits`),
+    // and `0` for integers.
+    let compact_int = SIGN + max(PRINT, l.max_int_digits);
+    let compact_frac = SIGN + PRINT + 1;
+    let compact_small = SIGN + max(2 + l.max_frac_zeros + PRINT, 2 + l.max_frac_digits);
+    assert!(exp <= len_dec);
+    assert!(compact_int <= len_dec);
+    assert!(compact_frac <= len_dec);
+    assert!(compact_small <= len_dec);
 }
 
-impl Sealed for Scientific {
-    type Buffer = [core::mem::MaybeUninit<u8>; LEN_EXP];
+const _: () = {
+    prove(F64);
+    prove(F32);
+    #[cfg(feature = "half")]
+    prove(F16);
+    #[cfg(feature = "half")]
+    prove(BF16);
+};
 
-    fn new_buffer() -> Self::Buffer {
-        [core::mem::MaybeUninit::uninit(); LEN_EXP]
-    }
+impl Sealed for General {
+    type Buffer<F: Float> = F::BufferExp;
+    const NAME: &'static str = "General";
+}
 
-    fn buffer_as_ptr(buf: &mut Self::Buffer) -> *mut u8 {
-        buf.as_mut_ptr() as *mut u8
-    }
+impl Sealed for Scientific {
+    type Buffer<F: Float> = F::BufferExp;
+    const NAME: &'static str = "Scientific";
 }
 
 impl Sealed for Decimal {
-    type Buffer = [core::mem::MaybeUninit<u8>; LEN_DEC];
+    type Buffer<F: Float> = F::BufferDec;
+    const NAME: &'static str = "Decimal";
+}
 
-    fn new_buffer() -> Self::Buffer {
-        [core::mem::MaybeUninit::uninit(); LEN_DEC]
-    }
+impl Sealed for Locale {
+    type Buffer<F: Float> = F::BufferLocale;
+    const NAME: &'static str = "Locale";
+}
 
-    fn buffer_as_ptr(buf: &mut Self::Buffer) -> *mut u8 {
-        buf.as_mut_ptr() as *mut u8
-    }
+impl Sealed for Bounded {
+    type Buffer<F: Float> = F::BufferDec;
+    const NAME: &'static str = "Bounded";
+}
+
+impl Sealed for Fixed {
+    type Buffer<F: Float> = F::BufferFixed;
+    const NAME: &'static str = "Fixed";
+}
+
+impl Sealed for Dynamic {
+    type Buffer<F: Float> = F::BufferDec;
+    const NAME: &'static str = "Dynamic";
+}
+
+impl Sealed for Fraction {
+    type Buffer<F: Float> = [MaybeUninit<u8>; LEN_FRACTION];
+    const NAME: &'static str = "Fraction";
+}
+
+impl Sealed for Interval {
+    type Buffer<F: Float> = [MaybeUninit<u8>; LEN_INTERVAL];
+    const NAME: &'static str = "Interval";
+}
+
+impl Sealed for Both {
+    type Buffer<F: Float> = [MaybeUninit<u8>; LEN_BOTH];
+    const NAME: &'static str = "Both";
+}
+
+impl Sealed for Grouped {
+    type Buffer<F: Float> = [MaybeUninit<u8>; LEN_GROUPED];
+    const NAME: &'static str = "Grouped";
+}
+
+#[cfg(feature = "num-complex")]
+impl Sealed for Complex {
+    type Buffer<F: Float> = [MaybeUninit<u8>; LEN_COMPLEX];
+    const NAME: &'static str = "Complex";
 }
@@ -6,7 +6,10 @@ macro_rules! mk_impl { (
     len_mantissa = $len_mantissa:path,
     print_mantissa = $print_mantissa:path,
     print_mantissa_known_len = $print_mantissa_known_len:path,
-    tests = {
+    print_mantissa_with_point = $print_mantissa_with_point:path,
+    ryu_general_bounds = ($ryu_low:literal, $ryu_high:literal),
+    limits = $limits:path,
+    $(tests = {
         pi = {
             dec = $pi_dec:literal,
             exp = $pi_exp:literal,
@@ -23,21 +26,24 @@ macro_rules! mk_impl { (
             decimal = $ln2_decimal:expr,
         },
         min_subnormal = {
+            general = $min_subnormal_general:literal,
             dec = $min_subnormal_dec:literal,
             exp = $min_subnormal_exp:literal,
             decimal = $min_subnormal_decimal:expr,
         },
         min_normal = {
+            general = $min_normal_general:literal,
             dec = $min_normal_dec:literal,
             exp = $min_normal_exp:literal,
             decimal = $min_normal_decimal:expr,
         },
         max = {
+            general = $max_general:literal,
             dec = $max_dec:literal,
             exp = $max_exp:literal,
             decimal = $max_decimal:expr,
         },
-    }
+    })?
 ) => {
 
 use crate::teju::{common, fmt};
@@ -108,6 +114,38 @@ pub const fn is_even(n: Mant) -> bool {
     n % 2 == 0
 }
 
+/// Prints a mantissa, returning its length. May clobber up to 20 bytes of `buf`.
+///
+/// With the `length-free-print` feature, uses `$print_mantissa`, which finds the length
+/// arithmetically; otherwise uses `$len_mantissa` and `$print_mantissa_known_len`.
+#[inline]
+const unsafe fn print_mantissa(x: u64, buf: *mut u8) -> usize {
+    unsafe {
+        if cfg!(feature = "length-free-print") {
+            $print_mantissa(x, buf)
+        } else {
+            $print_mantissa_known_len(x, buf, $len_mantissa(x))
+        }
+    }
+}
+
+/// Prints a mantissa with `len` digits, returning `len`. May clobber up to 20 bytes of `buf`.
+///
+/// With the `length-free-print` feature, uses `$print_mantissa` (ignoring `len`); otherwise uses
+/// `$print_mantissa_known_len`.
+#[inline]
+const unsafe fn print_mantissa_known_len(x: u64, buf: *mut u8, len: usize) -> usize {
+    unsafe {
+        if cfg!(feature = "length-free-print") {
+            let printed_len = $print_mantissa(x, buf);
+            debug_assert!(printed_len == len);
+            printed_len
+        } else {
+            $print_mantissa_known_len(x, buf, len)
+        }
+    }
+}
+
 impl Binary {
     /// Number of bits in precision of the mantissa, including the implicit `1.`.
     const BITS_MANTISSA: u32 = $f::MANTISSA_DIGITS;
@@ -122,6 +160,12 @@ impl Binary {
     /// 1 + the maximum mantissa value storable in a float.
     const MAX_MANT: Mant = 1 << Self::BITS_MANTISSA_EXPLICIT;
 
+    /// The sign bit of the bit representation of a float.
+    const SIGN_MASK: Mant = 1 << (Mant::BITS - 1);
+
+    /// The bit representation of positive infinity: all exponent bits set, and zero mantissa.
+    const INF_BITS: Mant = !Self::SIGN_MASK & !(Self::MAX_MANT - 1);
+
     /// Decomposes a **finite** `$f` into the binary exponent and mantissa of its absolute
     /// value, i.e. such that `|num| = mant * 2^exp`.
     ///
@@ -131,13 +175,14 @@ impl Binary {
     pub const fn new(num: $f) -> Self {
         if !cfg!(test) {
             debug_assert!(num.is_finite());
-            debug_assert!(num.abs() != 0.0);
+            debug_assert!(num.to_bits() & !Binary::SIGN_MASK != 0);
         }
 
-        // Extract fields
-        let num = num.abs();
-        let mut mant = lsb(num.to_bits(), Self::BITS_MANTISSA_EXPLICIT);
-        let mut exp = (num.to_bits() >> Self::BITS_MANTISSA_EXPLICIT) as Exp;
+        // Extract fields (clearing the sign bit with an integer mask rather than `abs`, so that no
+        // floating point instructions are involved)
+        let bits = num.to_bits() & !Self::SIGN_MASK;
+        let mut mant = lsb(bits, Self::BITS_MANTISSA_EXPLICIT);
+        let mut exp = (bits >> Self::BITS_MANTISSA_EXPLICIT) as Exp;
 
         // Normals have implicit unit (`1.xxx`) and -1 bias; subnormals don't
         if exp != 0 {
@@ -246,8 +291,6 @@ impl Binary {
                     }
                 } else if s == a && is_even(Self::MAX_MANT) && decimal_a.is_tie_uncentered() {
                     return Decimal{exp: exp_floor + 1, mant: q }.remove_trailing_zeros()
-                } else if (a + b) % 2 == 1 {
-                    return Decimal{exp: exp_floor, mant: (a + b) / 2 + 1}
                 }
 
                 let log2_mant_c = Self::BITS_MANTISSA + exp_residual + 1;
@@ -306,10 +349,16 @@ impl Decimal {
     }
 
     /// Shortens `self` by removing trailing zeros from `self.mant` while possible, and
-    /// incrementing `self.exp` by the same amount.
+    /// incrementing `self.exp` by the same amount. Invariant: `self.mant` is nonzero, or this never
+    /// returns.
     const fn remove_trailing_zeros(mut self) -> Self {
+        // `M_INV5` is the inverse of 5 modulo `2^Mant::BITS`, so for multiples of 10, `q` is
+        // `mant / 10`, which is less than `BOUND`. For odd `mant`, `q` has the top bit set. For
+        // other even `mant`, `q` is `(mant / 2) * M_INV5` modulo `2^(Mant::BITS - 1)`, and the
+        // values of this less than `BOUND` are exactly `(5 * k) * M_INV5 = k` for `k < BOUND`.
         const M_INV5: Mant = -((Mant::MAX / 5) as $mant_signed) as Mant;
         const BOUND: Mant = Mant::MAX / 10 + 1;
+        debug_assert!(self.mant != 0);
         loop {
             // let q = (self.mant * M_INV5).rotate_right(1);
             let q = self.mant.wrapping_mul(M_INV5).rotate_right(1);
@@ -330,14 +379,35 @@ impl Result {
     pub unsafe fn new(num: $f) -> Self {
         if !cfg!(test) {
             debug_assert!(num.is_finite());
-            debug_assert!(num.abs() != 0.0);
+            debug_assert!(num.to_bits() & !Binary::SIGN_MASK != 0);
         }
         // dbg!(num);
         // dbg!(Binary::new(num));
         // dbg!(Binary::new(num).teju_jagua());
+        unsafe { Result::from_binary(num, Binary::new(num)) }
+    }
+
+    /// As [Result::new], for `num` already decoded as `binary`.
+    #[inline]
+    pub unsafe fn from_binary(num: $f, binary: Binary) -> Self {
         Result{
             sign: num.is_sign_positive(),
-            decimal: unsafe { Binary::new(num).teju_jagua() },
+            decimal: unsafe { binary.teju_jagua() },
+        }
+    }
+
+    /// Like [Result::new], but `num` may also be ±0, which is represented by a zero mantissa.
+    ///
+    /// Only the formatting routines which say so support a zero mantissa.
+    #[inline]
+    pub unsafe fn new_finite(num: $f) -> Self {
+        if num.to_bits() & !Binary::SIGN_MASK == 0 {
+            Result{
+                sign: num.is_sign_positive(),
+                decimal: Decimal{ exp: 0, mant: 0 },
+            }
+        } else {
+            unsafe { Result::new(num) }
         }
     }
 
@@ -349,11 +419,7 @@ impl Result {
             buf = buf.add(!self.sign as usize);
 
             *buf.add(2) = b'0';
-            /*let mant_len = fmt::print_u64_mantissa(self.decimal.mant, buf.add(1));*/
-            let mant_len = {
-                let len = $len_mantissa(self.decimal.mant as u64);
-                $print_mantissa_known_len(self.decimal.mant as u64, buf.add(1), len)
-            };
+            let mant_len = print_mantissa(self.decimal.mant as u64, buf.add(1));
 
             *buf = *buf.add(1);
             *buf.add(1) = b'.';
@@ -367,6 +433,37 @@ impl Result {
         }
     }
 
+    /// Like [Result::format_exp], but pads the mantissa with zeros after the decimal point up to
+    /// `min_sig` significant digits. Supports a zero mantissa. Invariant: `min_sig <= 17`.
+    #[inline]
+    pub unsafe fn format_exp_min_sig(self, mut buf: *mut u8, min_sig: usize) -> usize {
+        debug_assert!(min_sig <= crate::teju::format::MAX_MIN_SIG);
+        let buf_orig = buf;
+        unsafe {
+            buf.write(b'-');
+            buf = buf.add(!self.sign as usize);
+
+            let mant_len = print_mantissa(self.decimal.mant as u64, buf.add(1));
+
+            *buf = *buf.add(1);
+            *buf.add(1) = b'.';
+            let sig_len = if mant_len < min_sig {
+                core::ptr::write_bytes(buf.add(1 + mant_len), b'0', min_sig - mant_len);
+                min_sig
+            } else {
+                mant_len
+            };
+            buf = buf.add(sig_len + ((sig_len > 1) as usize));
+
+            // The padding zeros don't change the exponent
+            *buf = b'e';
+            let exp = if self.decimal.mant == 0 { 0 } else { self.decimal.exp + mant_len as i32 - 1 };
+            let exp_len = fmt::print_i32_exp(exp, buf.add(1));
+
+            buf.offset_from(buf_orig) as usize + 1 + exp_len
+        }
+    }
+
     /*#[inline]
     unsafe fn format_exp_fixed(sign: bool, decimal: Decimal, mut buf: *mut u8) -> usize {
         let buf_orig = buf;
@@ -386,8 +483,22 @@ impl Result {
         }
     }*/
 
+    /// Formats the number in decimal notation if `GENERAL_LOW < decimal_exp <= GENERAL_HIGH`, i.e.
+    /// `-5 < decimal_exp <= 16`, and in scientific notation otherwise; see
+    /// [Result::format_general_bounded].
+    #[inline]
+    pub unsafe fn format_general(self, buf: *mut u8) -> usize {
+        use crate::teju::format::{GENERAL_HIGH, GENERAL_LOW};
+        unsafe { self.format_general_bounded(buf, GENERAL_LOW, GENERAL_HIGH) }
+    }
+
+    /// Formats the number in decimal notation if and only if `low < decimal_exp <= high`, where
+    /// `decimal_exp` is the number of digits before the decimal point, and in scientific notation
+    /// otherwise. Invariant: `low <= 0 < high`; the caller must ensure the buffer fits the longest
+    /// output these bounds allow (see the proof in `format`).
     #[inline]
-    pub unsafe fn format_general(self, mut buf: *mut u8) -> usize {
+    pub unsafe fn format_general_bounded(self, mut buf: *mut u8, low: i32, high: i32) -> usize {
+        debug_assert!(low <= 0 && 0 < high);
         unsafe {
             buf.write(b'-');
             buf = buf.add(!self.sign as usize);
@@ -395,21 +506,24 @@ impl Result {
             let mant_len = $len_mantissa(self.decimal.mant as u64);
             let decimal_exp = mant_len as i32 + self.decimal.exp;
 
-            if self.decimal.exp >= 0 && decimal_exp <= 16 {  // Implies mant_len <= 16
+            if self.decimal.exp >= 0 && decimal_exp <= high {
                 // 1234e7 -> 12340000000.0
-                // Write mantissa, pad with zeros (up to 17 of them), write decimal point at
-                // `decimal_exp`. Careful not to overflow 32 byte `buf`.
-                $print_mantissa_known_len(self.decimal.mant as u64, buf, mant_len);
-                core::ptr::write_bytes(buf.add(mant_len), b'0', 8);
-                if mant_len < 8 { core::ptr::write_bytes(buf.add(mant_len + 8), b'0', 10) };
+                // Write mantissa, pad with zeros (in 8 byte chunks), write decimal point at
+                // `decimal_exp`.
+                print_mantissa_known_len(self.decimal.mant as u64, buf, mant_len);
+                let n_zeros = self.decimal.exp as usize + 2;
+                core::ptr::write_bytes(buf.add(mant_len), b'0', n_zeros.next_multiple_of(8));
                 *buf.add(decimal_exp as usize) = b'.';
                 !self.sign as usize + decimal_exp as usize + 2
-            } else if 0 < decimal_exp && decimal_exp <= 16 {
+            } else if 0 < decimal_exp && decimal_exp <= high {
                 // 1234e-1 -> 123.4
                 // Write mantissa, shift digits after `decimal_exp` digit 1 place to the right,
                 // write decimal point in between.
+                // Printing the digits with the point already in place instead, with
+                // `$print_mantissa_with_point`, made no measurable difference on
+                // `unit_gaussian_around_zero` or the `teju_general` microbenchmarks.
                 debug_assert!(self.decimal.exp < 0);
-                $print_mantissa_known_len(self.decimal.mant as u64, buf, mant_len);
+                print_mantissa_known_len(self.decimal.mant as u64, buf, mant_len);
                 core::ptr::copy(
                     buf.add(decimal_exp as usize),
                     buf.add(decimal_exp as usize + 1),
@@ -417,14 +531,14 @@ impl Result {
                 );
                 *buf.add(decimal_exp as usize) = b'.';
                 !self.sign as usize + mant_len + 1
-            } else if -5 < decimal_exp && decimal_exp <= 0 {
+            } else if low < decimal_exp && decimal_exp <= 0 {
                 // 1234e-6 -> 0.001234
-                // Pad with zeros (up to 7 of them), write decimal point at second digit, write
+                // Pad with zeros (in 8 byte chunks), write decimal point at second digit, write
                 // mantissa after.
-                core::ptr::write_bytes(buf, b'0', 8);
-                *buf.add(1) = b'.';
                 let n_zeros = (2 - decimal_exp) as usize;
-                $print_mantissa_known_len(self.decimal.mant as u64, buf.add(n_zeros), mant_len);
+                core::ptr::write_bytes(buf, b'0', n_zeros.next_multiple_of(8));
+                *buf.add(1) = b'.';
+                print_mantissa_known_len(self.decimal.mant as u64, buf.add(n_zeros), mant_len);
                 (!self.sign as i32 + 2 - self.decimal.exp) as usize
             } else if mant_len == 1 {
                 // 1e30
@@ -435,11 +549,10 @@ impl Result {
                 !self.sign as usize + 2 + exp_len
             } else {
                 // 1234e30 -> 1.234e33
-                // Write mantissa, shift first digit to add decimal point, then `e`, then exponent.
-                $print_mantissa_known_len(self.decimal.mant as u64, buf.add(1), mant_len);
-                *buf = *buf.add(1);
-                *buf.add(1) = b'.';
-                *buf.add(mant_len + 1) = b'e';                
+                // Write mantissa with the decimal point after the first digit, then `e`, then
+                // exponent.
+                $print_mantissa_with_point(self.decimal.mant as u64, buf, mant_len, 1);
+                *buf.add(mant_len + 1) = b'e';
                 let exp_len = fmt::print_i32_exp(decimal_exp - 1, buf.add(2 + mant_len));
                 !self.sign as usize + 2 + mant_len + exp_len
             }
@@ -459,7 +572,7 @@ impl Result {
                 // 1234e7 -> 12340000000.0
                 // Write mantissa, pad with zeros (in 8 byte chunks), write decimal point at
                 // `decimal_exp`.
-                $print_mantissa_known_len(self.decimal.mant as u64, buf, mant_len);
+                print_mantissa_known_len(self.decimal.mant as u64, buf, mant_len);
                 let n_zeros = self.decimal.exp as usize + 2;
                 core::ptr::write_bytes(buf.add(mant_len), b'0', n_zeros.next_multiple_of(8));
                 *buf.add(decimal_exp as usize) = b'.';
@@ -468,7 +581,7 @@ impl Result {
                 // 1234e-1 -> 123.4
                 // Write mantissa, shift digits after `decimal_exp` digit 1 place to the right,
                 // write decimal point in between.
-                $print_mantissa_known_len(self.decimal.mant as u64, buf, mant_len);
+                print_mantissa_known_len(self.decimal.mant as u64, buf, mant_len);
                 core::ptr::copy(
                     buf.add(decimal_exp as usize),
                     buf.add(decimal_exp as usize + 1),
@@ -483,7 +596,7 @@ impl Result {
                 let n_zeros = (2 - decimal_exp) as usize;
                 core::ptr::write_bytes(buf, b'0', n_zeros.next_multiple_of(8));
                 *buf.add(1) = b'.';
-                $print_mantissa_known_len(self.decimal.mant as u64, buf.add(n_zeros), mant_len);
+                print_mantissa_known_len(self.decimal.mant as u64, buf.add(n_zeros), mant_len);
                 (!self.sign as i32 + 2 - self.decimal.exp) as usize
             }
         }
@@ -493,6 +606,17 @@ impl Result {
 use $crate::teju::float;
 
 impl float::Sealed for $f {
+    const NAME: &'static str = stringify!($f);
+    const LIMITS: $crate::teju::format::Limits = $limits;
+    const EXACT_INT_LIMIT_BITS: u64 =
+        (($f::MAX_EXP + Binary::BITS_MANTISSA as i32) as u64) << Binary::BITS_MANTISSA_EXPLICIT;
+    const QUIET_NAN_BIT: u64 = (Binary::MAX_MANT >> 1) as u64;
+
+    type BufferExp = [core::mem::MaybeUninit<u8>; $crate::teju::format::len_exp($limits)];
+    type BufferDec = [core::mem::MaybeUninit<u8>; $crate::teju::format::len_dec($limits)];
+    type BufferLocale = [core::mem::MaybeUninit<u8>; $crate::teju::format::len_locale($limits)];
+    type BufferFixed = [core::mem::MaybeUninit<u8>; $crate::teju::format::len_fixed($limits)];
+
     #[inline]
     fn classify(&self) -> float::FloatType {
         if self.is_finite() {
@@ -504,9 +628,19 @@ impl float::Sealed for $f {
         }
     }
 
+    #[inline]
+    fn is_sign_negative(&self) -> bool {
+        $f::is_sign_negative(*self)
+    }
+
+    #[inline]
+    fn to_bits_u64(&self) -> u64 {
+        self.to_bits() as u64
+    }
+
     #[inline]
     fn classify_finite(&self) -> float::FiniteFloatType {
-        if self.abs().to_bits() != 0 {
+        if self.to_bits() & !Binary::SIGN_MASK != 0 {
             float::FiniteFloatType::Nonzero
         } else {
             if self.is_sign_positive() {float::FiniteFloatType::PosZero} else {float::FiniteFloatType::NegZero}
@@ -514,8 +648,78 @@ impl float::Sealed for $f {
     }
 
     #[inline]
-    unsafe fn format_general_finite_nonzero(self, buf: *mut u8) -> usize {
-        unsafe { Result::new(self).format_general(buf) }
+    fn classify_bits(&self) -> float::FloatType {
+        let bits = self.to_bits();
+        let abs = bits & !Binary::SIGN_MASK;
+        if abs < Binary::INF_BITS {
+            float::FloatType::Finite
+        } else if abs == Binary::INF_BITS {
+            if bits & Binary::SIGN_MASK == 0 {float::FloatType::PosInf} else {float::FloatType::NegInf}
+        } else {
+            float::FloatType::Nan
+        }
+    }
+
+    #[inline]
+    fn classify_finite_bits(&self) -> float::FiniteFloatType {
+        let bits = self.to_bits();
+        if bits & !Binary::SIGN_MASK != 0 {
+            float::FiniteFloatType::Nonzero
+        } else {
+            if bits & Binary::SIGN_MASK == 0 {float::FiniteFloatType::PosZero} else {float::FiniteFloatType::NegZero}
+        }
+    }
+
+    #[inline]
+    unsafe fn to_decimal_finite(self) -> common::SignedDecimal {
+        let result = unsafe { Result::new_finite(self) };
+        common::SignedDecimal{
+            sign: result.sign,
+            mant: result.decimal.mant as u64,
+            exp: result.decimal.exp,
+        }
+    }
+
+    #[inline]
+    unsafe fn format_general_decimal(decimal: common::SignedDecimal, buf: *mut u8) -> usize {
+        debug_assert!(decimal.mant != 0 && decimal.mant <= Mant::MAX as u64);
+        let result = Result{
+            sign: decimal.sign,
+            decimal: Decimal{ exp: decimal.exp, mant: decimal.mant as Mant },
+        };
+        unsafe { result.format_general(buf) }
+    }
+
+    #[inline]
+    unsafe fn format_exp_decimal(decimal: common::SignedDecimal, buf: *mut u8) -> usize {
+        debug_assert!(decimal.mant != 0 && decimal.mant <= Mant::MAX as u64);
+        let result = Result{
+            sign: decimal.sign,
+            decimal: Decimal{ exp: decimal.exp, mant: decimal.mant as Mant },
+        };
+        unsafe { result.format_exp(buf) }
+    }
+
+    #[inline]
+    unsafe fn format_general_finite_nonzero(self, mut buf: *mut u8) -> usize {
+        // Fast path for the integers 1 to 9, e.g. flags or small counts, which are printed
+        // directly as `N.0`.
+        let binary = Binary::new(self);
+        if binary.is_small_integer() {
+            let int = binary.mant >> (-binary.exp as u32);
+            if int < 10 {
+                let sign_len = self.is_sign_negative() as usize;
+                unsafe {
+                    buf.write(b'-');
+                    buf = buf.add(sign_len);
+                    *buf = b'0' + int as u8;
+                    *buf.add(1) = b'.';
+                    *buf.add(2) = b'0';
+                }
+                return sign_len + 3
+            }
+        }
+        unsafe { Result::from_binary(self, binary).format_general(buf) }
     }
 
     #[inline]
@@ -527,12 +731,50 @@ impl float::Sealed for $f {
     unsafe fn format_dec_finite_nonzero(self, buf: *mut u8) -> usize {
         unsafe { Result::new(self).format_dec(buf) }
     }
+
+    #[inline]
+    unsafe fn format_ryu_finite_nonzero(self, buf: *mut u8) -> usize {
+        unsafe { Result::new(self).format_general_bounded(buf, $ryu_low, $ryu_high) }
+    }
+
+    #[inline]
+    unsafe fn format_general_bounded_finite_nonzero(self, buf: *mut u8, low: i32, high: i32) -> usize {
+        unsafe { Result::new(self).format_general_bounded(buf, low, high) }
+    }
+
+    #[inline]
+    unsafe fn format_exp_min_sig_finite(self, buf: *mut u8, min_sig: usize) -> usize {
+        unsafe { Result::new_finite(self).format_exp_min_sig(buf, min_sig) }
+    }
+
+    #[inline]
+    unsafe fn to_binary_finite_nonzero(self) -> (u64, i32) {
+        // Shift out the trailing zero bits, so the mantissa is odd, e.g. `1.0 = 1p0`.
+        let binary = Binary::new(self);
+        let zeros = binary.mant.trailing_zeros();
+        (binary.mant as u64 >> zeros, binary.exp + zeros as Exp)
+    }
+
+    #[inline]
+    unsafe fn format_bin_exp_finite_nonzero(self, mut buf: *mut u8) -> usize {
+        let (mant, exp) = unsafe { self.to_binary_finite_nonzero() };
+        let sign_len = self.is_sign_negative() as usize;
+        unsafe {
+            buf.write(b'-');
+            buf = buf.add(sign_len);
+            let len = print_mantissa(mant, buf);
+            *buf.add(len) = b'p';
+            sign_len + len + 1 + fmt::print_exp::<4>(exp, buf.add(len + 1))
+        }
+    }
 }
 
+$(
 #[cfg(test)]
 mod tests {
     use super::*;
     use proptest::prelude::*;
+    use crate::teju::test_float::TestFloat;
 
     mod binary {
         use super::*;
@@ -540,14 +782,14 @@ mod tests {
         /// Aux function, assert that `num` is decoded as `binary`; repeat for `-num`.
         fn assert_finite(num: $f, binary: Binary) {
             assert!(num.is_finite());
-            assert_eq!(Binary::new(num.abs()), binary);
-            assert_eq!(Binary::new(-num.abs()), binary);
+            assert_eq!(Binary::new(TestFloat::abs(num)), binary);
+            assert_eq!(Binary::new(-TestFloat::abs(num)), binary);
         }
 
         #[test]
         fn extremes() {
             // Zero
-            assert_finite(0.0, Binary { exp: Binary::MIN_EXP, mant: 0 });
+            assert_finite($f::from_f64(0.0), Binary { exp: Binary::MIN_EXP, mant: 0 });
             // Smallest subnormal
             assert_finite($f::from_bits(1), Binary {
                 exp: $f::MIN_EXP - $f::MANTISSA_DIGITS as Exp,
@@ -570,11 +812,11 @@ mod tests {
             
             #[test]
             fn float_roundtrip(
-                float in $f::MIN .. $f::MAX,
+                float in $f::finite(),
             ) {
-                prop_assume!(float.abs() != 0.0);
+                prop_assume!(float != $f::from_f64(0.0));
                 let binary = Binary::new(float);
-                let refloat = ((2 as $f).powi(binary.exp) * binary.mant as $f).copysign(float);
+                let refloat = $f::from_f64(2f64.powi(binary.exp) * binary.mant as f64).copysign(float);
                 assert_eq!(refloat, float);
             }
         }
@@ -587,25 +829,30 @@ mod tests {
         /// repeat for `-num` (with the opposite sign).
         fn assert_finite(num: $f, decimal: Decimal) {
             assert!(num.is_finite());
-            assert!(num.abs() != 0.0);
-            assert_eq!(unsafe { Result::new(num.abs()) }, Result { sign: true, decimal });
-            assert_eq!(unsafe { Result::new(-num.abs()) }, Result { sign: false, decimal });
+            assert!(num != $f::from_f64(0.0));
+            assert_eq!(unsafe { Result::new(TestFloat::abs(num)) }, Result { sign: true, decimal });
+            assert_eq!(unsafe { Result::new(-TestFloat::abs(num)) }, Result { sign: false, decimal });
         }
 
         #[test]
+        #[allow(clippy::zero_prefixed_literal)]  // The `ln2` mantissas, e.g. `0_6931`.
         fn small() {
-            assert_finite(123.456, Decimal{ exp: -3, mant: 123456 });
-            assert_finite(0.1234, Decimal{ exp: -4, mant: 1234 });
-            assert_finite(core::$f::consts::PI, $pi_decimal);
-            assert_finite(core::$f::consts::E, $e_decimal);
-            assert_finite(core::$f::consts::LN_2, $ln2_decimal);
+            if core::mem::size_of::<$f>() >= 4 {
+                assert_finite($f::from_f64(123.456), Decimal{ exp: -3, mant: 123456u32 as Mant });
+                assert_finite($f::from_f64(0.1234), Decimal{ exp: -4, mant: 1234 });
+            }
+            assert_finite($f::PI, $pi_decimal);
+            assert_finite($f::E, $e_decimal);
+            assert_finite($f::LN_2, $ln2_decimal);
         }
 
         #[test]
         fn small_integer() {
-            assert_finite(123456., Decimal{ exp: 0, mant: 123456 });
-            assert_finite(1., Decimal{ exp: 0, mant: 1 });
-            assert_finite(12003400., Decimal{ exp: 2, mant: 120034 });
+            if core::mem::size_of::<$f>() >= 4 {
+                assert_finite($f::from_f64(123456.), Decimal{ exp: 0, mant: 123456u32 as Mant });
+                assert_finite($f::from_f64(12003400.), Decimal{ exp: 2, mant: 120034u32 as Mant });
+            }
+            assert_finite($f::from_f64(1.), Decimal{ exp: 0, mant: 1 });
         }
 
         #[test]
@@ -615,16 +862,58 @@ mod tests {
             assert_finite($f::MAX, $max_decimal);
         }
 
+        /// Aux function, remove trailing zeros from `mant` by division.
+        fn remove_trailing_zeros_naive(mut mant: Mant) -> Decimal {
+            let mut exp = 0;
+            while mant % 10 == 0 {
+                mant /= 10;
+                exp += 1;
+            }
+            Decimal{ exp, mant }
+        }
+
+        #[test]
+        fn remove_trailing_zeros_boundaries() {
+            let check = |mant: Mant| assert_eq!(
+                Decimal{ exp: 0, mant }.remove_trailing_zeros(),
+                remove_trailing_zeros_naive(mant),
+                "{mant}",
+            );
+            // Every `a * 10^k`, up to the largest power of 10, and its neighbours.
+            for a in 2 ..= 1000 {
+                let mut mant: Mant = a;
+                loop {
+                    check(mant);
+                    check(mant - 1);
+                    check(mant + 1);
+                    match mant.checked_mul(10) {
+                        Some(next) if next < Mant::MAX => mant = next,
+                        _ => break,
+                    }
+                }
+            }
+            // Around the bound, and the largest values.
+            for mant in (Mant::MAX / 10 - 1000) ..= (Mant::MAX / 10 + 1000) {
+                check(mant);
+                if let Some(mant) = mant.checked_mul(10) {
+                    check(mant);
+                }
+            }
+            for mant in (Mant::MAX - 1000) ..= Mant::MAX {
+                check(mant);
+            }
+        }
+
         const INT_BOUND: $mant_signed = ((1 as $mant) << Binary::BITS_MANTISSA) as $mant_signed;
         proptest! {
             #![proptest_config(ProptestConfig::with_cases(200_000))]
             
             #[test]
             fn integer_roundtrip(
-                int in !INT_BOUND .. INT_BOUND,
+                int in -INT_BOUND ..= INT_BOUND,
             ) {
                 prop_assume!(int != 0);
-                let float = int as $f;
+                let float = $f::from_f64(int as f64);
                 assert_eq!(
                     unsafe { Result::new(float) },
                     Result{
@@ -633,6 +922,19 @@ mod tests {
                     }
                 )
             }
+
+            #[test]
+            fn remove_trailing_zeros_exact(
+                mant in 1 ..= Mant::MAX,
+                zeros in 0u32 .. 20,
+            ) {
+                // Append zeros while it fits, so that there is often more than one.
+                let mant = (0 .. zeros).fold(mant, |mant, _| mant.checked_mul(10).unwrap_or(mant));
+                assert_eq!(
+                    Decimal{ exp: 0, mant }.remove_trailing_zeros(),
+                    remove_trailing_zeros_naive(mant),
+                );
+            }
             
             /*#[test]
             fn float_roundtrip(
@@ -668,8 +970,8 @@ mod tests {
             assert_eq!(crate::Buffer::new().format(-num), str_neg.as_str());
             assert_eq!(crate::Buffer::new().format_finite(-num), str_neg.as_str());
 
-            assert_eq!(crate::Buffer::new().format(num), ryu::Buffer::new().format(num));
-            assert_eq!(crate::Buffer::new().format(-num), ryu::Buffer::new().format(-num));
+            assert_eq!(crate::Buffer::new().format(num), num.ryu());
+            assert_eq!(crate::Buffer::new().format(-num), (-num).ryu());
         }
 
         /// Aux function, assert that `num` is serialised as `str` via `format_exp{,_finite}`.
@@ -722,56 +1024,83 @@ mod tests {
 
         #[test]
         fn general() {
-            assert_finite(1234e-30, "1.234e-27");
-            assert_finite(1234e-6, "0.001234");
-            assert_finite(1234e-4, "0.1234");
-            assert_finite(1234e-2, "12.34");
-            assert_finite(1234e0, "1234.0");
-            assert_finite(1234e+2, "123400.0");
-            assert_finite(1234e+7, "12340000000.0");
-            assert_finite(1234e+30, "1.234e33");
-            assert_finite(1e30, "1e30");
-            if core::mem::size_of::<$f>() >= 64 {
-                assert_finite(1234e+12, "1234000000000000.0");
-                assert_finite(1234567890123456.0, "1234567890123456.0");
-                assert_finite(1000000000000000.0, "1000000000000000.0");
+            if core::mem::size_of::<$f>() >= 4 {
+                assert_finite($f::from_f64(1234e-30), "1.234e-27");
+                assert_finite($f::from_f64(1234e-6), "0.001234");
+                assert_finite($f::from_f64(1234e-4), "0.1234");
+                assert_finite($f::from_f64(1234e-2), "12.34");
+                assert_finite($f::from_f64(1234e0), "1234.0");
+                assert_finite($f::from_f64(1234e+2), "123400.0");
+                assert_finite($f::from_f64(1234e+7), "12340000000.0");
+                assert_finite($f::from_f64(1234e+30), "1.234e33");
+                assert_finite($f::from_f64(1e30), "1e30");
+            }
+            if core::mem::size_of::<$f>() >= 8 {
+                assert_finite($f::from_f64(1234e+12), "1234000000000000.0");
+                assert_finite($f::from_f64(1234567890123456.0), "1234567890123456.0");
+                assert_finite($f::from_f64(1000000000000000.0), "1000000000000000.0");
+                assert_finite($f::from_f64(1234567800000000.0), "1234567800000000.0");
+                assert_finite($f::from_f64(123456789000000.0), "123456789000000.0");
+            }
+        }
+
+        /// Numbers with 16 and 17 digits around `decimal_exp == 16`, the largest written in
+        /// decimal notation.
+        #[test]
+        #[allow(clippy::excessive_precision)]
+        fn general_boundary() {
+            if core::mem::size_of::<$f>() >= 8 {
+                // `decimal_exp == 15`
+                assert_finite($f::from_f64(123456789012345.6), "123456789012345.6");
+                assert_finite($f::from_f64(123456789012345.67), "123456789012345.67");
+                assert_finite($f::from_f64(999999999999999.9), "999999999999999.9");
+                // `decimal_exp == 16`
+                assert_finite($f::from_f64(1e15), "1000000000000000.0");
+                assert_finite($f::from_f64(9999999999999998.0), "9999999999999998.0");
+                assert_finite($f::from_f64(1234567890123456.8), "1234567890123456.8");
+                // `decimal_exp == 17`
+                assert_finite($f::from_f64(1e16), "1e16");
+                assert_finite($f::from_f64(9.999999999999998e16), "9.999999999999998e16");
+                assert_finite($f::from_f64(12345678901234568.0), "1.2345678901234568e16");
             }
         }
 
         #[test]
         fn small() {
+            if core::mem::size_of::<$f>() >= 4 {
+                assert_all_finite(
+                    $f::from_f64(123.456),
+                    "123.456",
+                    "1.23456e2",
+                    "123.456",
+                );
+                assert_all_finite(
+                    $f::from_f64(0.1234),
+                    "0.1234",
+                    "1.234e-1",
+                    "0.1234",
+                );
+                assert_all_finite(
+                    $f::from_f64(0.001234),
+                    "0.001234",
+                    "1.234e-3",
+                    "0.001234",
+                );
+            }
             assert_all_finite(
-                123.456,
-                "123.456",
-                "1.23456e2",
-                "123.456",
-            );
-            assert_all_finite(
-                0.1234,
-                "0.1234",
-                "1.234e-1",
-                "0.1234",
-            );
-            assert_all_finite(
-                0.001234,
-                "0.001234",
-                "1.234e-3",
-                "0.001234",
-            );
-            assert_all_finite(
-                core::$f::consts::PI,
+                $f::PI,
                 $pi_dec,
                 $pi_exp,
                 $pi_dec,
             );
             assert_all_finite(
-                core::$f::consts::E,
+                $f::E,
                 $e_dec,
                 $e_exp,
                 $e_dec,
             );
             assert_all_finite(
-                core::$f::consts::LN_2,
+                $f::LN_2,
                 $ln2_dec,
                 $ln2_exp,
                 $ln2_dec,
@@ -780,50 +1109,210 @@ mod tests {
 
         #[test]
         fn small_integer() {
+            if core::mem::size_of::<$f>() >= 4 {
+                assert_all_finite(
+                    $f::from_f64(123456.),
+                    "123456.0",
+                    "1.23456e5",
+                    "123456.0",
+                );
+                assert_all_finite(
+                    $f::from_f64(12003400.),
+                    "12003400.0",
+                    "1.20034e7",
+                    "12003400.0",
+                );
+            }
             assert_all_finite(
-                123456.,
-                "123456.0",
-                "1.23456e5",
-                "123456.0",
-            );
-            assert_all_finite(
-                1.,
+                $f::from_f64(1.),
                 "1.0",
                 "1e0",
                 "1.0",
             );
-            assert_all_finite(
-                12003400.,
-                "12003400.0",
-                "1.20034e7",
-                "12003400.0",
-            );
         }
 
         #[test]
         fn extremes() {
-            assert_all_finite(0.0,
+            assert_all_finite($f::from_f64(0.0),
                 "0.0",
                 "0e0",
                 "0.0",
             );
             assert_all_finite($f::from_bits(1),
-                $min_subnormal_exp,
+                $min_subnormal_general,
                 $min_subnormal_exp,
                 $min_subnormal_dec,
             );
             assert_all_finite($f::MIN_POSITIVE,
-                $min_normal_exp,
+                $min_normal_general,
                 $min_normal_exp,
                 $min_normal_dec,
             );
             assert_all_finite($f::MAX,
-                $max_exp,
+                $max_general,
                 $max_exp,
                 $max_dec,
             );
         }
 
+        #[test]
+        fn ryu_compatible() {
+            let (one, one_and_a_half, ten) = ($f::from_f64(1.), $f::from_f64(1.5), $f::from_f64(10.));
+            let mut pow10 = one;
+            for _ in 0..20 {
+                for num in [pow10, one_and_a_half * pow10, one / pow10, one_and_a_half / pow10] {
+                    for num in [num, -num] {
+                        assert_eq!(crate::Buffer::new().format_ryu_compatible(num), num.ryu());
+                    }
+                }
+                pow10 *= ten;
+            }
+            let zeros = [$f::from_f64(0.0), $f::from_f64(-0.0)];
+            for num in zeros.into_iter().chain([$f::MAX, $f::MIN_POSITIVE, $f::from_bits(1), $f::NAN, $f::INFINITY, $f::NEG_INFINITY]) {
+                assert_eq!(crate::Buffer::new().format_ryu_compatible(num), num.ryu());
+            }
+        }
+
+        #[test]
+        fn exp_min_sig() {
+            let mut buf = crate::Buffer::new();
+            assert_eq!(buf.format_exp_min_sig($f::from_f64(5.0), 2), "5.0e0");
+            assert_eq!(buf.format_exp_min_sig($f::from_f64(-5.0), 3), "-5.00e0");
+            if core::mem::size_of::<$f>() >= 4 {
+                assert_eq!(buf.format_exp_min_sig($f::from_f64(1.5e-7), 4), "1.500e-7");
+            }
+            assert_eq!(buf.format_exp_min_sig($f::from_f64(1200.0), 4), "1.200e3");
+            assert_eq!(buf.format_exp_min_sig($f::from_f64(0.0), 2), "0.0e0");
+            assert_eq!(buf.format_exp_min_sig($f::from_f64(-0.0), 1), "-0e0");
+            assert_eq!(buf.format_exp_min_sig($f::PI, 2), $pi_exp);
+            assert_eq!(buf.format_exp_min_sig($f::MAX, 0), $max_exp);
+            // Capped at 17 digits: sign, 17 digits, point, then the unpadded `e` and exponent
+            let str = buf.format_exp_min_sig(-$f::from_bits(1), 100);
+            assert_eq!(str.len(), 1 + 17 + 1 + $min_subnormal_exp.len() - 1);
+            assert_eq!(buf.format_exp_min_sig($f::NEG_INFINITY, 2), "-inf");
+        }
+
+        /// Formats numbers around every power of 10 (which have the longest mantissas and the
+        /// most zero padding) in every mode, in debug mode to check that no routine writes past
+        /// its buffer.
+        #[test]
+        fn extreme_magnitudes() {
+            let locale = crate::LocaleCfg { decimal_sep: b'.', group_sep: b'_', grouping: &[1] };
+            for exp in $f::MIN_10_EXP - 20 ..= $f::MAX_10_EXP {
+                let pow10 = format!("1e{exp}").parse::<$f>().unwrap();
+                for bits in [pow10.to_bits().saturating_sub(1), pow10.to_bits(), pow10.to_bits() + 1] {
+                    let num = $f::from_bits(bits);
+                    if !num.is_finite() || num == $f::from_f64(0.0) {
+                        continue
+                    }
+                    for num in [num, -num] {
+                        for str in [
+                            crate::Buffer::new().format(num),
+                            crate::Buffer::new().format_exp(num),
+                            crate::Buffer::new().format_dec(num),
+                            crate::Buffer::new().format_exp_min_sig(num, 17),
+                            crate::Buffer::new().format_ryu_compatible(num),
+                            &crate::Buffer::new().format_locale(num, locale).replace('_', ""),
+                        ] {
+                            assert_eq!(str.parse::<$f>().expect(str), num);
+                        }
+                    }
+                }
+            }
+        }
+
+        /// Single-digit mantissas, e.g. those of powers of ten, are printed without a decimal point
+        /// in scientific notation, and longer ones with exactly one.
+        #[test]
+        fn exp_decimal_point() {
+            for exp in $f::MIN_10_EXP - 20 ..= $f::MAX_10_EXP {
+                let pow10 = format!("1e{exp}").parse::<$f>().unwrap();
+                // The subnormals of 16-bit floats are too far apart to have powers of ten as their
+                // shortest representations.
+                let subnormal = pow10 < $f::MIN_POSITIVE && core::mem::size_of::<$f>() < 4;
+                if pow10 == $f::from_f64(0.0) || subnormal {
+                    continue
+                }
+                assert_exp_finite(pow10, &format!("1e{exp}"));
+                let num = $f::from_bits(pow10.to_bits() + 1);
+                let str = crate::Buffer::new().format_exp(num).to_owned();
+                let mant = str.split_once('e').unwrap().0;
+                assert!(mant.len() == 1 || mant.matches('.').count() == 1 && mant.as_bytes()[1] == b'.', "{str}");
+            }
+        }
+
+        #[test]
+        fn single_digit_integers() {
+            for int in 1 ..= 9 {
+                let num = $f::from_f64(int as f64);
+                assert_eq!(crate::Buffer::new().format(num), format!("{int}.0"));
+                assert_eq!(crate::Buffer::new().format(-num), format!("-{int}.0"));
+            }
+            assert_eq!(crate::Buffer::new().format($f::from_f64(10.0)), "10.0");
+            assert_eq!(crate::Buffer::new().format($f::from_f64(9.5)), "9.5");
+            let above_nine = $f::from_bits($f::from_f64(9.0).to_bits() + 1);
+            assert_eq!(crate::Buffer::new().format(above_nine), above_nine.ryu());
+        }
+
+        #[test]
+        fn limits() {
+            use float::Sealed;
+            let limits = $f::LIMITS;
+            let max = crate::Buffer::new().format_dec($f::MAX).to_owned();
+            assert_eq!(max.strip_suffix(".0").unwrap().len(), limits.max_int_digits);
+            let min = crate::Buffer::new().format_dec($f::from_bits(1)).to_owned();
+            assert_eq!(min.trim_start_matches("0.").trim_end_matches(|c| c != '0').len(), limits.max_frac_zeros);
+            let exp = crate::Buffer::new().format_exp($f::from_bits(1)).to_owned();
+            assert_eq!(exp.split_once('e').unwrap().1.len(), limits.exp);
+            let mant = crate::Buffer::new().format_exp($f::MAX).to_owned();
+            assert!(mant.split_once('e').unwrap().0.replace('.', "").len() <= limits.mant);
+            let exact_int = $f::from_bits($f::EXACT_INT_LIMIT_BITS as Mant);
+            let exact_int = crate::Buffer::new().format_dec(exact_int).to_owned();
+            let exact_int = exact_int.strip_suffix(".0").unwrap();
+            assert_eq!(exact_int, (1u128 << ($f::MANTISSA_DIGITS + 1)).to_string());
+            assert_eq!(exact_int.len(), limits.exact_int_digits);
+            let max_num = (1u64 << $f::MANTISSA_DIGITS) - 1;
+            assert_eq!(max_num.to_string().len(), limits.frac_num_digits);
+            let (_, min_exp) = unsafe { $f::from_bits(1).to_binary_finite_nonzero() };
+            let max_den = 1u64 << min_exp.unsigned_abs().min(63);
+            assert_eq!(max_den.to_string().len(), limits.frac_den_digits);
+
+            type Scientific = crate::Buffer<$f, crate::format::Scientific>;
+            type Dec = crate::Buffer<$f, crate::format::Decimal>;
+            type Locale = crate::Buffer<$f, crate::format::Locale>;
+            assert_eq!(Scientific::MAX_LEN, crate::teju::format::len_exp(limits));
+            assert!(Scientific::MAX_LEN <= crate::teju::format::LEN_EXP);
+            assert_eq!(Dec::MAX_LEN, crate::teju::format::len_dec(limits));
+            assert_eq!(Locale::MAX_LEN, crate::teju::format::len_locale(limits));
+            assert!(Dec::new().format_dec(-$f::from_bits(1)).len() <= Dec::MAX_LEN);
+        }
+
+        /// Checks every subnormal of `f32` and smaller types, and an evenly spaced sample of as many
+        /// subnormals of `f64`, against `ryu`, which is known to print the shortest correctly
+        /// rounded digits.
+        #[test]
+        fn subnormals() {
+            // The bits of the subnormals are exactly those below the implicit bit.
+            const COUNT: Mant = 1 << Binary::BITS_MANTISSA_EXPLICIT;
+            const SAMPLE: u64 = 1 << 23;
+            // Not a power of 2, so that the low bits vary.
+            const STEP: u64 = if COUNT as u64 <= SAMPLE { 1 } else { COUNT as u64 / SAMPLE + 1 };
+            for bits in (1 .. COUNT).step_by(STEP as usize) {
+                let float = $f::from_bits(bits);
+                assert_eq!(crate::Buffer::new().format_ryu_compatible(float), float.ryu());
+                assert_eq!(crate::Buffer::new().format_exp(float).parse::<$f>().unwrap(), float);
+            }
+        }
+
+        /// Checks every normal power of 2, whose rounding interval is uncentered, against `ryu`.
+        #[test]
+        fn powers_of_two() {
+            for exp in 1 .. Binary::INF_BITS >> Binary::BITS_MANTISSA_EXPLICIT {
+                let float = $f::from_bits(exp << Binary::BITS_MANTISSA_EXPLICIT);
+                assert_eq!(crate::Buffer::new().format_ryu_compatible(float), float.ryu());
+            }
+        }
+
         #[test]
         fn specials() {
             for (value, str) in [
@@ -866,7 +1355,7 @@ mod tests {
 
             #[test]
             fn float_roundtrip_general(
-                float in $f::MIN .. $f::MAX,
+                float in $f::finite(),
             ) {
                 let mut buf = crate::Buffer::new();
                 let str = buf.format(float);
@@ -876,7 +1365,7 @@ mod tests {
             
             #[test]
             fn float_roundtrip_exp(
-                float in $f::MIN .. $f::MAX,
+                float in $f::finite(),
             ) {
                 let mut buf = crate::Buffer::new();
                 let str = buf.format_exp(float);
@@ -886,7 +1375,7 @@ mod tests {
             
             #[test]
             fn float_roundtrip_dec(
-                float in $f::MIN .. $f::MAX,
+                float in $f::finite(),
             ) {
                 let mut buf = crate::Buffer::new();
                 let str = buf.format_dec(float);
@@ -896,17 +1385,173 @@ mod tests {
 
             #[test]
             fn ryu(
-                float in $f::MIN .. $f::MAX
+                float in $f::finite()
             ) {
-                assert_eq!(
-                    crate::Buffer::new().format(float),
-                    ryu::Buffer::new().format(float),
-                )
+                assert_eq!(crate::Buffer::new().format(float), float.ryu())
+            }
+        }
+
+        /// Number of significant digits in a finite number printed in any notation, e.g. `3` for
+        /// `-1.23e-5`, `0.0000123`, and `12300.0`.
+        fn significant_digits(str: &str) -> usize {
+            let mant = str.split('e').next().unwrap();
+            let digits = mant.trim_start_matches('-').replace('.', "");
+            digits.trim_start_matches('0').trim_end_matches('0').len()
+        }
+
+        /// Whether `teju` and `std` are the same number printed with different digits because it is
+        /// exactly halfway between two shortest representations, e.g. `203492458983362.625`: teju
+        /// rounds the last digit to even, `std` rounds it up.
+        fn is_tie_rounded_up(teju: &str, std: &str) -> bool {
+            let (teju_last, std_last) = (teju.as_bytes()[teju.len() - 1], std.as_bytes()[std.len() - 1]);
+            teju.len() == std.len()
+                && teju[.. teju.len() - 1] == std[.. std.len() - 1]
+                && teju_last % 2 == 0
+                && std_last == teju_last + 1
+        }
+
+        /// Exact cases in which [Buffer::format](crate::Buffer::format) differs from `std`'s
+        /// `{:?}`, and [Buffer::format_dec](crate::Buffer::format_dec) from `{}` (which doesn't print
+        /// `.0` for integers).
+        #[test]
+        #[allow(clippy::excessive_precision)]
+        fn std_differences() {
+            // The `Debug` and `Display` impls of `half` print the value widened to `f32`.
+            if core::mem::size_of::<$f>() < 4 {
+                return
+            }
+            let num = $f::from_f64;
+            // `1e-5 <= |num| < 1e-4` is printed in decimal notation by teju.
+            assert_eq!(crate::Buffer::new().format(num(1.5e-5)), "0.000015");
+            assert_eq!(format!("{:?}", num(1.5e-5)), "1.5e-5");
+            assert_eq!(crate::Buffer::new().format(num(-1e-5)), "-0.00001");
+            assert_eq!(format!("{:?}", num(-1e-5)), "-1e-5");
+            assert_eq!(crate::Buffer::new().format(num(1e-4)), format!("{:?}", num(1e-4)));
+            assert_eq!(crate::Buffer::new().format(num(1e-6)), format!("{:?}", num(1e-6)));
+            // Ties between two shortest representations are rounded to even by teju, and up by std.
+            if core::mem::size_of::<$f>() >= 8 {
+                let num = num(203492458983362.625);
+                assert_eq!(crate::Buffer::new().format(num), "203492458983362.62");
+                assert_eq!(format!("{num:?}"), "203492458983362.63");
+                assert_eq!(crate::Buffer::new().format_dec(num), "203492458983362.62");
+                assert_eq!(format!("{num}"), "203492458983362.63");
+            }
+        }
+
+        proptest! {
+            // 16-bit floats have enough non-finite values to exceed the default limit of rejects.
+            #![proptest_config(ProptestConfig {
+                max_global_rejects: 100_000,
+                ..ProptestConfig::with_cases(100_000)
+            })]
+
+            #[test]
+            fn ryu_compatible_bits(
+                bits in any::<Mant>(),
+            ) {
+                let float = $f::from_bits(bits);
+                assert_eq!(crate::Buffer::new().format_ryu_compatible(float), float.ryu())
+            }
+
+            /// Every mode parses back to the same bits, over all finite values (including zeros and
+            /// subnormals, which the tests above rarely hit).
+            #[test]
+            fn modes_agree(
+                bits in any::<Mant>(),
+            ) {
+                let float = $f::from_bits(bits);
+                prop_assume!(float.is_finite());
+                let general = crate::Buffer::new().format(float).parse::<$f>().unwrap();
+                let exp = crate::Buffer::new().format_exp(float).parse::<$f>().unwrap();
+                let dec = crate::Buffer::new().format_dec(float).parse::<$f>().unwrap();
+                assert_eq!(general.to_bits(), bits);
+                assert_eq!(exp.to_bits(), bits);
+                assert_eq!(dec.to_bits(), bits);
+            }
+
+            #[test]
+            fn shortest_as_ryu(
+                bits in any::<Mant>(),
+            ) {
+                let float = $f::from_bits(bits);
+                prop_assume!(float.is_finite() && float != $f::from_f64(0.0));
+                let ryu = float.ryu();
+                assert_eq!(significant_digits(crate::Buffer::new().format_exp(float)), significant_digits(&ryu));
+                assert_eq!(significant_digits(crate::Buffer::new().format(float)), significant_digits(&ryu));
+                assert_eq!(significant_digits(crate::Buffer::new().format_dec(float)), significant_digits(&ryu));
+            }
+
+            #[test]
+            fn std_compatible(
+                bits in any::<Mant>(),
+            ) {
+                // See `std_differences`.
+                if core::mem::size_of::<$f>() < 4 {
+                    return Ok(())
+                }
+                let float = $f::from_bits(bits);
+                let teju = crate::Buffer::new().format(float).to_owned();
+                let std = format!("{float:?}");
+                let abs = TestFloat::abs(float);
+                let in_decimal_range = $f::from_f64(1e-5) <= abs && abs < $f::from_f64(1e-4);
+                let same_number = || teju.parse::<$f>().unwrap() == std.parse::<$f>().unwrap();
+                assert!(teju == std || is_tie_rounded_up(&teju, &std) || (in_decimal_range && same_number()), "{teju} {std}");
+
+                let teju = crate::Buffer::new().format_dec(float).to_owned();
+                let teju = teju.strip_suffix(".0").unwrap_or(&teju);
+                let std = format!("{float}");
+                assert!(teju == std || is_tie_rounded_up(teju, &std), "{teju} {std}");
+            }
+
+            #[test]
+            fn ryu_compatible_subnormals(
+                bits in 1 .. (1 as Mant) << Binary::BITS_MANTISSA,
+            ) {
+                let float = $f::from_bits(bits);
+                assert_eq!(crate::Buffer::new().format_ryu_compatible(float), float.ryu())
+            }
+
+            #[test]
+            fn float_roundtrip_exp_min_sig(
+                float in $f::finite(),
+                min_sig in 0usize ..= 17,
+            ) {
+                let mut buf = crate::Buffer::new();
+                let str = buf.format_exp_min_sig(float, min_sig);
+                assert_eq!(str.parse::<$f>().unwrap(), float);
+                let mant = str.trim_start_matches('-').split('e').next().unwrap();
+                assert!(mant.len() - mant.contains('.') as usize >= min_sig);
+            }
+
+            #[test]
+            fn no_trailing_zeros(
+                bits in any::<Mant>(),
+            ) {
+                let float = $f::from_bits(bits);
+                prop_assume!(float.is_finite());
+                let mut buf_general = crate::Buffer::new();
+                let mut buf_exp = crate::Buffer::new();
+                let mut buf_dec = crate::Buffer::new();
+                let mut buf_round = crate::Buffer::new();
+                let strs = [
+                    buf_general.format(float),
+                    buf_exp.format_exp(float),
+                    buf_dec.format_dec(float),
+                    buf_round.format_round(float, usize::MAX, true),
+                ];
+                for str in strs {
+                    let mant = str.split('e').next().unwrap();
+                    if let Some((_, frac)) = mant.split_once('.') {
+                        assert!(frac == "0" || !frac.ends_with('0'), "{str}");
+                    }
+                }
+                assert_eq!(strs[3], strs[2]);
             }
         }
     }
 }
 
+)?
 }} // mk_impl
 
 pub(crate) use mk_impl;
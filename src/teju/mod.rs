@@ -1,9 +1,17 @@
-mod common;
-mod fmt;
+pub(crate) mod common;
+pub(crate) mod fixed;
+pub(crate) mod fmt;
 mod mk_impl;
 
 mod f32;
 mod f64;
+#[cfg(feature = "half")]
+mod f16;
+#[cfg(feature = "half")]
+mod bf16;
+
+#[cfg(test)]
+mod test_float;
 
 pub mod float;
 pub mod format;
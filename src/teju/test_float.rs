@@ -0,0 +1,110 @@
+//! What the tests shared by every float type (see [mk_impl](super::mk_impl)) need, but which the
+//! 16-bit types of `half` lack: `as` casts, some inherent methods, `proptest` strategies, and
+//! `ryu`.
+
+use proptest::prelude::*;
+
+pub(crate) trait TestFloat: Copy + Sized {
+    const PI: Self;
+    const E: Self;
+    const LN_2: Self;
+
+    /// `x` rounded to this type, as `x as Self`.
+    fn from_f64(x: f64) -> Self;
+    fn abs(self) -> Self;
+    /// Finite values, from `MIN` to `MAX`.
+    fn finite() -> BoxedStrategy<Self>;
+    /// As printed by `ryu::Buffer::format`.
+    fn ryu(self) -> String;
+}
+
+macro_rules! impl_primitive {
+    ($f:ident) => {
+        impl TestFloat for $f {
+            const PI: Self = core::$f::consts::PI;
+            const E: Self = core::$f::consts::E;
+            const LN_2: Self = core::$f::consts::LN_2;
+
+            fn from_f64(x: f64) -> Self {
+                x as $f
+            }
+
+            fn abs(self) -> Self {
+                $f::abs(self)
+            }
+
+            fn finite() -> BoxedStrategy<Self> {
+                ($f::MIN .. $f::MAX).boxed()
+            }
+
+            fn ryu(self) -> String {
+                ryu::Buffer::new().format(self).to_owned()
+            }
+        }
+    };
+}
+
+impl_primitive!(f32);
+impl_primitive!(f64);
+
+/// `ryu` doesn't support 16-bit floats, so their shortest digits are found by brute force: the
+/// next power of 10 if it parses back to `x`, else the digits of `x` correctly rounded to `1, 2,
+/// ...` significant digits, or one unit in the last place off, if the rounding interval of `x` is
+/// uneven (powers of 2). These are then printed as `ryu` prints the `f32` they parse to, which has
+/// the same shortest digits.
+#[cfg(feature = "half")]
+fn ryu_16(x: f64, to_f32: impl Fn(&str) -> Option<f32>) -> String {
+    if !x.is_finite() || x == 0.0 {
+        return ryu::Buffer::new().format(x as f32).to_owned()
+    }
+    let exp = format!("{x:e}").split_once('e').unwrap().1.parse::<i32>().unwrap();
+    if let Some(parsed) = to_f32(&format!("1e{}", exp + 1)) {
+        return ryu::Buffer::new().format(parsed.copysign(x as f32)).to_owned()
+    }
+    for digits in 1 .. 17 {
+        let rounded = format!("{:.*e}", digits - 1, x.abs());
+        let (mant, exp) = rounded.split_once('e').unwrap();
+        let mant = mant.replace('.', "").parse::<u64>().unwrap();
+        let exp = exp.parse::<i32>().unwrap() - (digits - 1) as i32;
+        for mant in [mant, mant - 1, mant + 1] {
+            if let Some(parsed) = to_f32(&format!("{mant}e{exp}")) {
+                return ryu::Buffer::new().format(parsed.copysign(x as f32)).to_owned()
+            }
+        }
+    }
+    unreachable!("{x} has no shortest representation")
+}
+
+macro_rules! impl_half {
+    ($f:ident) => {
+        #[cfg(feature = "half")]
+        impl TestFloat for half::$f {
+            const PI: Self = half::$f::PI;
+            const E: Self = half::$f::E;
+            const LN_2: Self = half::$f::LN_2;
+
+            fn from_f64(x: f64) -> Self {
+                half::$f::from_f64(x)
+            }
+
+            fn abs(self) -> Self {
+                half::$f::from_bits(self.to_bits() & 0x7fff)
+            }
+
+            fn finite() -> BoxedStrategy<Self> {
+                (half::$f::MIN.to_f32() ..= half::$f::MAX.to_f32()).prop_map(half::$f::from_f32).boxed()
+            }
+
+            fn ryu(self) -> String {
+                let abs = TestFloat::abs(self);
+                ryu_16(self.to_f64(), |str| {
+                    let parsed = str.parse::<half::$f>().unwrap();
+                    (parsed == abs).then(|| str.parse().unwrap())
+                })
+            }
+        }
+    };
+}
+
+impl_half!(f16);
+impl_half!(bf16);
@@ -0,0 +1,149 @@
+//! Assertions for testing code which formats floats with [teju](crate), e.g. against a corpus of
+//! expected strings. These are the same checks which [teju](crate) runs on its own test cases.
+//!
+//! Requires the `test-util` feature.
+
+use core::fmt::Debug;
+use core::ops::Neg;
+use core::str::FromStr;
+
+use crate::{Buffer, Float};
+
+/// Assert that the string `neg` is the string `pos` with its sign flipped.
+fn assert_negated(pos: &str, neg: &str) {
+    let flipped = match pos.strip_prefix('-') {
+        Some(unsigned) => neg == unsigned,
+        None => neg.strip_prefix('-') == Some(pos),
+    };
+    assert!(flipped, "{neg:?} is not the negation of {pos:?}");
+}
+
+/// Assert that `num` is formatted as `general` by [Buffer::format], as `exp` by
+/// [Buffer::format_exp], and as `dec` by [Buffer::format_dec], as well as by their `_finite`
+/// variants; and that `-num` is formatted as the same strings with the sign flipped.
+///
+/// Also asserts that the expected strings are correct, i.e. that they parse back to `num`.
+///
+/// ## Panics
+///
+/// Panics if any of the above fails, or if `num` is not finite.
+///
+/// ```
+/// teju::test_util::assert_formats(1234e-6, "0.001234", "1.234e-3", "0.001234");
+/// teju::test_util::assert_formats(-1e30f32, "-1e30", "-1e30", "-1000000000000000000000000000000.0");
+/// ```
+pub fn assert_formats<F>(num: F, general: &str, exp: &str, dec: &str)
+where
+    F: Float + FromStr + Neg<Output = F> + PartialEq + Debug,
+    F::Err: Debug,
+{
+    assert!(matches!(num.classify(), crate::teju::float::FloatType::Finite), "{num:?} is not finite");
+    for str in [general, exp, dec] {
+        assert_eq!(str.parse::<F>().unwrap(), num, "Incorrect test case {str:?} for {num:?}");
+    }
+
+    assert_eq!(Buffer::new().format(num), general);
+    assert_eq!(Buffer::new().format_finite(num), general);
+    assert_eq!(Buffer::new().format_exp(num), exp);
+    assert_eq!(Buffer::new().format_exp_finite(num), exp);
+    assert_eq!(Buffer::new().format_dec(num), dec);
+    assert_eq!(Buffer::new().format_dec_finite(num), dec);
+
+    assert_negated(general, Buffer::new().format(-num));
+    assert_negated(general, Buffer::new().format_finite(-num));
+    assert_negated(exp, Buffer::new().format_exp(-num));
+    assert_negated(exp, Buffer::new().format_exp_finite(-num));
+    assert_negated(dec, Buffer::new().format_dec(-num));
+    assert_negated(dec, Buffer::new().format_dec_finite(-num));
+}
+
+/// Check that every formatting mode prints `num` as a string which parses back to `num`,
+/// including the sign of zero. By convention, returns `true` if `num` is infinite or NaN (which
+/// are printed as the special strings `"inf"`, `"-inf"`, and `"NaN"`).
+///
+/// ```
+/// assert!(teju::roundtrips(0.1));
+/// assert!(teju::roundtrips(-0.0f32));
+/// assert!(teju::roundtrips(f64::NAN));
+/// ```
+pub fn roundtrips<F>(num: F) -> bool
+where
+    F: Float + FromStr + PartialEq,
+{
+    if !matches!(num.classify(), crate::teju::float::FloatType::Finite) {
+        return true
+    }
+    parses_to(Buffer::new().format(num), num)
+        && parses_to(Buffer::new().format_exp(num), num)
+        && parses_to(Buffer::new().format_dec(num), num)
+}
+
+/// Whether `str` parses to `num`, with the same sign, which `==` does not compare for zero.
+fn parses_to<F>(str: &str, num: F) -> bool
+where
+    F: Float + FromStr + PartialEq,
+{
+    str.parse::<F>().is_ok_and(|parsed| parsed == num && parsed.is_sign_negative() == num.is_sign_negative())
+}
+
+/// Assert that [roundtrips] holds for `num`, and that [Buffer::format_ryu_compatible] matches
+/// [`ryu`](https://docs.rs/ryu/).
+///
+/// ## Panics
+///
+/// Panics if any of the above fails.
+///
+/// ```
+/// for num in [0.1, -2.5e-300, f64::MAX, f64::NAN] {
+///     teju::test_util::assert_roundtrip(num);
+/// }
+/// ```
+pub fn assert_roundtrip<F>(num: F)
+where
+    F: Float + ryu::Float + FromStr + PartialEq + Debug,
+{
+    assert!(roundtrips(num), "{num:?} does not round-trip");
+    assert_eq!(Buffer::new().format_ryu_compatible(num), ryu::Buffer::new().format(num));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats() {
+        assert_formats(1.5f64, "1.5", "1.5e0", "1.5");
+        assert_formats(-1.5f32, "-1.5", "-1.5e0", "-1.5");
+        assert_formats(0.0f64, "0.0", "0e0", "0.0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn formats_wrong() {
+        assert_formats(1.5f64, "1.5", "15e-1", "1.5");
+    }
+
+    #[test]
+    #[should_panic]
+    fn formats_incorrect_case() {
+        assert_formats(1.5f64, "1.6", "1.6e0", "1.6");
+    }
+
+    #[test]
+    fn roundtrips_sign_of_zero() {
+        assert!(roundtrips(-0.0f64));
+        assert!(roundtrips(0.0f32));
+        assert!(roundtrips(f32::NEG_INFINITY));
+        assert!(parses_to("-0.0", -0.0f64));
+        assert!(!parses_to("-0.0", 0.0f64));
+        assert!(!parses_to("0e0", -0.0f32));
+    }
+
+    #[test]
+    fn roundtrip() {
+        for num in [1.5, -0.0, 5e-324, f64::MAX, f64::INFINITY, f64::NAN] {
+            assert_roundtrip(num);
+            assert_roundtrip(num as f32);
+        }
+    }
+}
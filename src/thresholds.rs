@@ -0,0 +1,167 @@
+//! General notation with custom thresholds for switching to scientific notation.
+
+use crate::{Buffer, Float, teju};
+use teju::format;
+
+/// The range of magnitudes which [Buffer::format_with_thresholds] writes in decimal notation.
+///
+/// Numbers are written in decimal notation if `low < decimal_exp <= high`, and in scientific
+/// notation otherwise, where `decimal_exp` is the exponent `e` such that the number is
+/// `0.d₁d₂d₃... × 10^e`. Equivalently, for a number `x`, decimal notation is used if
+/// `10^low <= |x| < 10^high`.
+///
+/// The default thresholds, `-5` and `16`, are those of [Buffer::format].
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub struct GeneralThresholds {
+    low: i32,
+    high: i32,
+}
+
+impl GeneralThresholds {
+    /// The thresholds `low` and `high`, or an error if not `low <= 0 < high`.
+    ///
+    /// ```
+    /// let thresholds = teju::GeneralThresholds::new(-3, 21).unwrap();
+    /// assert_eq!((thresholds.low(), thresholds.high()), (-3, 21));
+    /// assert_eq!(teju::GeneralThresholds::new(1, 16), Err(teju::InvalidThresholds { low: 1, high: 16 }));
+    /// ```
+    pub const fn new(low: i32, high: i32) -> Result<Self, InvalidThresholds> {
+        if low <= 0 && 0 < high {
+            Ok(GeneralThresholds { low, high })
+        } else {
+            Err(InvalidThresholds { low, high })
+        }
+    }
+
+    pub const fn low(&self) -> i32 {
+        self.low
+    }
+
+    pub const fn high(&self) -> i32 {
+        self.high
+    }
+}
+
+impl Default for GeneralThresholds {
+    fn default() -> Self {
+        GeneralThresholds { low: format::GENERAL_LOW, high: format::GENERAL_HIGH }
+    }
+}
+
+/// The thresholds passed to [GeneralThresholds::new] do not satisfy `low <= 0 < high`.
+///
+/// ```
+/// let err = teju::GeneralThresholds::new(0, 0).unwrap_err();
+/// assert_eq!(err.to_string(), "thresholds 0 and 0 do not satisfy `low <= 0 < high`");
+/// ```
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub struct InvalidThresholds {
+    /// The `low` threshold passed.
+    pub low: i32,
+    /// The `high` threshold passed.
+    pub high: i32,
+}
+
+impl core::fmt::Display for InvalidThresholds {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "thresholds {} and {} do not satisfy `low <= 0 < high`", self.low, self.high)
+    }
+}
+
+impl core::error::Error for InvalidThresholds {}
+
+impl<F: Float> Buffer<F, format::Bounded> {
+    /// Print a floating point `num` into this buffer, and return a reference to its string
+    /// representation.
+    ///
+    /// The number is formatted as in [Self::format], but switching between decimal and scientific
+    /// notation at the given `thresholds`. The buffer is large enough for any thresholds.
+    ///
+    /// This function formats NaN as the string `"NaN"`, positive infinity as `"inf"`, and negative
+    /// infinity as `"-inf"`, to match [core::fmt].
+    ///
+    /// ```
+    /// let thresholds = teju::GeneralThresholds::new(-3, 21).unwrap();
+    /// assert_eq!(teju::Buffer::new().format_with_thresholds(1e20, thresholds), "100000000000000000000.0");
+    /// assert_eq!(teju::Buffer::new().format_with_thresholds(1e21, thresholds), "1e21");
+    /// assert_eq!(teju::Buffer::new().format_with_thresholds(0.001, thresholds), "0.001");
+    /// assert_eq!(teju::Buffer::new().format_with_thresholds(0.0001, thresholds), "1e-4");
+    /// ```
+    pub fn format_with_thresholds(&mut self, num: F, thresholds: GeneralThresholds) -> &str {
+        match num.classify() {
+            teju::float::FloatType::Finite => (),
+            teju::float::FloatType::PosInf => return crate::POS_INF,
+            teju::float::FloatType::NegInf => return crate::NEG_INF,
+            teju::float::FloatType::Nan => return crate::NAN,
+        }
+        match num.classify_finite() {
+            teju::float::FiniteFloatType::PosZero => return crate::POS_ZERO,
+            teju::float::FiniteFloatType::NegZero => return crate::NEG_ZERO,
+            teju::float::FiniteFloatType::Nonzero => (),
+        }
+        let ptr = <format::Bounded as teju::format::Sealed>::buffer_as_ptr::<F>(&mut self.bytes);
+        let n = unsafe {
+            num.format_general_bounded_finite_nonzero(ptr, thresholds.low, thresholds.high)
+        };
+        let slice = unsafe { core::slice::from_raw_parts(ptr, n) };
+        debug_assert!(n <= Self::MAX_LEN);
+        unsafe { core::str::from_utf8_unchecked(slice) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boundaries() {
+        let thresholds = GeneralThresholds::new(-3, 21).unwrap();
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_with_thresholds(9.5e20, thresholds), "950000000000000000000.0");
+        assert_eq!(buf.format_with_thresholds(-1e21, thresholds), "-1e21");
+        assert_eq!(buf.format_with_thresholds(1.5e21, thresholds), "1.5e21");
+        assert_eq!(buf.format_with_thresholds(0.0015, thresholds), "0.0015");
+        assert_eq!(buf.format_with_thresholds(-9.5e-4, thresholds), "-9.5e-4");
+        assert_eq!(buf.format_with_thresholds(1.5, thresholds), "1.5");
+
+        let thresholds = GeneralThresholds::new(0, 1).unwrap();
+        assert_eq!(buf.format_with_thresholds(9.5, thresholds), "9.5");
+        assert_eq!(buf.format_with_thresholds(10., thresholds), "1e1");
+        assert_eq!(buf.format_with_thresholds(0.5, thresholds), "5e-1");
+    }
+
+    #[test]
+    fn default() {
+        for num in [1e15, 1e16, 1e-4, 1e-5, 1234.5, f64::MAX, 5e-324, -0.0] {
+            assert_eq!(
+                Buffer::new().format_with_thresholds(num, GeneralThresholds::default()),
+                Buffer::new().format(num),
+            );
+            assert_eq!(
+                Buffer::new().format_with_thresholds(num as f32, GeneralThresholds::default()),
+                Buffer::new().format(num as f32),
+            );
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        let thresholds = GeneralThresholds::new(-400, 400).unwrap();
+        for num in [f64::MAX, -f64::MAX, f64::from_bits(1), f64::MIN_POSITIVE] {
+            assert_eq!(Buffer::new().format_with_thresholds(num, thresholds), Buffer::new().format_dec(num));
+        }
+        assert_eq!(Buffer::new().format_with_thresholds(f64::NAN, thresholds), "NaN");
+        assert_eq!(Buffer::new().format_with_thresholds(f64::NEG_INFINITY, thresholds), "-inf");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(GeneralThresholds::new(1, 16), Err(InvalidThresholds { low: 1, high: 16 }));
+        assert_eq!(GeneralThresholds::new(-5, 0), Err(InvalidThresholds { low: -5, high: 0 }));
+        assert_eq!(GeneralThresholds::new(0, 1).map(|t| (t.low(), t.high())), Ok((0, 1)));
+    }
+}
@@ -0,0 +1,77 @@
+//! TOML floats.
+
+use crate::{Buffer, Float, Specials};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Print a floating point `num` into this buffer as a TOML float, and return a reference to
+    /// its string representation.
+    ///
+    /// Finite numbers are formatted as by [Self::format], which is always a valid TOML float:
+    /// integers have a fractional part or an exponent (e.g. `1.0` or `1e16`, never `1`), there
+    /// are no leading zeros nor a leading `+`, and there is always a digit on both sides of the
+    /// decimal point. NaN and infinities are written as TOML's special floats `nan`, `inf`, and
+    /// `-inf`.
+    ///
+    /// ```
+    /// let mut buffer = teju::Buffer::new();
+    /// assert_eq!(buffer.format_toml(3.0), "3.0");
+    /// assert_eq!(buffer.format_toml(1e300), "1e300");
+    /// assert_eq!(buffer.format_toml(f64::NAN), "nan");
+    /// assert_eq!(buffer.format_toml(f64::NEG_INFINITY), "-inf");
+    /// ```
+    pub fn format_toml(&mut self, num: F) -> &str {
+        self.format_specials(num, Specials { nan: "nan", pos_inf: "inf", neg_inf: "-inf" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Parse `str` as the value of a TOML key.
+    fn parse(str: &str) -> f64 {
+        let table: toml::Table = format!("x = {str}").parse().unwrap_or_else(|e| panic!("{str}: {e}"));
+        match table["x"] {
+            toml::Value::Float(x) => x,
+            ref value => panic!("{str} is not a float: {value:?}"),
+        }
+    }
+
+    #[test]
+    fn toml() {
+        let mut buf = Buffer::new();
+        for (num, str) in [
+            (1.0f64, "1.0"), (-0.0, "-0.0"), (1e16, "1e16"), (-1.5e-7, "-1.5e-7"), (0.001, "0.001"),
+            (123456.789, "123456.789"), (5e-324, "5e-324"), (f64::MAX, "1.7976931348623157e308"),
+        ] {
+            assert_eq!(buf.format_toml(num), str);
+            assert_eq!(parse(str).to_bits(), num.to_bits());
+        }
+        assert_eq!(Buffer::new().format_toml(16777216.0f32), "16777216.0");
+    }
+
+    #[test]
+    fn specials() {
+        let mut buf = Buffer::new();
+        assert_eq!(buf.format_toml(f64::NAN), "nan");
+        assert_eq!(buf.format_toml(-f64::NAN), "nan");
+        assert_eq!(buf.format_toml(f64::INFINITY), "inf");
+        assert_eq!(buf.format_toml(f64::NEG_INFINITY), "-inf");
+        assert!(parse("nan").is_nan());
+        assert_eq!(parse("inf"), f64::INFINITY);
+        assert_eq!(parse("-inf"), f64::NEG_INFINITY);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn roundtrip(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let parsed = parse(Buffer::new().format_toml(num));
+            assert!(parsed.to_bits() == bits || num.is_nan() && parsed.is_nan());
+        }
+    }
+}
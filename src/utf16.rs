@@ -0,0 +1,63 @@
+//! Formatting into caller-provided UTF-16 slices.
+
+use crate::{Buffer, CapacityError, Float};
+use crate::teju::format;
+
+impl<F: Float> Buffer<F, format::General> {
+    /// Like [Self::format], but writes the string as UTF-16, without a byte order mark, to the
+    /// start of `out`. Returns the written part of `out`, or an error if `out` is too short, in
+    /// which case `out` is unchanged.
+    ///
+    /// The string is ASCII, so each byte is widened to one `u16`, and a slice of
+    /// [required_len](crate::required_len) `u16`s is always long enough.
+    ///
+    /// ```
+    /// let mut out = [0u16; 8];
+    /// let str = teju::Buffer::new().format_utf16(-1.5, &mut out).unwrap();
+    /// assert_eq!(String::from_utf16(str).unwrap(), "-1.5");
+    /// ```
+    pub fn format_utf16<'a>(&mut self, num: F, out: &'a mut [u16]) -> Result<&'a [u16], CapacityError> {
+        let str = self.format(num);
+        let have = out.len();
+        let dst = out.get_mut(..str.len()).ok_or(CapacityError { needed: str.len(), have })?;
+        for (d, &b) in dst.iter_mut().zip(str.as_bytes()) {
+            *d = b.into();
+        }
+        Ok(dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn utf16() {
+        let mut out = [0xffff; 8];
+        assert_eq!(Buffer::new().format_utf16(1e-7f64, &mut out), Ok(&[0x31, 0x65, 0x2d, 0x37][..]));
+        assert_eq!(out[4 ..], [0xffff; 4]);
+        assert_eq!(Buffer::new().format_utf16(f32::NEG_INFINITY, &mut out), Ok(&[0x2d, 0x69, 0x6e, 0x66][..]));
+        assert_eq!(Buffer::new().format_utf16(f64::NAN, &mut out[.. 3]), Ok(&[0x4e, 0x61, 0x4e][..]));
+    }
+
+    #[test]
+    fn too_short() {
+        let mut out = [0xffff; 4];
+        assert_eq!(Buffer::new().format_utf16(1.25e-7f64, &mut out), Err(CapacityError { needed: 7, have: 4 }));
+        assert_eq!(Buffer::new().format_utf16(0.0f64, &mut []), Err(CapacityError { needed: 3, have: 0 }));
+        assert_eq!(out, [0xffff; 4]);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn same_as_format(bits in any::<u64>()) {
+            let num = f64::from_bits(bits);
+            let mut out = [0; crate::required_len(64, crate::Style::General)];
+            let utf16 = Buffer::new().format_utf16(num, &mut out).unwrap();
+            assert_eq!(String::from_utf16(utf16).unwrap(), Buffer::new().format(num));
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Writing to a [core::fmt::Write] sink.
+
+use core::fmt;
+
+use crate::{Buffer, Float};
+
+/// Write a floating point `num` to `w` as formatted by [Buffer::format], e.g. to append it to a
+/// `String` or a [fmt::Formatter], without handling the intermediate string.
+///
+/// ```
+/// let mut line = String::from("x = ");
+/// teju::write_into(&mut line, 1.5).unwrap();
+/// teju::write_into(&mut line, 1e30f32).unwrap();
+/// assert_eq!(line, "x = 1.51e30");
+/// ```
+pub fn write_into<W: fmt::Write + ?Sized, F: Float>(w: &mut W, num: F) -> fmt::Result {
+    w.write_str(Buffer::new().format(num))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A sink which accepts at most `.0` more bytes.
+    struct Limited(usize);
+
+    impl fmt::Write for Limited {
+        fn write_str(&mut self, str: &str) -> fmt::Result {
+            self.0 = self.0.checked_sub(str.len()).ok_or(fmt::Error)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write() {
+        let mut str = String::new();
+        for num in [0.1f64, -0.0, f64::NAN, f64::NEG_INFINITY, 5e-324] {
+            write_into(&mut str, num).unwrap();
+            str.push(' ');
+        }
+        assert_eq!(str, "0.1 -0.0 NaN -inf 5e-324 ");
+        let dyn_str: &mut dyn fmt::Write = &mut str;
+        write_into(dyn_str, 0.5f32).unwrap();
+        assert!(str.ends_with(" 0.5"));
+    }
+
+    #[test]
+    fn error() {
+        assert_eq!(write_into(&mut Limited(3), 1.5f64), Ok(()));
+        assert_eq!(write_into(&mut Limited(3), 1.25f64), Err(fmt::Error));
+    }
+}